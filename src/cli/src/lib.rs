@@ -0,0 +1,511 @@
+//! Instructions, settings and output of the `spl-token-faucet` command-line utility.
+//! The binary only fetches accounts and sends transactions, so everything here runs against a test bank as well.
+
+use clap::{Arg, ArgMatches};
+use solana_clap_utils::{
+    input_parsers::{pubkey_of, value_of},
+    input_validators::is_parsable,
+};
+use solana_sdk::{
+    account::Account,
+    commitment_config::CommitmentConfig,
+    instruction::{AccountMeta, Instruction},
+    program_error::ProgramError,
+    program_option::COption,
+    program_pack::Pack,
+    pubkey::{Pubkey, MAX_SEED_LEN},
+    system_instruction,
+};
+use spl_token::instruction::AuthorityType;
+use spl_token_faucet::{
+    client::cluster::ClusterProfile,
+    find_mint_authority_address, instruction,
+    state::{Cooldown, Faucet, FaucetConfig},
+    token::{self, find_associated_token_address},
+};
+
+pub type Error = Box<dyn std::error::Error>;
+
+/// What the utility connects to and acts as
+#[derive(Debug, PartialEq)]
+pub struct Settings {
+    pub json_rpc_url: String,
+    pub keypair_path: String,
+    pub program_id: Pubkey,
+    pub commitment: CommitmentConfig,
+}
+
+/// Resolves the settings from the global arguments, which take precedence over the cluster profile,
+/// which takes precedence over the configuration file of the Solana CLI
+pub fn resolve_settings(
+    matches: &ArgMatches<'_>,
+    cli_config: &solana_cli_config::Config,
+) -> Result<Settings, Error> {
+    let cluster = match matches.value_of("cluster") {
+        Some(name) => Some(ClusterProfile::from_name(name)?),
+        None => None,
+    };
+    Ok(Settings {
+        json_rpc_url: matches
+            .value_of("json_rpc_url")
+            .map(str::to_string)
+            .or_else(|| cluster.as_ref().map(|cluster| cluster.json_rpc_url.clone()))
+            .unwrap_or_else(|| cli_config.json_rpc_url.clone()),
+        keypair_path: matches
+            .value_of("keypair")
+            .map(str::to_string)
+            .unwrap_or_else(|| cli_config.keypair_path.clone()),
+        program_id: pubkey_of(matches, "program_id")
+            .or_else(|| cluster.map(|cluster| cluster.program_id))
+            .unwrap_or_else(spl_token_faucet::id),
+        commitment: match matches.value_of("commitment") {
+            Some("recent") => CommitmentConfig::recent(),
+            Some("single") => CommitmentConfig::single(),
+            Some("singleGossip") => CommitmentConfig::single_gossip(),
+            Some("root") => CommitmentConfig::root(),
+            _ => CommitmentConfig::max(),
+        },
+    })
+}
+
+/// The faucet stored in the account, which has to be owned by the faucet program
+pub fn unpack_faucet(
+    program_id: &Pubkey,
+    address: &Pubkey,
+    account: &Account,
+) -> Result<Faucet, Error> {
+    if account.owner != *program_id {
+        return Err(format!("{} is not a faucet", address).into());
+    }
+    Ok(Faucet::unpack_versioned(&account.data)?)
+}
+
+/// The signing admin of the faucet, if the fee payer is its admin
+pub fn admin_of(payer: &Pubkey, faucet: &Faucet) -> Option<Pubkey> {
+    if faucet.admin == COption::Some(*payer) {
+        Some(*payer)
+    } else {
+        None
+    }
+}
+
+/// Labels are seeds of the faucet address and can't be longer than those
+pub fn is_valid_label(label: String) -> Result<(), String> {
+    if label.len() > MAX_SEED_LEN {
+        return Err(format!(
+            "the label is {} bytes long, it can be at most {} bytes",
+            label.len(),
+            MAX_SEED_LEN
+        ));
+    }
+    Ok(())
+}
+
+/// The limits shared by `init` and `update`
+pub fn faucet_config_args<'a, 'b>() -> Vec<Arg<'a, 'b>> {
+    vec![
+        Arg::with_name("cooldown_slots")
+            .long("cooldown-slots")
+            .value_name("SLOTS")
+            .validator(is_parsable::<u64>)
+            .conflicts_with("cooldown_seconds")
+            .help("Slots a recipient has to wait between two mints"),
+        Arg::with_name("cooldown_seconds")
+            .long("cooldown-seconds")
+            .value_name("SECONDS")
+            .validator(is_parsable::<u64>)
+            .help("Seconds a recipient has to wait between two mints"),
+        Arg::with_name("recipient_limit")
+            .long("recipient-limit")
+            .value_name("AMOUNT")
+            .validator(is_parsable::<u64>)
+            .help("Amount a recipient may receive in total"),
+        Arg::with_name("max_total_minted")
+            .long("max-total-minted")
+            .value_name("AMOUNT")
+            .validator(is_parsable::<u64>)
+            .help("Amount the faucet may mint in total"),
+    ]
+}
+
+/// The arguments only `update` takes, besides the limits shared with `init`
+pub fn update_config_args<'a, 'b>() -> Vec<Arg<'a, 'b>> {
+    vec![
+        Arg::with_name("amount")
+            .long("amount")
+            .value_name("AMOUNT")
+            .validator(is_parsable::<u64>)
+            .help("Amount anyone but the admin may mint at once, in base units of the mint"),
+        Arg::with_name("no_cooldown")
+            .long("no-cooldown")
+            .conflicts_with_all(&["cooldown_slots", "cooldown_seconds"])
+            .help("Remove the cooldown"),
+        Arg::with_name("no_recipient_limit")
+            .long("no-recipient-limit")
+            .conflicts_with("recipient_limit")
+            .help("Remove the recipient limit"),
+        Arg::with_name("no_max_total_minted")
+            .long("no-max-total-minted")
+            .conflicts_with("max_total_minted")
+            .help("Remove the limit on the total amount minted"),
+    ]
+}
+
+/// Applies the limits given on the command line to `config`
+pub fn parse_faucet_config(matches: &ArgMatches<'_>, config: &mut FaucetConfig) {
+    if let Some(amount) = value_of(matches, "amount") {
+        config.amount = amount;
+    }
+    if let Some(slots) = value_of(matches, "cooldown_slots") {
+        config.cooldown = Some(Cooldown::Slots(slots));
+    }
+    if let Some(seconds) = value_of(matches, "cooldown_seconds") {
+        config.cooldown = Some(Cooldown::Seconds(seconds));
+    }
+    if matches.is_present("no_cooldown") {
+        config.cooldown = None;
+    }
+    if let Some(limit) = value_of(matches, "recipient_limit") {
+        config.recipient_limit = Some(limit);
+    }
+    if matches.is_present("no_recipient_limit") {
+        config.recipient_limit = None;
+    }
+    if let Some(max) = value_of(matches, "max_total_minted") {
+        config.max_total_minted = Some(max);
+    }
+    if matches.is_present("no_max_total_minted") {
+        config.max_total_minted = None;
+    }
+}
+
+/// Creates and initializes a mint of the token program, `authority` keeps the mint authority
+/// until a faucet is initialized on the mint
+pub fn create_mint(
+    token_program_id: &Pubkey,
+    payer: &Pubkey,
+    mint: &Pubkey,
+    authority: &Pubkey,
+    decimals: u8,
+    lamports: u64,
+) -> Result<Vec<Instruction>, ProgramError> {
+    Ok(vec![
+        system_instruction::create_account(
+            payer,
+            mint,
+            lamports,
+            spl_token::state::Mint::LEN as u64,
+            token_program_id,
+        ),
+        spl_token::instruction::initialize_mint(token_program_id, mint, authority, None, decimals)?,
+    ])
+}
+
+/// Hands the mint authority over from `payer` to the faucet's PDA and creates the faucet,
+/// `token_program_id` is the token program owning the mint
+#[allow(clippy::too_many_arguments)]
+pub fn init(
+    program_id: &Pubkey,
+    token_program_id: &Pubkey,
+    payer: &Pubkey,
+    mint: &Pubkey,
+    faucet: &Pubkey,
+    label: Option<&[u8]>,
+    admin: Option<&Pubkey>,
+    config: FaucetConfig,
+) -> Result<Vec<Instruction>, ProgramError> {
+    let (pda, _nonce) = find_mint_authority_address(program_id, faucet);
+    Ok(vec![
+        token::set_authority(
+            token_program_id,
+            mint,
+            Some(&pda),
+            AuthorityType::MintTokens,
+            payer,
+        ),
+        instruction::create_faucet(program_id, payer, mint, faucet, admin, label, config),
+    ])
+}
+
+/// Mints from the faucet to the associated token account of `recipient`,
+/// `payer` pays for the claim record if the faucet tracks claims
+#[allow(clippy::too_many_arguments)]
+pub fn mint(
+    program_id: &Pubkey,
+    token_program_id: &Pubkey,
+    payer: &Pubkey,
+    faucet_address: &Pubkey,
+    faucet: &Faucet,
+    recipient: &Pubkey,
+    admin: Option<&Pubkey>,
+    amount: u64,
+) -> Instruction {
+    let destination = find_associated_token_address(recipient, &faucet.mint, token_program_id);
+    let mut ix = instruction::mint_tokens(
+        program_id,
+        token_program_id,
+        &faucet.mint,
+        &destination,
+        faucet_address,
+        None,
+        amount,
+    );
+    ix.accounts.extend(instruction::mint_tokens_faucet_accounts(
+        program_id,
+        faucet_address,
+        faucet,
+        recipient,
+        payer,
+    ));
+    if let Some(admin) = admin {
+        ix.accounts.push(AccountMeta::new_readonly(*admin, true));
+    }
+    ix
+}
+
+/// Closes the faucet, the tokens of a vault go to the associated token account of the admin
+pub fn close(
+    program_id: &Pubkey,
+    token_program_id: &Pubkey,
+    admin: &Pubkey,
+    faucet_address: &Pubkey,
+    faucet: &Faucet,
+    rent_destination: &Pubkey,
+) -> Instruction {
+    let mut ix = instruction::close_faucet(
+        program_id,
+        token_program_id,
+        admin,
+        faucet_address,
+        rent_destination,
+        &faucet.mint,
+    );
+    if let COption::Some(vault) = faucet.vault {
+        ix.accounts.push(AccountMeta::new(vault, false));
+        ix.accounts.push(AccountMeta::new(
+            find_associated_token_address(admin, &faucet.mint, token_program_id),
+            false,
+        ));
+    }
+    // lamports sent to the SOL vault of a faucet that doesn't drip any are swept as well
+    ix.accounts
+        .extend(instruction::close_faucet_sol_vault_accounts(
+            program_id,
+            faucet_address,
+        ));
+    ix
+}
+
+/// The faucet the way `show` prints it
+pub fn display_faucet(address: &Pubkey, faucet: &Faucet) -> String {
+    let or_none = |value: Option<String>| value.unwrap_or_else(|| "none".to_string());
+    let pubkey = |key: COption<Pubkey>| match key {
+        COption::Some(key) => key.to_string(),
+        COption::None => "none".to_string(),
+    };
+    let number = |value: Option<u64>| or_none(value.map(|value| value.to_string()));
+    let cooldown = or_none(faucet.cooldown.map(|cooldown| match cooldown {
+        Cooldown::Slots(slots) => format!("{} slots", slots),
+        Cooldown::Seconds(seconds) => format!("{} seconds", seconds),
+    }));
+
+    [
+        format!("Faucet: {}", address),
+        format!("Mint: {}", faucet.mint),
+        format!("Admin: {}", pubkey(faucet.admin)),
+        format!("Pending admin: {}", pubkey(faucet.pending_admin)),
+        format!("Amount: {}", faucet.amount),
+        format!("Cooldown: {}", cooldown),
+        format!("Recipient limit: {}", number(faucet.recipient_limit)),
+        format!("Max total minted: {}", number(faucet.max_total_minted)),
+        format!("Total minted: {}", faucet.total_minted),
+        format!("Paused: {}", faucet.paused),
+        format!("Vault: {}", pubkey(faucet.vault)),
+        format!("Lamport amount: {}", faucet.lamport_amount),
+        format!("Lamports per claim: {}", number(faucet.lamports_per_claim)),
+        format!("Mints: {}", faucet.mint_count),
+        format!("Last mint slot: {}", faucet.last_mint_slot),
+        format!("Unique recipients: {}", faucet.unique_recipients),
+    ]
+    .join("\n")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use clap::App;
+
+    /// The global arguments of the utility that make up the settings
+    fn settings_app<'a, 'b>() -> App<'a, 'b> {
+        App::new("test").args(&[
+            Arg::with_name("json_rpc_url").long("url").takes_value(true),
+            Arg::with_name("cluster").long("cluster").takes_value(true),
+            Arg::with_name("keypair").long("keypair").takes_value(true),
+            Arg::with_name("program_id")
+                .long("program-id")
+                .takes_value(true),
+            Arg::with_name("commitment")
+                .long("commitment")
+                .takes_value(true),
+        ])
+    }
+
+    fn cli_config() -> solana_cli_config::Config {
+        solana_cli_config::Config {
+            json_rpc_url: "http://localhost:8899".to_string(),
+            keypair_path: "id.json".to_string(),
+            ..solana_cli_config::Config::default()
+        }
+    }
+
+    #[test]
+    fn test_resolve_settings_from_config_file() {
+        let matches = settings_app().get_matches_from(vec!["test"]);
+        assert_eq!(
+            Settings {
+                json_rpc_url: "http://localhost:8899".to_string(),
+                keypair_path: "id.json".to_string(),
+                program_id: spl_token_faucet::id(),
+                commitment: CommitmentConfig::max(),
+            },
+            resolve_settings(&matches, &cli_config()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_resolve_settings_from_cluster_profile() {
+        let devnet = ClusterProfile::from_name("devnet").unwrap();
+        let matches = settings_app().get_matches_from(vec!["test", "--cluster", "devnet"]);
+        let settings = resolve_settings(&matches, &cli_config()).unwrap();
+        assert_eq!(devnet.json_rpc_url, settings.json_rpc_url);
+        assert_eq!(devnet.program_id, settings.program_id);
+        assert_eq!("id.json", settings.keypair_path);
+    }
+
+    #[test]
+    fn test_resolve_settings_arguments_take_precedence() {
+        let program_id = Pubkey::new_unique();
+        let matches = settings_app().get_matches_from(vec![
+            "test",
+            "--cluster",
+            "devnet",
+            "--url",
+            "http://example.com",
+            "--keypair",
+            "payer.json",
+            "--program-id",
+            &program_id.to_string(),
+            "--commitment",
+            "singleGossip",
+        ]);
+        assert_eq!(
+            Settings {
+                json_rpc_url: "http://example.com".to_string(),
+                keypair_path: "payer.json".to_string(),
+                program_id,
+                commitment: CommitmentConfig::single_gossip(),
+            },
+            resolve_settings(&matches, &cli_config()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_resolve_settings_unknown_cluster() {
+        let matches = settings_app().get_matches_from(vec!["test", "--cluster", "localnet"]);
+        assert!(resolve_settings(&matches, &cli_config()).is_err());
+    }
+
+    #[test]
+    fn test_unpack_faucet() {
+        let program_id = Pubkey::new_unique();
+        let address = Pubkey::new_unique();
+        let faucet = Faucet {
+            is_initialized: true,
+            amount: 10,
+            ..Faucet::default()
+        };
+        let mut account = Account::new(0, Faucet::LEN, &program_id);
+        Faucet::pack(faucet, &mut account.data).unwrap();
+        assert_eq!(
+            faucet,
+            unpack_faucet(&program_id, &address, &account).unwrap()
+        );
+
+        account.owner = Pubkey::new_unique();
+        assert_eq!(
+            format!("{} is not a faucet", address),
+            unpack_faucet(&program_id, &address, &account)
+                .unwrap_err()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_admin_of() {
+        let payer = Pubkey::new_unique();
+        let faucet = Faucet {
+            admin: COption::Some(payer),
+            ..Faucet::default()
+        };
+        assert_eq!(Some(payer), admin_of(&payer, &faucet));
+        assert_eq!(None, admin_of(&Pubkey::new_unique(), &faucet));
+        assert_eq!(None, admin_of(&payer, &Faucet::default()));
+    }
+
+    #[test]
+    fn test_is_valid_label() {
+        assert!(is_valid_label("a".repeat(MAX_SEED_LEN)).is_ok());
+        assert!(is_valid_label("a".repeat(MAX_SEED_LEN + 1)).is_err());
+    }
+
+    #[test]
+    fn test_parse_faucet_config() {
+        let app = App::new("test")
+            .args(&faucet_config_args())
+            .args(&update_config_args());
+        let mut config = FaucetConfig {
+            amount: 10,
+            cooldown: Some(Cooldown::Slots(5)),
+            recipient_limit: Some(100),
+            ..FaucetConfig::default()
+        };
+
+        // limits that aren't given stay as they are
+        parse_faucet_config(
+            &app.clone()
+                .get_matches_from(vec!["test", "--max-total-minted", "1000"]),
+            &mut config,
+        );
+        assert_eq!(
+            FaucetConfig {
+                amount: 10,
+                cooldown: Some(Cooldown::Slots(5)),
+                recipient_limit: Some(100),
+                max_total_minted: Some(1000),
+                ..FaucetConfig::default()
+            },
+            config
+        );
+
+        parse_faucet_config(
+            &app.get_matches_from(vec![
+                "test",
+                "--amount",
+                "20",
+                "--cooldown-seconds",
+                "60",
+                "--no-recipient-limit",
+                "--no-max-total-minted",
+            ]),
+            &mut config,
+        );
+        assert_eq!(
+            FaucetConfig {
+                amount: 20,
+                cooldown: Some(Cooldown::Seconds(60)),
+                ..FaucetConfig::default()
+            },
+            config
+        );
+    }
+}
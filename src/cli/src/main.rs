@@ -0,0 +1,478 @@
+use clap::{
+    crate_description, crate_name, crate_version, App, AppSettings, Arg, ArgMatches, SubCommand,
+};
+use solana_clap_utils::{
+    input_parsers::{keypair_of, pubkey_of, value_of},
+    input_validators::{is_keypair, is_parsable, is_url, is_valid_pubkey},
+};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    instruction::Instruction,
+    program_option::COption,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Keypair, Signer},
+    transaction::Transaction,
+};
+use spl_token::state::Mint;
+use spl_token_faucet::{
+    find_faucet_address, instruction,
+    state::{Faucet, FaucetConfig},
+    token::{
+        create_associated_token_account, find_associated_token_address, is_token_program,
+        unpack_mint,
+    },
+};
+use spl_token_faucet_cli::{
+    admin_of, faucet_config_args, is_valid_label, parse_faucet_config, resolve_settings,
+    unpack_faucet, update_config_args, Error,
+};
+use std::process::exit;
+
+type CommandResult = Result<(), Error>;
+
+struct Config {
+    rpc_client: RpcClient,
+    payer: Keypair,
+    program_id: Pubkey,
+}
+
+fn send(config: &Config, instructions: &[Instruction], signers: &[&Keypair]) -> CommandResult {
+    let (recent_blockhash, _fee_calculator) = config.rpc_client.get_recent_blockhash()?;
+    let mut all_signers = vec![&config.payer];
+    all_signers.extend_from_slice(signers);
+    let transaction = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&config.payer.pubkey()),
+        &all_signers,
+        recent_blockhash,
+    );
+    let signature = config
+        .rpc_client
+        .send_and_confirm_transaction_with_spinner(&transaction)?;
+    println!("Signature: {}", signature);
+    Ok(())
+}
+
+fn account_exists(config: &Config, address: &Pubkey) -> Result<bool, Error> {
+    Ok(config
+        .rpc_client
+        .get_account_with_commitment(address, config.rpc_client.commitment())?
+        .value
+        .is_some())
+}
+
+fn get_faucet(config: &Config, address: &Pubkey) -> Result<Faucet, Error> {
+    let account = config.rpc_client.get_account(address)?;
+    unpack_faucet(&config.program_id, address, &account)
+}
+
+/// The token program owning the mint and the mint, SPL Token and Token-2022 mints alike
+fn get_mint(config: &Config, address: &Pubkey) -> Result<(Pubkey, Mint), Error> {
+    let account = config.rpc_client.get_account(address)?;
+    if !is_token_program(&account.owner) {
+        return Err(format!("{} is not a mint", address).into());
+    }
+    Ok((account.owner, unpack_mint(&account.data)?))
+}
+
+fn command_create_mint(config: &Config, decimals: u8, mint: Option<Keypair>) -> CommandResult {
+    let mint = mint.unwrap_or_else(Keypair::new);
+    let lamports = config
+        .rpc_client
+        .get_minimum_balance_for_rent_exemption(Mint::LEN)?;
+    println!("Creating mint {}", mint.pubkey());
+    let instructions = spl_token_faucet_cli::create_mint(
+        &spl_token::id(),
+        &config.payer.pubkey(),
+        &mint.pubkey(),
+        &config.payer.pubkey(),
+        decimals,
+        lamports,
+    )?;
+    send(config, &instructions, &[&mint])
+}
+
+fn command_init(
+    config: &Config,
+    mint: &Pubkey,
+    label: Option<&str>,
+    faucet: Option<Keypair>,
+    with_admin: bool,
+    faucet_config: FaucetConfig,
+) -> CommandResult {
+    let payer = config.payer.pubkey();
+    let (token_program_id, mint_state) = get_mint(config, mint)?;
+    if mint_state.mint_authority != COption::Some(payer) {
+        return Err(format!("the mint authority of {} has to be the fee payer", mint).into());
+    }
+
+    // labeled faucets live at a PDA, all others at the address of a keypair that signs their creation
+    let (faucet, faucet_keypair) = match label {
+        Some(label) => (
+            find_faucet_address(&config.program_id, mint, label.as_bytes()).0,
+            None,
+        ),
+        None => {
+            let keypair = faucet.unwrap_or_else(Keypair::new);
+            (keypair.pubkey(), Some(keypair))
+        }
+    };
+    println!("Creating faucet {}", faucet);
+
+    let instructions = spl_token_faucet_cli::init(
+        &config.program_id,
+        &token_program_id,
+        &payer,
+        mint,
+        &faucet,
+        label.map(str::as_bytes),
+        if with_admin { Some(&payer) } else { None },
+        faucet_config,
+    )?;
+    let signers: Vec<&Keypair> = faucet_keypair.iter().collect();
+    send(config, &instructions, &signers)
+}
+
+fn command_mint(
+    config: &Config,
+    faucet_address: &Pubkey,
+    amount: Option<u64>,
+    recipient: Option<Pubkey>,
+) -> CommandResult {
+    let faucet = get_faucet(config, faucet_address)?;
+    let (token_program_id, _mint) = get_mint(config, &faucet.mint)?;
+    let payer = config.payer.pubkey();
+    let recipient = recipient.unwrap_or(payer);
+    let destination = find_associated_token_address(&recipient, &faucet.mint, &token_program_id);
+
+    let mut instructions = vec![];
+    if !account_exists(config, &destination)? {
+        instructions.push(create_associated_token_account(
+            &payer,
+            &recipient,
+            &faucet.mint,
+            &token_program_id,
+        ));
+    }
+    instructions.push(spl_token_faucet_cli::mint(
+        &config.program_id,
+        &token_program_id,
+        &payer,
+        faucet_address,
+        &faucet,
+        &recipient,
+        admin_of(&config.payer.pubkey(), &faucet).as_ref(),
+        amount.unwrap_or(faucet.amount),
+    ));
+    println!("Minting to {}", destination);
+    send(config, &instructions, &[])
+}
+
+fn command_close(
+    config: &Config,
+    faucet_address: &Pubkey,
+    rent_recipient: Option<Pubkey>,
+) -> CommandResult {
+    let faucet = get_faucet(config, faucet_address)?;
+    let admin = &admin_of(&config.payer.pubkey(), &faucet)
+        .ok_or("the fee payer is not the faucet's admin")?;
+    let (token_program_id, _mint) = get_mint(config, &faucet.mint)?;
+
+    let mut instructions = vec![];
+    if faucet.vault.is_some() {
+        let destination = find_associated_token_address(admin, &faucet.mint, &token_program_id);
+        if !account_exists(config, &destination)? {
+            instructions.push(create_associated_token_account(
+                admin,
+                admin,
+                &faucet.mint,
+                &token_program_id,
+            ));
+        }
+    }
+    instructions.push(spl_token_faucet_cli::close(
+        &config.program_id,
+        &token_program_id,
+        admin,
+        faucet_address,
+        &faucet,
+        &rent_recipient.unwrap_or(*admin),
+    ));
+    send(config, &instructions, &[])
+}
+
+fn command_show(config: &Config, faucet_address: &Pubkey) -> CommandResult {
+    let faucet = get_faucet(config, faucet_address)?;
+    println!(
+        "{}",
+        spl_token_faucet_cli::display_faucet(faucet_address, &faucet)
+    );
+    Ok(())
+}
+
+fn command_update(
+    config: &Config,
+    faucet_address: &Pubkey,
+    matches: &ArgMatches<'_>,
+) -> CommandResult {
+    let faucet = get_faucet(config, faucet_address)?;
+    let admin = &admin_of(&config.payer.pubkey(), &faucet)
+        .ok_or("the fee payer is not the faucet's admin")?;
+    let mut faucet_config = faucet.config();
+    parse_faucet_config(matches, &mut faucet_config);
+    send(
+        config,
+        &[instruction::update_config(
+            &config.program_id,
+            admin,
+            faucet_address,
+            faucet_config,
+        )],
+        &[],
+    )
+}
+
+fn faucet_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("faucet")
+        .value_name("FAUCET_ADDRESS")
+        .validator(is_valid_pubkey)
+        .index(1)
+        .required(true)
+        .help("The address of the faucet")
+}
+
+fn main() {
+    let app_matches = App::new(crate_name!())
+        .about(crate_description!())
+        .version(crate_version!())
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .arg({
+            let arg = Arg::with_name("config_file")
+                .short("C")
+                .long("config")
+                .value_name("PATH")
+                .takes_value(true)
+                .global(true)
+                .help("Configuration file to use");
+            if let Some(ref config_file) = *solana_cli_config::CONFIG_FILE {
+                arg.default_value(&config_file)
+            } else {
+                arg
+            }
+        })
+        .arg(
+            Arg::with_name("json_rpc_url")
+                .short("u")
+                .long("url")
+                .value_name("URL")
+                .takes_value(true)
+                .global(true)
+                .validator(is_url)
+                .help("JSON RPC URL for the cluster [default: value from the cluster profile or configuration file]"),
+        )
+        .arg(
+            Arg::with_name("cluster")
+                .long("cluster")
+                .value_name("CLUSTER")
+                .takes_value(true)
+                .global(true)
+                .help("Cluster profile to use, devnet, testnet, mainnet-beta or the path of a cluster env file"),
+        )
+        .arg(
+            Arg::with_name("keypair")
+                .long("keypair")
+                .value_name("KEYPAIR")
+                .takes_value(true)
+                .global(true)
+                .validator(is_keypair)
+                .help("Fee payer, mint authority and admin [default: value from configuration file]"),
+        )
+        .arg(
+            Arg::with_name("program_id")
+                .long("program-id")
+                .value_name("PROGRAM_ID")
+                .takes_value(true)
+                .global(true)
+                .validator(is_valid_pubkey)
+                .help("The faucet program [default: value from the cluster profile or the program id the crate was built with]"),
+        )
+        .arg(
+            Arg::with_name("commitment")
+                .long("commitment")
+                .value_name("COMMITMENT_LEVEL")
+                .takes_value(true)
+                .global(true)
+                .possible_values(&["recent", "single", "singleGossip", "root", "max"])
+                .help("Commitment to read accounts and confirm transactions with [default: max]"),
+        )
+        .subcommand(
+            SubCommand::with_name("create-mint")
+                .about("Create a new mint, the fee payer is its mint authority")
+                .arg(
+                    Arg::with_name("decimals")
+                        .long("decimals")
+                        .value_name("DECIMALS")
+                        .validator(is_parsable::<u8>)
+                        .default_value("9")
+                        .help("Number of base 10 digits to the right of the decimal place"),
+                )
+                .arg(
+                    Arg::with_name("mint_keypair")
+                        .long("mint-keypair")
+                        .value_name("KEYPAIR")
+                        .validator(is_keypair)
+                        .help("Keypair of the mint [default: randomly generated keypair]"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("init")
+                .about("Create a faucet on a mint, the mint authority is handed over from the fee payer to the faucet")
+                .arg(
+                    Arg::with_name("mint")
+                        .value_name("MINT_ADDRESS")
+                        .validator(is_valid_pubkey)
+                        .index(1)
+                        .required(true)
+                        .help("The mint of the faucet"),
+                )
+                .arg(
+                    Arg::with_name("amount")
+                        .value_name("AMOUNT")
+                        .validator(is_parsable::<u64>)
+                        .index(2)
+                        .required(true)
+                        .help("Amount anyone but the admin may mint at once, in base units of the mint"),
+                )
+                .arg(
+                    Arg::with_name("label")
+                        .long("label")
+                        .value_name("LABEL")
+                        .validator(is_valid_label)
+                        .conflicts_with("faucet_keypair")
+                        .help("Create the faucet at the address derived from the mint and this label"),
+                )
+                .arg(
+                    Arg::with_name("faucet_keypair")
+                        .long("faucet-keypair")
+                        .value_name("KEYPAIR")
+                        .validator(is_keypair)
+                        .help("Keypair of the faucet [default: randomly generated keypair]"),
+                )
+                .arg(
+                    Arg::with_name("no_admin")
+                        .long("no-admin")
+                        .help("Create the faucet without an admin, it can't be changed or closed afterwards"),
+                )
+                .args(&faucet_config_args()),
+        )
+        .subcommand(
+            SubCommand::with_name("mint")
+                .about("Mint tokens from a faucet to the associated token account of the recipient")
+                .arg(faucet_arg())
+                .arg(
+                    Arg::with_name("amount")
+                        .value_name("AMOUNT")
+                        .validator(is_parsable::<u64>)
+                        .index(2)
+                        .help("Amount to mint, in base units of the mint [default: the faucet's amount]"),
+                )
+                .arg(
+                    Arg::with_name("recipient")
+                        .long("recipient")
+                        .value_name("WALLET_ADDRESS")
+                        .validator(is_valid_pubkey)
+                        .help("Wallet to mint to [default: the fee payer]"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("close")
+                .about("Close a faucet, the mint authority goes back to the admin")
+                .arg(faucet_arg())
+                .arg(
+                    Arg::with_name("rent_recipient")
+                        .long("rent-recipient")
+                        .value_name("ADDRESS")
+                        .validator(is_valid_pubkey)
+                        .help("Account to send the faucet's rent to [default: the admin]"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("show")
+                .about("Show the state of a faucet")
+                .arg(faucet_arg()),
+        )
+        .subcommand(
+            SubCommand::with_name("update")
+                .about("Update the limits of a faucet, limits that aren't given stay as they are")
+                .arg(faucet_arg())
+                .args(&faucet_config_args())
+                .args(&update_config_args()),
+        )
+        .get_matches();
+
+    let (sub_command, sub_matches) = app_matches.subcommand();
+    let matches = sub_matches.unwrap();
+
+    let config = {
+        let cli_config = if let Some(config_file) = matches.value_of("config_file") {
+            solana_cli_config::Config::load(config_file).unwrap_or_default()
+        } else {
+            solana_cli_config::Config::default()
+        };
+        let settings = resolve_settings(matches, &cli_config).unwrap_or_else(|err| {
+            eprintln!("error: {}", err);
+            exit(1);
+        });
+        let payer = read_keypair_file(&settings.keypair_path).unwrap_or_else(|err| {
+            eprintln!(
+                "error: failed to read keypair {}: {}",
+                settings.keypair_path, err
+            );
+            exit(1);
+        });
+        Config {
+            rpc_client: RpcClient::new_with_commitment(settings.json_rpc_url, settings.commitment),
+            payer,
+            program_id: settings.program_id,
+        }
+    };
+
+    let result = match sub_command {
+        "create-mint" => command_create_mint(
+            &config,
+            value_of(matches, "decimals").unwrap(),
+            keypair_of(matches, "mint_keypair"),
+        ),
+        "init" => {
+            let mut faucet_config = FaucetConfig::default();
+            parse_faucet_config(matches, &mut faucet_config);
+            command_init(
+                &config,
+                &pubkey_of(matches, "mint").unwrap(),
+                matches.value_of("label"),
+                keypair_of(matches, "faucet_keypair"),
+                !matches.is_present("no_admin"),
+                faucet_config,
+            )
+        }
+        "mint" => command_mint(
+            &config,
+            &pubkey_of(matches, "faucet").unwrap(),
+            value_of(matches, "amount"),
+            pubkey_of(matches, "recipient"),
+        ),
+        "close" => command_close(
+            &config,
+            &pubkey_of(matches, "faucet").unwrap(),
+            pubkey_of(matches, "rent_recipient"),
+        ),
+        "show" => command_show(&config, &pubkey_of(matches, "faucet").unwrap()),
+        "update" => command_update(&config, &pubkey_of(matches, "faucet").unwrap(), matches),
+        _ => unreachable!(),
+    };
+    if let Err(err) = result {
+        eprintln!("error: {}", err);
+        exit(1);
+    }
+}
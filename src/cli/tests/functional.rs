@@ -0,0 +1,438 @@
+// Mark this test as BPF-only due to current `ProgramTest` limitations when CPIing into the system program.
+// `BPF_OUT_DIR` has to point at the build of the program, see `npm run test:cli`
+#![cfg(feature = "test-bpf")]
+
+use solana_program_test::*;
+use solana_sdk::{
+    hash::Hash,
+    instruction::Instruction,
+    program_option::COption,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    transaction::Transaction,
+};
+use spl_associated_token_account::{create_associated_token_account, get_associated_token_address};
+use spl_token_faucet::{
+    find_claim_record_address, find_faucet_address, find_mint_authority_address, id, processor,
+    state::{Cooldown, Faucet, FaucetConfig},
+};
+
+fn program_test() -> ProgramTest {
+    let mut pc = ProgramTest::new(
+        "spl_token_faucet",
+        id(),
+        processor!(processor::Processor::process),
+    );
+
+    // Add SPL Token program
+    pc.add_program(
+        "spl_token",
+        spl_token::id(),
+        processor!(spl_token::processor::Processor::process),
+    );
+
+    // Add Associated Token Account program
+    pc.add_program(
+        "spl_associated_token_account",
+        spl_associated_token_account::id(),
+        processor!(spl_associated_token_account::processor::process_instruction),
+    );
+
+    pc
+}
+
+async fn process(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    recent_blockhash: Hash,
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+) {
+    let mut all_signers = vec![payer];
+    all_signers.extend_from_slice(signers);
+    let transaction = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&payer.pubkey()),
+        &all_signers,
+        recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await.unwrap();
+}
+
+/// Runs `create-mint`, the payer is the mint authority
+async fn create_mint(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    recent_blockhash: Hash,
+) -> Pubkey {
+    let mint = Keypair::new();
+    let rent = banks_client.get_rent().await.unwrap();
+    let instructions = spl_token_faucet_cli::create_mint(
+        &spl_token::id(),
+        &payer.pubkey(),
+        &mint.pubkey(),
+        &payer.pubkey(),
+        6,
+        rent.minimum_balance(spl_token::state::Mint::LEN),
+    )
+    .unwrap();
+    process(
+        banks_client,
+        payer,
+        recent_blockhash,
+        &instructions,
+        &[&mint],
+    )
+    .await;
+    mint.pubkey()
+}
+
+/// Runs `init` on a new mint, with the payer as the admin
+async fn init(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    recent_blockhash: Hash,
+    config: FaucetConfig,
+) -> Pubkey {
+    let mint = create_mint(banks_client, payer, recent_blockhash).await;
+    let faucet = Keypair::new();
+    let instructions = spl_token_faucet_cli::init(
+        &id(),
+        &spl_token::id(),
+        &payer.pubkey(),
+        &mint,
+        &faucet.pubkey(),
+        None,
+        Some(&payer.pubkey()),
+        config,
+    )
+    .unwrap();
+    process(
+        banks_client,
+        payer,
+        recent_blockhash,
+        &instructions,
+        &[&faucet],
+    )
+    .await;
+    faucet.pubkey()
+}
+
+async fn get_faucet(banks_client: &mut BanksClient, faucet: &Pubkey) -> Faucet {
+    let account = banks_client.get_account(*faucet).await.unwrap().unwrap();
+    Faucet::unpack_versioned(&account.data).unwrap()
+}
+
+async fn get_mint(banks_client: &mut BanksClient, mint: &Pubkey) -> spl_token::state::Mint {
+    let account = banks_client.get_account(*mint).await.unwrap().unwrap();
+    spl_token::state::Mint::unpack(&account.data).unwrap()
+}
+
+/// Runs `mint`, creating the associated token account of the recipient first
+async fn mint(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    recent_blockhash: Hash,
+    faucet_address: &Pubkey,
+    recipient: &Pubkey,
+    amount: u64,
+) -> Pubkey {
+    let faucet = get_faucet(banks_client, faucet_address).await;
+    let instructions = [
+        create_associated_token_account(&payer.pubkey(), recipient, &faucet.mint),
+        spl_token_faucet_cli::mint(
+            &id(),
+            &spl_token::id(),
+            &payer.pubkey(),
+            faucet_address,
+            &faucet,
+            recipient,
+            None,
+            amount,
+        ),
+    ];
+    process(banks_client, payer, recent_blockhash, &instructions, &[]).await;
+    get_associated_token_address(recipient, &faucet.mint)
+}
+
+#[tokio::test]
+async fn test_happy_flow_create_mint() {
+    // GIVEN
+    let (mut banks_client, payer, recent_blockhash) = program_test().start().await;
+
+    // WHEN
+    let mint = create_mint(&mut banks_client, &payer, recent_blockhash).await;
+
+    // THEN
+    let mint_state = get_mint(&mut banks_client, &mint).await;
+    assert_eq!(mint_state.mint_authority, COption::Some(payer.pubkey()));
+    assert_eq!(mint_state.decimals, 6);
+}
+
+#[tokio::test]
+async fn test_happy_flow_init() {
+    // GIVEN
+    let (mut banks_client, payer, recent_blockhash) = program_test().start().await;
+
+    // WHEN
+    let faucet_address = init(
+        &mut banks_client,
+        &payer,
+        recent_blockhash,
+        FaucetConfig {
+            amount: 100,
+            ..FaucetConfig::default()
+        },
+    )
+    .await;
+
+    // THEN
+    let faucet = get_faucet(&mut banks_client, &faucet_address).await;
+    assert_eq!(faucet.admin, COption::Some(payer.pubkey()));
+    assert_eq!(faucet.amount, 100);
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_address);
+    assert_eq!(
+        get_mint(&mut banks_client, &faucet.mint)
+            .await
+            .mint_authority,
+        COption::Some(pda)
+    );
+}
+
+#[tokio::test]
+async fn test_happy_flow_init_with_label() {
+    // GIVEN
+    let (mut banks_client, payer, recent_blockhash) = program_test().start().await;
+    let mint = create_mint(&mut banks_client, &payer, recent_blockhash).await;
+    let (faucet_address, _nonce) = find_faucet_address(&id(), &mint, b"devnet");
+
+    // WHEN
+    let instructions = spl_token_faucet_cli::init(
+        &id(),
+        &spl_token::id(),
+        &payer.pubkey(),
+        &mint,
+        &faucet_address,
+        Some(b"devnet"),
+        None,
+        FaucetConfig {
+            amount: 100,
+            ..FaucetConfig::default()
+        },
+    )
+    .unwrap();
+    process(
+        &mut banks_client,
+        &payer,
+        recent_blockhash,
+        &instructions,
+        &[],
+    )
+    .await;
+
+    // THEN
+    let faucet = get_faucet(&mut banks_client, &faucet_address).await;
+    assert_eq!(faucet.admin, COption::None);
+    assert_eq!(faucet.mint, mint);
+}
+
+#[tokio::test]
+async fn test_happy_flow_mint() {
+    // GIVEN
+    let (mut banks_client, payer, recent_blockhash) = program_test().start().await;
+    let faucet_address = init(
+        &mut banks_client,
+        &payer,
+        recent_blockhash,
+        FaucetConfig {
+            amount: 100,
+            ..FaucetConfig::default()
+        },
+    )
+    .await;
+    let recipient = Pubkey::new_unique();
+
+    // WHEN
+    let destination = mint(
+        &mut banks_client,
+        &payer,
+        recent_blockhash,
+        &faucet_address,
+        &recipient,
+        100,
+    )
+    .await;
+
+    // THEN
+    let account = banks_client
+        .get_account(destination)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        spl_token::state::Account::unpack(&account.data)
+            .unwrap()
+            .amount,
+        100
+    );
+    assert_eq!(
+        get_faucet(&mut banks_client, &faucet_address)
+            .await
+            .mint_count,
+        1
+    );
+}
+
+#[tokio::test]
+async fn test_happy_flow_mint_with_cooldown() {
+    // GIVEN
+    let (mut banks_client, payer, recent_blockhash) = program_test().start().await;
+    let faucet_address = init(
+        &mut banks_client,
+        &payer,
+        recent_blockhash,
+        FaucetConfig {
+            amount: 100,
+            cooldown: Some(Cooldown::Slots(100)),
+            ..FaucetConfig::default()
+        },
+    )
+    .await;
+    let recipient = Pubkey::new_unique();
+
+    // WHEN
+    mint(
+        &mut banks_client,
+        &payer,
+        recent_blockhash,
+        &faucet_address,
+        &recipient,
+        100,
+    )
+    .await;
+
+    // THEN
+    let (claim_record, _nonce) = find_claim_record_address(&id(), &faucet_address, &recipient);
+    assert!(banks_client
+        .get_account(claim_record)
+        .await
+        .unwrap()
+        .is_some());
+}
+
+#[tokio::test]
+async fn test_happy_flow_update() {
+    // GIVEN
+    let (mut banks_client, payer, recent_blockhash) = program_test().start().await;
+    let faucet_address = init(
+        &mut banks_client,
+        &payer,
+        recent_blockhash,
+        FaucetConfig {
+            amount: 100,
+            ..FaucetConfig::default()
+        },
+    )
+    .await;
+    let mut config = get_faucet(&mut banks_client, &faucet_address)
+        .await
+        .config();
+    config.amount = 200;
+    config.recipient_limit = Some(1000);
+
+    // WHEN
+    process(
+        &mut banks_client,
+        &payer,
+        recent_blockhash,
+        &[spl_token_faucet::instruction::update_config(
+            &id(),
+            &payer.pubkey(),
+            &faucet_address,
+            config,
+        )],
+        &[],
+    )
+    .await;
+
+    // THEN
+    let faucet = get_faucet(&mut banks_client, &faucet_address).await;
+    assert_eq!(faucet.config(), config);
+}
+
+#[tokio::test]
+async fn test_happy_flow_close() {
+    // GIVEN
+    let (mut banks_client, payer, recent_blockhash) = program_test().start().await;
+    let faucet_address = init(
+        &mut banks_client,
+        &payer,
+        recent_blockhash,
+        FaucetConfig {
+            amount: 100,
+            ..FaucetConfig::default()
+        },
+    )
+    .await;
+    let faucet = get_faucet(&mut banks_client, &faucet_address).await;
+
+    // WHEN
+    process(
+        &mut banks_client,
+        &payer,
+        recent_blockhash,
+        &[spl_token_faucet_cli::close(
+            &id(),
+            &spl_token::id(),
+            &payer.pubkey(),
+            &faucet_address,
+            &faucet,
+            &payer.pubkey(),
+        )],
+        &[],
+    )
+    .await;
+
+    // THEN
+    assert!(banks_client
+        .get_account(faucet_address)
+        .await
+        .unwrap()
+        .is_none());
+    assert_eq!(
+        get_mint(&mut banks_client, &faucet.mint)
+            .await
+            .mint_authority,
+        COption::Some(payer.pubkey())
+    );
+}
+
+#[tokio::test]
+async fn test_show() {
+    // GIVEN
+    let (mut banks_client, payer, recent_blockhash) = program_test().start().await;
+    let faucet_address = init(
+        &mut banks_client,
+        &payer,
+        recent_blockhash,
+        FaucetConfig {
+            amount: 100,
+            cooldown: Some(Cooldown::Seconds(60)),
+            ..FaucetConfig::default()
+        },
+    )
+    .await;
+
+    // WHEN
+    let faucet = get_faucet(&mut banks_client, &faucet_address).await;
+    let output = spl_token_faucet_cli::display_faucet(&faucet_address, &faucet);
+
+    // THEN
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(lines[0], format!("Faucet: {}", faucet_address));
+    assert_eq!(lines[2], format!("Admin: {}", payer.pubkey()));
+    assert_eq!(lines[3], "Pending admin: none");
+    assert_eq!(lines[4], "Amount: 100");
+    assert_eq!(lines[5], "Cooldown: 60 seconds");
+}
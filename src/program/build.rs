@@ -0,0 +1,63 @@
+//! Picks the program id at build time, so copies of the program can be deployed without patching the source.
+//!
+//! `FAUCET_PROGRAM_ID` takes a base58 program id, `FAUCET_PROGRAM_KEYPAIR` the path of the program's keypair file,
+//! without either the program keeps the id it is deployed at on the public clusters.
+
+use std::{env, fs, path::Path};
+
+const DEFAULT_PROGRAM_ID: &str = "4bXpkKSV8swHSnwqtzuboGPaPDeEgAn4Vt8GfarV5rZt";
+
+/// Reads the public key of a keypair file written by `solana-keygen`, a JSON array of the 64 keypair bytes
+fn keypair_file_pubkey(path: &str) -> String {
+    let contents = fs::read_to_string(path)
+        .unwrap_or_else(|err| panic!("Unable to read FAUCET_PROGRAM_KEYPAIR {}: {}", path, err));
+    let bytes = contents
+        .trim()
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .split(',')
+        .map(|byte| byte.trim().parse::<u8>())
+        .collect::<Result<Vec<u8>, _>>()
+        .unwrap_or_else(|err| panic!("Invalid FAUCET_PROGRAM_KEYPAIR {}: {}", path, err));
+    if bytes.len() != 64 {
+        panic!("Invalid FAUCET_PROGRAM_KEYPAIR {}: expected 64 bytes", path);
+    }
+    bs58::encode(&bytes[32..]).into_string()
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-env-changed=FAUCET_PROGRAM_ID");
+    println!("cargo:rerun-if-env-changed=FAUCET_PROGRAM_KEYPAIR");
+
+    let program_id = match (
+        env::var("FAUCET_PROGRAM_ID"),
+        env::var("FAUCET_PROGRAM_KEYPAIR"),
+    ) {
+        (Ok(_), Ok(_)) => {
+            panic!("Only one of FAUCET_PROGRAM_ID and FAUCET_PROGRAM_KEYPAIR may be set")
+        }
+        (Ok(program_id), Err(_)) => {
+            let is_pubkey = bs58::decode(&program_id)
+                .into_vec()
+                .map(|bytes| bytes.len() == 32)
+                .unwrap_or(false);
+            if !is_pubkey {
+                panic!("Invalid FAUCET_PROGRAM_ID {}", program_id);
+            }
+            program_id
+        }
+        (Err(_), Ok(path)) => {
+            println!("cargo:rerun-if-changed={}", path);
+            keypair_file_pubkey(&path)
+        }
+        (Err(_), Err(_)) => DEFAULT_PROGRAM_ID.to_string(),
+    };
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(
+        Path::new(&out_dir).join("program_id.rs"),
+        format!("solana_program::declare_id!(\"{}\");\n", program_id),
+    )
+    .unwrap();
+}
@@ -0,0 +1,355 @@
+//! Client of the faucet program, over an `RpcTransport` in production and over a `BanksClient` in tests
+
+pub mod cluster;
+
+use self::cluster::ClusterProfile;
+use crate::{error::FaucetError, find_mint_authority_address, instruction, state::Faucet, token};
+use async_trait::async_trait;
+use num_traits::FromPrimitive;
+use solana_banks_client::{BanksClient, BanksClientExt};
+use solana_client::{
+    client_error::{ClientError as RpcClientError, ClientErrorKind},
+    rpc_client::RpcClient,
+    rpc_request::{RpcError, RpcResponseErrorData},
+    rpc_response::RpcSimulateTransactionResult,
+};
+use solana_sdk::{
+    account::Account,
+    hash::Hash,
+    instruction::{AccountMeta, Instruction, InstructionError},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    signature::{Keypair, Signature, Signer},
+    transaction::{Transaction, TransactionError},
+    transport::TransportError,
+};
+use std::sync::Arc;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ClientError {
+    /// The faucet program failed the transaction
+    #[error("Faucet error: {0}")]
+    Faucet(#[from] FaucetError),
+    /// Any other failed transaction
+    #[error("Transaction error: {0}")]
+    Transaction(#[from] TransactionError),
+    /// Account not found
+    #[error("Account {0} not found")]
+    AccountNotFound(Pubkey),
+    /// Account not owned by the faucet program
+    #[error("Account {0} is not a faucet")]
+    NotAFaucet(Pubkey),
+    /// Account data that can't be unpacked
+    #[error("Program error: {0}")]
+    Program(ProgramError),
+    /// The cluster couldn't be reached
+    #[error("Transport error: {0}")]
+    Transport(String),
+}
+
+impl From<ProgramError> for ClientError {
+    fn from(err: ProgramError) -> Self {
+        match err {
+            ProgramError::Custom(code) => match FaucetError::from_u32(code) {
+                Some(err) => ClientError::Faucet(err),
+                None => ClientError::Program(err),
+            },
+            err => ClientError::Program(err),
+        }
+    }
+}
+
+impl From<RpcClientError> for ClientError {
+    fn from(err: RpcClientError) -> Self {
+        match err.kind {
+            ClientErrorKind::TransactionError(err) => ClientError::Transaction(err),
+            // transactions that fail the preflight simulation never make it to the cluster
+            ClientErrorKind::RpcError(RpcError::RpcResponseError {
+                data:
+                    RpcResponseErrorData::SendTransactionPreflightFailure(
+                        RpcSimulateTransactionResult { err: Some(err), .. },
+                    ),
+                ..
+            }) => ClientError::Transaction(err),
+            kind => ClientError::Transport(kind.to_string()),
+        }
+    }
+}
+
+impl From<TransportError> for ClientError {
+    fn from(err: TransportError) -> Self {
+        match err {
+            TransportError::TransactionError(err) => ClientError::Transaction(err),
+            err => ClientError::Transport(err.to_string()),
+        }
+    }
+}
+
+impl ClientError {
+    /// Surfaces the custom error of an instruction of the faucet program as a `FaucetError`.
+    /// Errors of the programs the faucet calls into carry the index of the faucet's instruction as well,
+    /// so their codes can't be told apart from the faucet's own.
+    fn from_transaction_error(
+        err: TransactionError,
+        instructions: &[Instruction],
+        program_id: &Pubkey,
+    ) -> Self {
+        if let TransactionError::InstructionError(index, InstructionError::Custom(code)) = err {
+            let is_faucet_instruction = instructions
+                .get(index as usize)
+                .map_or(false, |ix| ix.program_id == *program_id);
+            if is_faucet_instruction {
+                if let Some(err) = FaucetError::from_u32(code) {
+                    return ClientError::Faucet(err);
+                }
+            }
+        }
+        ClientError::Transaction(err)
+    }
+}
+
+/// How the client reaches a cluster
+#[async_trait]
+pub trait FaucetTransport {
+    async fn get_account(&mut self, address: &Pubkey) -> Result<Option<Account>, ClientError>;
+    async fn get_recent_blockhash(&mut self) -> Result<Hash, ClientError>;
+    /// Sends the transaction and waits until it is confirmed
+    async fn send_transaction(
+        &mut self,
+        transaction: Transaction,
+    ) -> Result<Signature, ClientError>;
+}
+
+/// Transport over an `RpcClient`. Its calls block until the cluster responds,
+/// so they run on the blocking pool of the runtime instead of holding up the executor.
+#[derive(Clone)]
+pub struct RpcTransport {
+    client: Arc<RpcClient>,
+}
+
+impl RpcTransport {
+    pub fn new(client: RpcClient) -> Self {
+        RpcTransport {
+            client: Arc::new(client),
+        }
+    }
+
+    pub fn client(&self) -> &RpcClient {
+        &self.client
+    }
+
+    async fn spawn_blocking<F, R>(&self, f: F) -> Result<R, ClientError>
+    where
+        F: FnOnce(&RpcClient) -> Result<R, RpcClientError> + Send + 'static,
+        R: Send + 'static,
+    {
+        let client = self.client.clone();
+        tokio::task::spawn_blocking(move || f(&client))
+            .await
+            .map_err(|err| ClientError::Transport(err.to_string()))?
+            .map_err(ClientError::from)
+    }
+}
+
+#[async_trait]
+impl FaucetTransport for RpcTransport {
+    async fn get_account(&mut self, address: &Pubkey) -> Result<Option<Account>, ClientError> {
+        let address = *address;
+        self.spawn_blocking(move |client| {
+            Ok(client
+                .get_account_with_commitment(&address, client.commitment())?
+                .value)
+        })
+        .await
+    }
+
+    async fn get_recent_blockhash(&mut self) -> Result<Hash, ClientError> {
+        self.spawn_blocking(|client| Ok(client.get_recent_blockhash()?.0))
+            .await
+    }
+
+    async fn send_transaction(
+        &mut self,
+        transaction: Transaction,
+    ) -> Result<Signature, ClientError> {
+        self.spawn_blocking(move |client| client.send_and_confirm_transaction(&transaction))
+            .await
+    }
+}
+
+#[async_trait]
+impl FaucetTransport for BanksClient {
+    async fn get_account(&mut self, address: &Pubkey) -> Result<Option<Account>, ClientError> {
+        BanksClientExt::get_account(self, *address)
+            .await
+            .map_err(|err| ClientError::Transport(err.to_string()))
+    }
+
+    async fn get_recent_blockhash(&mut self) -> Result<Hash, ClientError> {
+        BanksClientExt::get_recent_blockhash(self)
+            .await
+            .map_err(|err| ClientError::Transport(err.to_string()))
+    }
+
+    async fn send_transaction(
+        &mut self,
+        transaction: Transaction,
+    ) -> Result<Signature, ClientError> {
+        let signature = transaction.signatures[0];
+        self.process_transaction(transaction).await?;
+        Ok(signature)
+    }
+}
+
+/// Reads faucets and mints from them
+#[derive(Clone)]
+pub struct FaucetClient<T> {
+    transport: T,
+    program_id: Pubkey,
+}
+
+impl FaucetClient<RpcTransport> {
+    /// A client of the program deployed on the cluster of the profile
+    pub fn from_cluster(profile: &ClusterProfile) -> Self {
+        Self::with_program_id(RpcTransport::new(profile.rpc_client()), profile.program_id)
+    }
+}
+
+impl<T: FaucetTransport + Send> FaucetClient<T> {
+    /// A client of the program at the id the crate was built with
+    pub fn new(transport: T) -> Self {
+        Self::with_program_id(transport, crate::id())
+    }
+
+    pub fn with_program_id(transport: T, program_id: Pubkey) -> Self {
+        FaucetClient {
+            transport,
+            program_id,
+        }
+    }
+
+    pub fn program_id(&self) -> &Pubkey {
+        &self.program_id
+    }
+
+    pub fn transport(&mut self) -> &mut T {
+        &mut self.transport
+    }
+
+    /// The mint authority of the faucet, unless it has a vault
+    pub fn mint_authority(&self, faucet: &Pubkey) -> Pubkey {
+        find_mint_authority_address(&self.program_id, faucet).0
+    }
+
+    async fn get_existing_account(&mut self, address: &Pubkey) -> Result<Account, ClientError> {
+        self.transport
+            .get_account(address)
+            .await?
+            .ok_or(ClientError::AccountNotFound(*address))
+    }
+
+    /// Fetches and decodes a faucet of any layout
+    pub async fn get_faucet(&mut self, faucet: &Pubkey) -> Result<Faucet, ClientError> {
+        let account = self.get_existing_account(faucet).await?;
+        if account.owner != self.program_id {
+            return Err(ClientError::NotAFaucet(*faucet));
+        }
+        Ok(Faucet::unpack_versioned(&account.data)?)
+    }
+
+    /// Creates the instructions that mint from the faucet to the associated token account of `recipient`,
+    /// creating the account first if it doesn't exist yet. `payer` pays for it and for the claim record.
+    pub async fn mint_tokens_instructions(
+        &mut self,
+        faucet_address: &Pubkey,
+        recipient: &Pubkey,
+        payer: &Pubkey,
+        admin: Option<&Pubkey>,
+        amount: u64,
+    ) -> Result<Vec<Instruction>, ClientError> {
+        let faucet = self.get_faucet(faucet_address).await?;
+        let token_program_id = self.get_existing_account(&faucet.mint).await?.owner;
+        let destination =
+            token::find_associated_token_address(recipient, &faucet.mint, &token_program_id);
+
+        let mut instructions = vec![];
+        if self.transport.get_account(&destination).await?.is_none() {
+            instructions.push(token::create_associated_token_account(
+                payer,
+                recipient,
+                &faucet.mint,
+                &token_program_id,
+            ));
+        }
+        let mut ix = instruction::mint_tokens(
+            &self.program_id,
+            &token_program_id,
+            &faucet.mint,
+            &destination,
+            faucet_address,
+            None,
+            amount,
+        );
+        ix.accounts.extend(instruction::mint_tokens_faucet_accounts(
+            &self.program_id,
+            faucet_address,
+            &faucet,
+            recipient,
+            payer,
+        ));
+        if let Some(admin) = admin {
+            ix.accounts.push(AccountMeta::new_readonly(*admin, true));
+        }
+        instructions.push(ix);
+        Ok(instructions)
+    }
+
+    /// Mints from the faucet to the associated token account of `recipient`, see `mint_tokens_instructions`
+    pub async fn mint_tokens(
+        &mut self,
+        payer: &Keypair,
+        faucet_address: &Pubkey,
+        recipient: &Pubkey,
+        admin: Option<&Keypair>,
+        amount: u64,
+    ) -> Result<Signature, ClientError> {
+        let instructions = self
+            .mint_tokens_instructions(
+                faucet_address,
+                recipient,
+                &payer.pubkey(),
+                admin.map(Signer::pubkey).as_ref(),
+                amount,
+            )
+            .await?;
+        let signers: Vec<&Keypair> = admin.into_iter().collect();
+        self.send(&instructions, payer, &signers).await
+    }
+
+    /// Signs and sends the instructions, errors of the faucet program's instructions come back as `ClientError::Faucet`
+    pub async fn send(
+        &mut self,
+        instructions: &[Instruction],
+        payer: &Keypair,
+        signers: &[&Keypair],
+    ) -> Result<Signature, ClientError> {
+        let recent_blockhash = self.transport.get_recent_blockhash().await?;
+        let mut all_signers = vec![payer];
+        all_signers.extend_from_slice(signers);
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&payer.pubkey()),
+            &all_signers,
+            recent_blockhash,
+        );
+        match self.transport.send_transaction(transaction).await {
+            Err(ClientError::Transaction(err)) => Err(ClientError::from_transaction_error(
+                err,
+                instructions,
+                &self.program_id,
+            )),
+            result => result,
+        }
+    }
+}
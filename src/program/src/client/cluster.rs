@@ -0,0 +1,158 @@
+//! Cluster profiles, read from the `clusters/cluster-*.env` files of the crate
+
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use std::{fs, path::Path, str::FromStr};
+use thiserror::Error;
+
+/// The profiles shipped with the crate, by cluster name
+const SHIPPED_PROFILES: &[(&str, &str)] = &[
+    ("devnet", include_str!("../../clusters/cluster-devnet.env")),
+    (
+        "testnet",
+        include_str!("../../clusters/cluster-testnet.env"),
+    ),
+    (
+        "mainnet-beta",
+        include_str!("../../clusters/cluster-mainnet-beta.env"),
+    ),
+];
+
+#[derive(Error, Debug)]
+pub enum ClusterError {
+    /// Unknown cluster
+    #[error("Unknown cluster {0}, expected devnet, testnet, mainnet-beta or the path of a cluster env file")]
+    UnknownCluster(String),
+    /// Missing key
+    #[error("Missing key {0}")]
+    MissingKey(&'static str),
+    /// Invalid line
+    #[error("Invalid line {0}")]
+    InvalidLine(usize),
+    /// Invalid program id
+    #[error("Invalid program id {0}")]
+    InvalidProgramId(String),
+    /// Io error
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+/// Where the faucet program of a cluster is deployed and how to reach it
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClusterProfile {
+    pub cluster: String,
+    pub json_rpc_url: String,
+    pub program_id: Pubkey,
+}
+
+impl ClusterProfile {
+    /// Parses the contents of a cluster env file, `KEY=VALUE` lines of which
+    /// `JSON_RPC_URL` and `PROGRAM_ID` are required and `CLUSTER` names the profile.
+    /// The URL isn't under `RPC_URL`, the JS client would take that over the URL of `CLUSTER`.
+    pub fn parse(contents: &str) -> Result<Self, ClusterError> {
+        let mut cluster = None;
+        let mut json_rpc_url = None;
+        let mut program_id = None;
+        for (index, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut parts = line.splitn(2, '=');
+            let key = parts.next().unwrap_or_default().trim();
+            let value = parts
+                .next()
+                .ok_or(ClusterError::InvalidLine(index + 1))?
+                .trim()
+                .trim_matches('"')
+                .to_string();
+            match key {
+                "CLUSTER" => cluster = Some(value),
+                "JSON_RPC_URL" => json_rpc_url = Some(value),
+                "PROGRAM_ID" => {
+                    program_id = Some(
+                        Pubkey::from_str(&value)
+                            .map_err(|_| ClusterError::InvalidProgramId(value))?,
+                    )
+                }
+                _ => {}
+            }
+        }
+        Ok(ClusterProfile {
+            cluster: cluster.ok_or(ClusterError::MissingKey("CLUSTER"))?,
+            json_rpc_url: json_rpc_url.ok_or(ClusterError::MissingKey("JSON_RPC_URL"))?,
+            program_id: program_id.ok_or(ClusterError::MissingKey("PROGRAM_ID"))?,
+        })
+    }
+
+    /// Reads the profile of a cluster env file
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, ClusterError> {
+        Self::parse(&fs::read_to_string(path)?)
+    }
+
+    /// The profile of a cluster the repository ships one for, or of the cluster env file at `name`
+    pub fn from_name(name: &str) -> Result<Self, ClusterError> {
+        match SHIPPED_PROFILES
+            .iter()
+            .find(|(cluster, _)| *cluster == name)
+        {
+            Some((_, contents)) => Self::parse(contents),
+            None if Path::new(name).is_file() => Self::load(name),
+            None => Err(ClusterError::UnknownCluster(name.to_string())),
+        }
+    }
+
+    /// A client of the cluster's RPC endpoint
+    pub fn rpc_client(&self) -> RpcClient {
+        RpcClient::new(self.json_rpc_url.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let profile = ClusterProfile::parse(
+            "LIVE=1\n# a comment\n\nCLUSTER=devnet\nJSON_RPC_URL=\"http://localhost:8899\"\nPROGRAM_ID=4bXpkKSV8swHSnwqtzuboGPaPDeEgAn4Vt8GfarV5rZt\n",
+        )
+        .unwrap();
+        assert_eq!(
+            profile,
+            ClusterProfile {
+                cluster: "devnet".to_string(),
+                json_rpc_url: "http://localhost:8899".to_string(),
+                program_id: Pubkey::from_str("4bXpkKSV8swHSnwqtzuboGPaPDeEgAn4Vt8GfarV5rZt")
+                    .unwrap(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_missing_key() {
+        assert!(matches!(
+            ClusterProfile::parse("CLUSTER=devnet\nJSON_RPC_URL=http://localhost:8899\n"),
+            Err(ClusterError::MissingKey("PROGRAM_ID"))
+        ));
+    }
+
+    #[test]
+    fn test_parse_invalid_line() {
+        assert!(matches!(
+            ClusterProfile::parse("CLUSTER=devnet\nJSON_RPC_URL\n"),
+            Err(ClusterError::InvalidLine(2))
+        ));
+    }
+
+    #[test]
+    fn test_shipped_profiles() {
+        for (name, _) in SHIPPED_PROFILES {
+            assert_eq!(ClusterProfile::from_name(name).unwrap().cluster, *name);
+        }
+        assert!(matches!(
+            ClusterProfile::from_name("localnet"),
+            Err(ClusterError::UnknownCluster(_))
+        ));
+    }
+}
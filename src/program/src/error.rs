@@ -37,6 +37,120 @@ pub enum FaucetError {
     /// Incorrect Mint Authority
     #[error("Incorrect Mint Authority")]
     IncorrectMintAuthority,
+    /// Non Admin Migration Attempt
+    #[error("Non Admin Migration Attempt")]
+    NonAdminMigrationAttempt,
+    /// Faucet Account Not Owned By Faucet Program
+    #[error("Faucet Account Not Owned By Faucet Program")]
+    IncorrectFaucetAccountOwner,
+    /// Mint Account Not Owned By Token Program
+    #[error("Mint Account Not Owned By Token Program")]
+    IncorrectMintAccountOwner,
+    /// Token Account Not Owned By Token Program
+    #[error("Token Account Not Owned By Token Program")]
+    IncorrectTokenAccountOwner,
+    /// Incorrect Token Program Id
+    #[error("Incorrect Token Program Id")]
+    IncorrectTokenProgramId,
+    /// Incorrect Mint Authority Account
+    #[error("Incorrect Mint Authority Account")]
+    IncorrectMintAuthorityAccount,
+    /// Account Not Writable
+    #[error("Account Not Writable")]
+    AccountNotWritable,
+    /// Account Not Signer
+    #[error("Account Not Signer")]
+    AccountNotSigner,
+    /// Cooldown Not Elapsed
+    #[error("Cooldown Not Elapsed")]
+    CooldownNotElapsed,
+    /// Incorrect Claim Record Account
+    #[error("Incorrect Claim Record Account")]
+    IncorrectClaimRecordAccount,
+    /// Incorrect System Program Id
+    #[error("Incorrect System Program Id")]
+    IncorrectSystemProgramId,
+    /// Recipient Limit Exceeded
+    #[error("Recipient Limit Exceeded")]
+    RecipientLimitExceeded,
+    /// Faucet Exhausted
+    #[error("Faucet Exhausted")]
+    FaucetExhausted,
+    /// Non Admin Config Update Attempt
+    #[error("Non Admin Config Update Attempt")]
+    NonAdminConfigUpdateAttempt,
+    /// Non Updatable Faucet Config Update Attempt
+    #[error("Non Updatable Faucet Config Update Attempt")]
+    NonUpdatableFaucetConfigUpdateAttempt,
+    /// Non Admin Admin Change Attempt
+    #[error("Non Admin Admin Change Attempt")]
+    NonAdminAdminChangeAttempt,
+    /// Non Pending Admin Acceptance Attempt
+    #[error("Non Pending Admin Acceptance Attempt")]
+    NonPendingAdminAcceptanceAttempt,
+    /// Non Admin Pause Attempt
+    #[error("Non Admin Pause Attempt")]
+    NonAdminPauseAttempt,
+    /// Faucet Paused
+    #[error("Faucet Paused")]
+    FaucetPaused,
+    /// Incorrect Vault Account
+    #[error("Incorrect Vault Account")]
+    IncorrectVaultAccount,
+    /// Non Admin Withdrawal Attempt
+    #[error("Non Admin Withdrawal Attempt")]
+    NonAdminWithdrawalAttempt,
+    /// Incorrect SOL Vault Account
+    #[error("Incorrect SOL Vault Account")]
+    IncorrectSolVaultAccount,
+    /// Requesting Too Many Lamports
+    #[error("Requesting Too Many Lamports")]
+    RequestingTooManyLamports,
+    /// Incorrect Recipient Account
+    #[error("Incorrect Recipient Account")]
+    IncorrectRecipientAccount,
+    /// Incorrect Associated Token Account
+    #[error("Incorrect Associated Token Account")]
+    IncorrectAssociatedTokenAccount,
+    /// Incorrect Associated Token Account Program Id
+    #[error("Incorrect Associated Token Account Program Id")]
+    IncorrectAssociatedTokenProgramId,
+    /// Incorrect Basket Faucet Account
+    #[error("Incorrect Basket Faucet Account")]
+    IncorrectBasketFaucetAccount,
+    /// Too Many Basket Faucets
+    #[error("Too Many Basket Faucets")]
+    TooManyBasketFaucets,
+    /// Non Admin Batch Mint Attempt
+    #[error("Non Admin Batch Mint Attempt")]
+    NonAdminBatchMintAttempt,
+    /// Incorrect Batch Destination Account
+    #[error("Incorrect Batch Destination Account")]
+    IncorrectBatchDestinationAccount,
+    /// Faucet Not Migrated
+    #[error("Faucet Not Migrated")]
+    FaucetNotMigrated,
+    /// Faucet Already Migrated
+    #[error("Faucet Already Migrated")]
+    FaucetAlreadyMigrated,
+    /// Incorrect Faucet Address
+    #[error("Incorrect Faucet Address")]
+    IncorrectFaucetAddress,
+    /// Untracked Lamports Per Claim
+    #[error("Untracked Lamports Per Claim")]
+    UntrackedLamportsPerClaim,
+    /// Incorrect Faucet Account Length
+    #[error("Incorrect Faucet Account Length")]
+    IncorrectFaucetAccountLength,
+    /// Unsupported Associated Token Account Creation
+    #[error("Unsupported Associated Token Account Creation")]
+    UnsupportedAssociatedTokenAccountCreation,
+    /// Non Admin Basket Creation Attempt
+    #[error("Non Admin Basket Creation Attempt")]
+    NonAdminBasketCreationAttempt,
+    /// Too Many Batch Destinations
+    #[error("Too Many Batch Destinations")]
+    TooManyBatchDestinations,
 }
 
 impl From<FaucetError> for ProgramError {
@@ -1,40 +1,254 @@
-use std::convert::TryInto;
+use std::convert::{TryFrom, TryInto};
 use std::mem::size_of;
 
 use crate::error::FaucetError;
+use crate::state::{Cooldown, Faucet, FaucetConfig, MAX_BASKET_FAUCETS};
+use crate::token::find_associated_token_address;
+use crate::{
+    find_claim_record_address, find_lamport_claim_record_address,
+    find_legacy_mint_authority_address, find_mint_authority_address, find_sol_vault_address,
+};
+use solana_program::instruction::{AccountMeta, Instruction};
 use solana_program::program_error::ProgramError;
+use solana_program::program_option::COption;
+use solana_program::pubkey::{Pubkey, MAX_SEED_LEN};
+use solana_program::{system_program, sysvar};
+
+/// The most destinations a `BatchMint` can mint to, their count is packed into a byte
+pub const MAX_BATCH_DESTINATIONS: usize = u8::MAX as usize;
 
 #[repr(C)]
 #[derive(Clone, Debug, PartialEq)]
 pub enum FaucetInstruction {
     /// Initializes a faucet
     ///
-    /// 0. `[]` Token Mint Account, mint authority must equal Program Derived Address
+    /// 0. `[]` Token Mint Account, mint authority must equal the faucet's Program Derived Address
     /// 1. `[writable]` Faucet Account
     /// 2. `[]` Rent Sysvar
     /// 3. `[optional]` Admin Account
+    ///
+    /// If the faucet account doesn't exist yet, it is created with the rent paid by the payer
+    /// and the accounts to create it are inserted before the admin account:
+    ///
+    /// 3. `[writable/signer]` Payer for the creation of the faucet account
+    /// 4. `[]` The System Program
+    /// 5. `[optional]` Admin Account
+    ///
+    /// Faucets with a label live at the Program Derived Address of their mint and label,
+    /// see `find_faucet_address`, all others at the address of a keypair,
+    /// which has to sign for the creation of the faucet account.
     InitFaucet {
-        /// all other accounts may only mint this amount per ix
-        amount: u64,
+        config: FaucetConfig,
+        label: Option<Vec<u8>>,
     },
     /// Mints Tokens
     ///
-    /// 0. `[]` The mint authority - Program Derived Address
+    /// 0. `[]` The mint authority - Program Derived Address of the faucet
     /// 1. `[writable]` Token Mint Account
     /// 2. `[writable]` Destination Account
-    /// 3. `[]` The SPL Token Program
-    /// 4. `[]` The Faucet Account
+    /// 3. `[]` The token program owning the mint, SPL Token or Token-2022
+    /// 4. `[writable]` The Faucet Account
     /// 5. `[optional/signer]` Admin Account
+    /// 6. `[optional]` Clock Sysvar, to record the slot of the mint, takes the place of the admin account if there is none
+    ///
+    /// If the faucet tracks claims (it has a cooldown or a recipient limit), the claim record
+    /// accounts are inserted before the admin account:
+    ///
+    /// 5. `[writable]` Claim Record Account - Program Derived Address of the faucet and the destination's owner
+    /// 6. `[writable/signer]` Payer for the creation of the claim record
+    /// 7. `[]` The System Program
+    /// 8. `[]` Clock Sysvar
+    /// 9. `[]` Rent Sysvar
+    /// 10. `[optional/signer]` Admin Account
+    ///
+    /// If the faucet has a vault, the tokens are transferred from the vault instead of being minted
+    /// and the vault account is inserted right after the faucet account, shifting all following accounts by one:
+    ///
+    /// 5. `[writable]` Vault Account
+    ///
+    /// If the faucet drips lamports with every claim, the accounts to drip them are inserted
+    /// before the claim record accounts, shifting all following accounts by three:
+    ///
+    /// 5. `[writable]` SOL Vault Account - Program Derived Address of the faucet
+    /// 6. `[writable]` Recipient Account, the owner of the destination account
+    /// 7. `[]` The System Program
     MintTokens { amount: u64 },
-    /// Closes the faucet, can only be done if the faucet has an admin key, transfers mint authority back to admin
+    /// Closes the faucet, can only be done if the faucet has an admin key, transfers mint authority back to admin.
+    /// Faucets with a vault instead transfer the tokens of the vault to the admin and close the vault.
+    /// The lamports of the SOL vault go to the destination account along with the rent.
     ///
     /// 0. `[signer]` Admin account
     /// 1. `[writable]` Faucet account
-    /// 2. `[writable]` Destination account for rent
+    /// 2. `[writable]` Destination account for rent and the lamports of the SOL vault
     /// 3. `[writable]` Mint account
-    /// 4. `[]` The SPL Token Program
-    /// 5. `[]` The PDA
+    /// 4. `[]` The token program owning the mint, SPL Token or Token-2022
+    /// 5. `[]` The PDA of the faucet
+    /// 6. `[writable/optional]` Vault account, required if the faucet has a vault
+    /// 7. `[writable/optional]` Destination token account for the tokens of the vault, required if the faucet has a vault
+    /// 8. `[writable/optional]` SOL Vault Account - Program Derived Address of the faucet, follows the PDA if there is no vault,
+    ///    required if the faucet drips lamports
+    /// 9. `[optional]` The System Program, required if the SOL vault account is passed
     CloseFaucet,
+    /// Moves the mint authority from the legacy PDA shared by all faucets to the faucet's own PDA
+    ///
+    /// 0. `[writable]` Token Mint Account
+    /// 1. `[]` Faucet Account
+    /// 2. `[]` The legacy mint authority - Program Derived Address
+    /// 3. `[]` The token program owning the mint, SPL Token or Token-2022
+    /// 4. `[optional/signer]` Admin Account, required if the faucet has an admin
+    MigrateMintAuthority,
+    /// Replaces the configuration of the faucet, can only be done by the admin
+    ///
+    /// 0. `[signer]` Admin account
+    /// 1. `[writable]` Faucet account
+    UpdateConfig { config: FaucetConfig },
+    /// Proposes a new admin, who only becomes admin once it accepts with `AcceptAdmin`.
+    /// A later proposal replaces an earlier one.
+    ///
+    /// 0. `[signer]` Admin account
+    /// 1. `[writable]` Faucet account
+    ProposeAdmin { new_admin: Pubkey },
+    /// Makes the proposed admin the admin of the faucet
+    ///
+    /// 0. `[signer]` Proposed admin account
+    /// 1. `[writable]` Faucet account
+    AcceptAdmin,
+    /// Removes the admin of the faucet, which makes it permanent and non-closable
+    ///
+    /// 0. `[signer]` Admin account
+    /// 1. `[writable]` Faucet account
+    RenounceAdmin,
+    /// Suspends minting until the faucet is unpaused, can only be done by the admin
+    ///
+    /// 0. `[signer]` Admin account
+    /// 1. `[writable]` Faucet account
+    Pause,
+    /// Resumes minting of a paused faucet, can only be done by the admin
+    ///
+    /// 0. `[signer]` Admin account
+    /// 1. `[writable]` Faucet account
+    Unpause,
+    /// Initializes a faucet that transfers tokens from a vault instead of minting them
+    ///
+    /// 0. `[]` Token Mint Account
+    /// 1. `[writable]` Faucet Account
+    /// 2. `[]` Vault Account, a token account of the mint owned by the faucet's Program Derived Address
+    /// 3. `[]` Rent Sysvar
+    /// 4. `[optional]` Admin Account
+    InitVaultFaucet { config: FaucetConfig },
+    /// Transfers tokens into the vault of a faucet
+    ///
+    /// 0. `[writable]` Source token account
+    /// 1. `[signer]` Owner of the source token account
+    /// 2. `[writable]` Vault account
+    /// 3. `[]` Token Mint Account
+    /// 4. `[]` The token program owning the mint, SPL Token or Token-2022
+    /// 5. `[]` Faucet account
+    Deposit { amount: u64 },
+    /// Transfers tokens out of the vault of a faucet, can only be done by the admin
+    ///
+    /// 0. `[signer]` Admin account
+    /// 1. `[]` Faucet account
+    /// 2. `[writable]` Vault account
+    /// 3. `[writable]` Destination token account
+    /// 4. `[]` Token Mint Account
+    /// 5. `[]` The token program owning the mint, SPL Token or Token-2022
+    /// 6. `[]` The PDA of the faucet
+    Withdraw { amount: u64 },
+    /// Transfers lamports from the SOL vault of the faucet to the recipient
+    ///
+    /// 0. `[writable]` SOL Vault Account - Program Derived Address of the faucet
+    /// 1. `[writable]` Recipient Account
+    /// 2. `[]` The System Program
+    /// 3. `[]` The Faucet Account
+    /// 4. `[optional/signer]` Admin Account
+    ///
+    /// If the faucet has a cooldown, the claim record accounts are inserted before the admin account:
+    ///
+    /// 4. `[writable]` Lamport Claim Record Account - Program Derived Address of the faucet and the recipient
+    /// 5. `[writable/signer]` Payer for the creation of the claim record
+    /// 6. `[]` Clock Sysvar
+    /// 7. `[]` Rent Sysvar
+    /// 8. `[optional/signer]` Admin Account
+    DripLamports { lamports: u64 },
+    /// Transfers lamports into the SOL vault of the faucet
+    ///
+    /// 0. `[writable/signer]` Funding account
+    /// 1. `[writable]` SOL Vault Account - Program Derived Address of the faucet
+    /// 2. `[]` The System Program
+    /// 3. `[]` The Faucet Account
+    FundSolVault { lamports: u64 },
+    /// Transfers lamports out of the SOL vault of the faucet, can only be done by the admin
+    ///
+    /// 0. `[signer]` Admin account
+    /// 1. `[]` The Faucet Account
+    /// 2. `[writable]` SOL Vault Account - Program Derived Address of the faucet
+    /// 3. `[writable]` Destination account
+    /// 4. `[]` The System Program
+    WithdrawLamports { lamports: u64 },
+    /// Mints tokens to the associated token account of the recipient, creating it if it doesn't exist yet.
+    /// Only associated token accounts of SPL Token mints are created, those of Token-2022 mints have to exist.
+    ///
+    /// 0. `[]` The mint authority - Program Derived Address of the faucet
+    /// 1. `[writable]` Token Mint Account
+    /// 2. `[writable]` Associated Token Account of the recipient
+    /// 3. `[]` The token program owning the mint, SPL Token or Token-2022
+    /// 4. `[writable]` The Faucet Account
+    /// 5. `[]` Recipient Account
+    /// 6. `[writable/signer]` Payer for the creation of the associated token account
+    /// 7. `[]` The System Program
+    /// 8. `[]` Rent Sysvar
+    /// 9. `[]` The Associated Token Account Program
+    ///
+    /// followed by the accounts `MintTokens` expects after the faucet account
+    MintToOwner { amount: u64 },
+    /// Initializes a basket of faucets whose tokens are claimed together,
+    /// the creator has to be the admin of every faucet of the basket that has one
+    ///
+    /// 0. `[writable]` Basket Account
+    /// 1. `[signer]` Creator Account
+    /// 2. `[]` Rent Sysvar
+    /// 3. `[]` The Faucet Accounts of the basket, at most `MAX_BASKET_FAUCETS`
+    InitBasket,
+    /// Mints the tokens of every faucet of the basket, one amount per faucet
+    ///
+    /// 0. `[]` Basket Account
+    ///
+    /// followed by the accounts `MintTokens` expects for every faucet of the basket, in the order of the basket,
+    /// without the admin account
+    ///
+    /// n. `[optional/signer]` Admin Account, overrides the limits of the faucets it is the admin of
+    /// n+1. `[optional]` Clock Sysvar, to record the slot of the mint, takes the place of the admin account if there is none
+    MintBasket { amounts: Vec<u64> },
+    /// Mints tokens to many destinations at once, can only be done by the admin
+    ///
+    /// 0. `[signer]` Admin account
+    /// 1. `[writable]` Faucet account
+    /// 2. `[]` The mint authority - Program Derived Address of the faucet
+    /// 3. `[writable]` Token Mint Account
+    /// 4. `[]` The token program owning the mint, SPL Token or Token-2022
+    /// 5. `[writable/optional]` Vault account, required if the faucet has a vault
+    ///
+    /// followed by the `[writable]` destination accounts, in the order of `destinations`,
+    /// at most `MAX_BATCH_DESTINATIONS`
+    ///
+    /// n. `[optional]` Clock Sysvar, to record the slot of the mint
+    BatchMint { destinations: Vec<(Pubkey, u64)> },
+    /// Moves a faucet of an older layout into an account of the current layout.
+    /// Programs can't resize their accounts, so the faucet moves to a new address:
+    /// the mint authority is handed to the new faucet's PDA and the old account is closed,
+    /// its rent goes to the new account. The new account has to be created for the faucet program
+    /// with a length of `Faucet::LEN` beforehand, in the same transaction so nobody else can take it.
+    /// Mints still held by the legacy mint authority have to be migrated with `MigrateMintAuthority` first.
+    ///
+    /// 0. `[writable]` Faucet Account, of an older layout
+    /// 1. `[writable]` New Faucet Account, uninitialized and of the current layout
+    /// 2. `[writable]` Token Mint Account
+    /// 3. `[]` The token program owning the mint, SPL Token or Token-2022
+    /// 4. `[]` The PDA of the faucet
+    /// 5. `[]` Rent Sysvar
+    /// 6. `[optional/signer]` Admin Account, required if the faucet has an admin
+    MigrateFaucet,
 }
 
 impl FaucetInstruction {
@@ -43,22 +257,81 @@ impl FaucetInstruction {
         let (&tag, rest) = input.split_first().ok_or(FaucetError::InvalidInstruction)?;
         Ok(match tag {
             0 => {
-                let amount = rest
-                    .get(..8)
-                    .and_then(|slice| slice.try_into().ok())
-                    .map(u64::from_le_bytes)
-                    .ok_or(FaucetError::InvalidInstruction)?;
-                Self::InitFaucet { amount }
+                let (config, rest) = Self::unpack_config(rest)?;
+                // the label follows the full config, configs of older clients have none
+                let (label, _rest) = if rest.is_empty() {
+                    (None, rest)
+                } else {
+                    Self::unpack_label(rest)?
+                };
+                Self::InitFaucet { config, label }
             }
-            1 => {
-                let amount = rest
-                    .get(..8)
+            1 => Self::MintTokens {
+                amount: Self::unpack_amount(rest)?,
+            },
+            2 => Self::CloseFaucet,
+            3 => Self::MigrateMintAuthority,
+            4 => Self::UpdateConfig {
+                config: Self::unpack_config(rest)?.0,
+            },
+            5 => {
+                let new_admin = rest
+                    .get(..32)
                     .and_then(|slice| slice.try_into().ok())
-                    .map(u64::from_le_bytes)
+                    .map(Pubkey::new_from_array)
                     .ok_or(FaucetError::InvalidInstruction)?;
-                Self::MintTokens { amount }
+                Self::ProposeAdmin { new_admin }
             }
-            2 => Self::CloseFaucet,
+            6 => Self::AcceptAdmin,
+            7 => Self::RenounceAdmin,
+            8 => Self::Pause,
+            9 => Self::Unpause,
+            10 => Self::InitVaultFaucet {
+                config: Self::unpack_config(rest)?.0,
+            },
+            11 => Self::Deposit {
+                amount: Self::unpack_amount(rest)?,
+            },
+            12 => Self::Withdraw {
+                amount: Self::unpack_amount(rest)?,
+            },
+            13 => Self::DripLamports {
+                lamports: Self::unpack_amount(rest)?,
+            },
+            14 => Self::FundSolVault {
+                lamports: Self::unpack_amount(rest)?,
+            },
+            15 => Self::WithdrawLamports {
+                lamports: Self::unpack_amount(rest)?,
+            },
+            16 => Self::MintToOwner {
+                amount: Self::unpack_amount(rest)?,
+            },
+            17 => Self::InitBasket,
+            18 => {
+                let (&count, rest) = rest.split_first().ok_or(FaucetError::InvalidInstruction)?;
+                let amounts = rest
+                    .get(..count as usize * 8)
+                    .ok_or(FaucetError::InvalidInstruction)?
+                    .chunks(8)
+                    .map(Self::unpack_amount)
+                    .collect::<Result<_, _>>()?;
+                Self::MintBasket { amounts }
+            }
+            19 => {
+                let (&count, rest) = rest.split_first().ok_or(FaucetError::InvalidInstruction)?;
+                let destinations = rest
+                    .get(..count as usize * 40)
+                    .ok_or(FaucetError::InvalidInstruction)?
+                    .chunks(40)
+                    .map(|chunk| {
+                        let (destination, amount) = chunk.split_at(32);
+                        Ok((Pubkey::new(destination), Self::unpack_amount(amount)?))
+                    })
+                    .collect::<Result<_, ProgramError>>()?;
+                Self::BatchMint { destinations }
+            }
+            20 => Self::MigrateFaucet,
             _ => return Err(FaucetError::InvalidInstruction.into()),
         })
     }
@@ -66,9 +339,14 @@ impl FaucetInstruction {
     pub fn pack(&self) -> Vec<u8> {
         let mut buf = Vec::with_capacity(size_of::<Self>());
         match self {
-            Self::InitFaucet { amount } => {
+            Self::InitFaucet { config, label } => {
                 buf.push(0);
-                buf.extend_from_slice(&amount.to_le_bytes());
+                Self::pack_config(config, &mut buf);
+                if let Some(label) = label {
+                    buf.push(1);
+                    buf.push(label.len() as u8);
+                    buf.extend_from_slice(label);
+                }
             }
             Self::MintTokens { amount } => {
                 buf.push(1);
@@ -77,10 +355,785 @@ impl FaucetInstruction {
             Self::CloseFaucet => {
                 buf.push(2);
             }
+            Self::MigrateMintAuthority => {
+                buf.push(3);
+            }
+            Self::UpdateConfig { config } => {
+                buf.push(4);
+                Self::pack_config(config, &mut buf);
+            }
+            Self::ProposeAdmin { new_admin } => {
+                buf.push(5);
+                buf.extend_from_slice(new_admin.as_ref());
+            }
+            Self::AcceptAdmin => {
+                buf.push(6);
+            }
+            Self::RenounceAdmin => {
+                buf.push(7);
+            }
+            Self::Pause => {
+                buf.push(8);
+            }
+            Self::Unpause => {
+                buf.push(9);
+            }
+            Self::InitVaultFaucet { config } => {
+                buf.push(10);
+                Self::pack_config(config, &mut buf);
+            }
+            Self::Deposit { amount } => {
+                buf.push(11);
+                buf.extend_from_slice(&amount.to_le_bytes());
+            }
+            Self::Withdraw { amount } => {
+                buf.push(12);
+                buf.extend_from_slice(&amount.to_le_bytes());
+            }
+            Self::DripLamports { lamports } => {
+                buf.push(13);
+                buf.extend_from_slice(&lamports.to_le_bytes());
+            }
+            Self::FundSolVault { lamports } => {
+                buf.push(14);
+                buf.extend_from_slice(&lamports.to_le_bytes());
+            }
+            Self::WithdrawLamports { lamports } => {
+                buf.push(15);
+                buf.extend_from_slice(&lamports.to_le_bytes());
+            }
+            Self::MintToOwner { amount } => {
+                buf.push(16);
+                buf.extend_from_slice(&amount.to_le_bytes());
+            }
+            Self::InitBasket => {
+                buf.push(17);
+            }
+            Self::MintBasket { amounts } => {
+                buf.push(18);
+                assert!(
+                    amounts.len() <= MAX_BASKET_FAUCETS,
+                    "a basket has at most MAX_BASKET_FAUCETS faucets"
+                );
+                buf.push(amounts.len() as u8);
+                for amount in amounts {
+                    buf.extend_from_slice(&amount.to_le_bytes());
+                }
+            }
+            Self::BatchMint { destinations } => {
+                buf.push(19);
+                let count = u8::try_from(destinations.len())
+                    .expect("a batch mints to at most MAX_BATCH_DESTINATIONS destinations");
+                buf.push(count);
+                for (destination, amount) in destinations {
+                    buf.extend_from_slice(destination.as_ref());
+                    buf.extend_from_slice(&amount.to_le_bytes());
+                }
+            }
+            Self::MigrateFaucet => {
+                buf.push(20);
+            }
         }
 
         buf
     }
+
+    fn unpack_amount(input: &[u8]) -> Result<u64, ProgramError> {
+        input
+            .get(..8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or_else(|| FaucetError::InvalidInstruction.into())
+    }
+
+    fn unpack_config(input: &[u8]) -> Result<(FaucetConfig, &[u8]), ProgramError> {
+        let amount = Self::unpack_amount(input)?;
+        // configs of older clients have no limits besides the amount
+        let (cooldown, rest) = Self::unpack_cooldown(&input[8..])?;
+        let (recipient_limit, rest) = Self::unpack_option_u64(rest)?;
+        let (max_total_minted, rest) = Self::unpack_option_u64(rest)?;
+        let (cap_admin_mints, rest) = Self::unpack_bool(rest)?;
+        let (admin_mints_while_paused, rest) = Self::unpack_bool(rest)?;
+        let (lamport_amount, rest) = if rest.is_empty() {
+            (0, rest)
+        } else {
+            (Self::unpack_amount(rest)?, &rest[8..])
+        };
+        let (lamports_per_claim, rest) = Self::unpack_option_u64(rest)?;
+        let config = FaucetConfig {
+            amount,
+            cooldown,
+            recipient_limit,
+            max_total_minted,
+            cap_admin_mints,
+            admin_mints_while_paused,
+            lamport_amount,
+            lamports_per_claim,
+        };
+        Ok((config, rest))
+    }
+
+    fn pack_config(config: &FaucetConfig, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(&config.amount.to_le_bytes());
+        Self::pack_cooldown(&config.cooldown, buf);
+        Self::pack_option_u64(&config.recipient_limit, buf);
+        Self::pack_option_u64(&config.max_total_minted, buf);
+        buf.push(config.cap_admin_mints as u8);
+        buf.push(config.admin_mints_while_paused as u8);
+        buf.extend_from_slice(&config.lamport_amount.to_le_bytes());
+        Self::pack_option_u64(&config.lamports_per_claim, buf);
+    }
+
+    fn unpack_label(input: &[u8]) -> Result<(Option<Vec<u8>>, &[u8]), ProgramError> {
+        match input.split_first() {
+            Some((0, rest)) => Ok((None, rest)),
+            Some((1, rest)) => {
+                let (&len, rest) = rest.split_first().ok_or(FaucetError::InvalidInstruction)?;
+                let len = len as usize;
+                if len > MAX_SEED_LEN || rest.len() < len {
+                    return Err(FaucetError::InvalidInstruction.into());
+                }
+                Ok((Some(rest[..len].to_vec()), &rest[len..]))
+            }
+            _ => Err(FaucetError::InvalidInstruction.into()),
+        }
+    }
+
+    fn unpack_bool(input: &[u8]) -> Result<(bool, &[u8]), ProgramError> {
+        match input.split_first() {
+            None => Ok((false, input)),
+            Some((0, rest)) => Ok((false, rest)),
+            Some((1, rest)) => Ok((true, rest)),
+            _ => Err(FaucetError::InvalidInstruction.into()),
+        }
+    }
+
+    fn unpack_cooldown(input: &[u8]) -> Result<(Option<Cooldown>, &[u8]), ProgramError> {
+        let (&tag, rest) = match input.split_first() {
+            Some(split) => split,
+            None => return Ok((None, input)),
+        };
+        if tag == 0 {
+            return Ok((None, rest));
+        }
+        let value = rest
+            .get(..8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(FaucetError::InvalidInstruction)?;
+        let cooldown = match tag {
+            1 => Cooldown::Slots(value),
+            2 => Cooldown::Seconds(value),
+            _ => return Err(FaucetError::InvalidInstruction.into()),
+        };
+        Ok((Some(cooldown), &rest[8..]))
+    }
+
+    fn unpack_option_u64(input: &[u8]) -> Result<(Option<u64>, &[u8]), ProgramError> {
+        let (&tag, rest) = match input.split_first() {
+            Some(split) => split,
+            None => return Ok((None, input)),
+        };
+        match tag {
+            0 => Ok((None, rest)),
+            1 => {
+                let value = rest
+                    .get(..8)
+                    .and_then(|slice| slice.try_into().ok())
+                    .map(u64::from_le_bytes)
+                    .ok_or(FaucetError::InvalidInstruction)?;
+                Ok((Some(value), &rest[8..]))
+            }
+            _ => Err(FaucetError::InvalidInstruction.into()),
+        }
+    }
+
+    fn pack_option_u64(value: &Option<u64>, buf: &mut Vec<u8>) {
+        match value {
+            None => buf.push(0),
+            Some(value) => {
+                buf.push(1);
+                buf.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+    }
+
+    fn pack_cooldown(cooldown: &Option<Cooldown>, buf: &mut Vec<u8>) {
+        match cooldown {
+            None => buf.push(0),
+            Some(Cooldown::Slots(slots)) => {
+                buf.push(1);
+                buf.extend_from_slice(&slots.to_le_bytes());
+            }
+            Some(Cooldown::Seconds(seconds)) => {
+                buf.push(2);
+                buf.extend_from_slice(&seconds.to_le_bytes());
+            }
+        }
+    }
+}
+
+/// Creates an `InitFaucet` instruction for a faucet that only limits the amount per mint
+pub fn init_faucet(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    faucet: &Pubkey,
+    admin: Option<&Pubkey>,
+    amount: u64,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new(*faucet, false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+    ];
+    if let Some(admin) = admin {
+        accounts.push(AccountMeta::new_readonly(*admin, false));
+    }
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: FaucetInstruction::InitFaucet {
+            config: FaucetConfig {
+                amount,
+                ..FaucetConfig::default()
+            },
+            label: None,
+        }
+        .pack(),
+    }
+}
+
+/// Creates an `InitFaucet` instruction that also creates the faucet account,
+/// the faucet has to sign unless it has a label
+pub fn create_faucet(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    mint: &Pubkey,
+    faucet: &Pubkey,
+    admin: Option<&Pubkey>,
+    label: Option<&[u8]>,
+    config: FaucetConfig,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new(*faucet, label.is_none()),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    if let Some(admin) = admin {
+        accounts.push(AccountMeta::new_readonly(*admin, false));
+    }
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: FaucetInstruction::InitFaucet {
+            config,
+            label: label.map(<[u8]>::to_vec),
+        }
+        .pack(),
+    }
+}
+
+/// Creates a `MintTokens` instruction for a faucet without a vault, a lamport drip or claim tracking,
+/// the accounts of those have to be inserted by the caller
+pub fn mint_tokens(
+    program_id: &Pubkey,
+    token_program_id: &Pubkey,
+    mint: &Pubkey,
+    destination: &Pubkey,
+    faucet: &Pubkey,
+    admin: Option<&Pubkey>,
+    amount: u64,
+) -> Instruction {
+    let (pda, _nonce) = find_mint_authority_address(program_id, faucet);
+    let mut accounts = vec![
+        AccountMeta::new_readonly(pda, false),
+        AccountMeta::new(*mint, false),
+        AccountMeta::new(*destination, false),
+        AccountMeta::new_readonly(*token_program_id, false),
+        AccountMeta::new(*faucet, false),
+    ];
+    if let Some(admin) = admin {
+        accounts.push(AccountMeta::new_readonly(*admin, true));
+    }
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: FaucetInstruction::MintTokens { amount }.pack(),
+    }
+}
+
+/// The accounts the vault, the lamport drip and the claim tracking of a faucet add to `MintTokens`,
+/// they go right after the faucet account of an instruction created by `mint_tokens`, before the admin
+pub fn mint_tokens_faucet_accounts(
+    program_id: &Pubkey,
+    faucet_address: &Pubkey,
+    faucet: &Faucet,
+    recipient: &Pubkey,
+    payer: &Pubkey,
+) -> Vec<AccountMeta> {
+    let mut accounts = vec![];
+    if let COption::Some(vault) = faucet.vault {
+        accounts.push(AccountMeta::new(vault, false));
+    }
+    if faucet.lamports_per_claim.is_some() {
+        let (sol_vault, _nonce) = find_sol_vault_address(program_id, faucet_address);
+        accounts.extend(vec![
+            AccountMeta::new(sol_vault, false),
+            AccountMeta::new(*recipient, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ]);
+    }
+    if faucet.tracks_claims() {
+        let (claim_record, _nonce) =
+            find_claim_record_address(program_id, faucet_address, recipient);
+        accounts.extend(vec![
+            AccountMeta::new(claim_record, false),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+        ]);
+    }
+    accounts
+}
+
+/// Creates a `CloseFaucet` instruction for a faucet without a vault
+pub fn close_faucet(
+    program_id: &Pubkey,
+    token_program_id: &Pubkey,
+    admin: &Pubkey,
+    faucet: &Pubkey,
+    rent_destination: &Pubkey,
+    mint: &Pubkey,
+) -> Instruction {
+    let (pda, _nonce) = find_mint_authority_address(program_id, faucet);
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new(*faucet, false),
+            AccountMeta::new(*rent_destination, false),
+            AccountMeta::new(*mint, false),
+            AccountMeta::new_readonly(*token_program_id, false),
+            AccountMeta::new_readonly(pda, false),
+        ],
+        data: FaucetInstruction::CloseFaucet.pack(),
+    }
+}
+
+/// The accounts that sweep the SOL vault when closing a faucet, they go at the end of an instruction
+/// created by `close_faucet` and are required if the faucet drips lamports
+pub fn close_faucet_sol_vault_accounts(program_id: &Pubkey, faucet: &Pubkey) -> Vec<AccountMeta> {
+    let (sol_vault, _nonce) = find_sol_vault_address(program_id, faucet);
+    vec![
+        AccountMeta::new(sol_vault, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ]
+}
+
+/// Creates a `MigrateMintAuthority` instruction, the admin only signs for faucets that have one
+pub fn migrate_mint_authority(
+    program_id: &Pubkey,
+    token_program_id: &Pubkey,
+    mint: &Pubkey,
+    faucet: &Pubkey,
+    admin: Option<&Pubkey>,
+) -> Instruction {
+    let (legacy_pda, _nonce) = find_legacy_mint_authority_address(program_id);
+    let mut accounts = vec![
+        AccountMeta::new(*mint, false),
+        AccountMeta::new_readonly(*faucet, false),
+        AccountMeta::new_readonly(legacy_pda, false),
+        AccountMeta::new_readonly(*token_program_id, false),
+    ];
+    if let Some(admin) = admin {
+        accounts.push(AccountMeta::new_readonly(*admin, true));
+    }
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: FaucetInstruction::MigrateMintAuthority.pack(),
+    }
+}
+
+/// Creates an `UpdateConfig` instruction
+pub fn update_config(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    faucet: &Pubkey,
+    config: FaucetConfig,
+) -> Instruction {
+    admin_instruction(
+        program_id,
+        admin,
+        faucet,
+        FaucetInstruction::UpdateConfig { config },
+    )
+}
+
+/// Creates a `ProposeAdmin` instruction
+pub fn propose_admin(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    faucet: &Pubkey,
+    new_admin: &Pubkey,
+) -> Instruction {
+    admin_instruction(
+        program_id,
+        admin,
+        faucet,
+        FaucetInstruction::ProposeAdmin {
+            new_admin: *new_admin,
+        },
+    )
+}
+
+/// Creates an `AcceptAdmin` instruction, signed by the proposed admin
+pub fn accept_admin(program_id: &Pubkey, pending_admin: &Pubkey, faucet: &Pubkey) -> Instruction {
+    admin_instruction(
+        program_id,
+        pending_admin,
+        faucet,
+        FaucetInstruction::AcceptAdmin,
+    )
+}
+
+/// Creates a `RenounceAdmin` instruction
+pub fn renounce_admin(program_id: &Pubkey, admin: &Pubkey, faucet: &Pubkey) -> Instruction {
+    admin_instruction(program_id, admin, faucet, FaucetInstruction::RenounceAdmin)
+}
+
+/// Creates a `Pause` instruction
+pub fn pause(program_id: &Pubkey, admin: &Pubkey, faucet: &Pubkey) -> Instruction {
+    admin_instruction(program_id, admin, faucet, FaucetInstruction::Pause)
+}
+
+/// Creates an `Unpause` instruction
+pub fn unpause(program_id: &Pubkey, admin: &Pubkey, faucet: &Pubkey) -> Instruction {
+    admin_instruction(program_id, admin, faucet, FaucetInstruction::Unpause)
+}
+
+/// Creates an `InitVaultFaucet` instruction
+pub fn init_vault_faucet(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    faucet: &Pubkey,
+    vault: &Pubkey,
+    admin: Option<&Pubkey>,
+    config: FaucetConfig,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new(*faucet, false),
+        AccountMeta::new_readonly(*vault, false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+    ];
+    if let Some(admin) = admin {
+        accounts.push(AccountMeta::new_readonly(*admin, false));
+    }
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: FaucetInstruction::InitVaultFaucet { config }.pack(),
+    }
+}
+
+/// Creates a `Deposit` instruction, signed by the owner of the source token account
+#[allow(clippy::too_many_arguments)]
+pub fn deposit(
+    program_id: &Pubkey,
+    token_program_id: &Pubkey,
+    source: &Pubkey,
+    owner: &Pubkey,
+    vault: &Pubkey,
+    mint: &Pubkey,
+    faucet: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*source, false),
+            AccountMeta::new_readonly(*owner, true),
+            AccountMeta::new(*vault, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*token_program_id, false),
+            AccountMeta::new_readonly(*faucet, false),
+        ],
+        data: FaucetInstruction::Deposit { amount }.pack(),
+    }
+}
+
+/// Creates a `Withdraw` instruction
+#[allow(clippy::too_many_arguments)]
+pub fn withdraw(
+    program_id: &Pubkey,
+    token_program_id: &Pubkey,
+    admin: &Pubkey,
+    faucet: &Pubkey,
+    vault: &Pubkey,
+    destination: &Pubkey,
+    mint: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let (pda, _nonce) = find_mint_authority_address(program_id, faucet);
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new_readonly(*faucet, false),
+            AccountMeta::new(*vault, false),
+            AccountMeta::new(*destination, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*token_program_id, false),
+            AccountMeta::new_readonly(pda, false),
+        ],
+        data: FaucetInstruction::Withdraw { amount }.pack(),
+    }
+}
+
+/// Creates a `DripLamports` instruction. Faucets with a cooldown record the claim,
+/// they need the payer of the claim record, the admin only signs for admin drips.
+pub fn drip_lamports(
+    program_id: &Pubkey,
+    faucet: &Pubkey,
+    recipient: &Pubkey,
+    claim_record_payer: Option<&Pubkey>,
+    admin: Option<&Pubkey>,
+    lamports: u64,
+) -> Instruction {
+    let (sol_vault, _nonce) = find_sol_vault_address(program_id, faucet);
+    let mut accounts = vec![
+        AccountMeta::new(sol_vault, false),
+        AccountMeta::new(*recipient, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(*faucet, false),
+    ];
+    if let Some(payer) = claim_record_payer {
+        let (claim_record, _nonce) =
+            find_lamport_claim_record_address(program_id, faucet, recipient);
+        accounts.extend(vec![
+            AccountMeta::new(claim_record, false),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+        ]);
+    }
+    if let Some(admin) = admin {
+        accounts.push(AccountMeta::new_readonly(*admin, true));
+    }
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: FaucetInstruction::DripLamports { lamports }.pack(),
+    }
+}
+
+/// Creates a `FundSolVault` instruction
+pub fn fund_sol_vault(
+    program_id: &Pubkey,
+    funder: &Pubkey,
+    faucet: &Pubkey,
+    lamports: u64,
+) -> Instruction {
+    let (sol_vault, _nonce) = find_sol_vault_address(program_id, faucet);
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*funder, true),
+            AccountMeta::new(sol_vault, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(*faucet, false),
+        ],
+        data: FaucetInstruction::FundSolVault { lamports }.pack(),
+    }
+}
+
+/// Creates a `WithdrawLamports` instruction
+pub fn withdraw_lamports(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    faucet: &Pubkey,
+    destination: &Pubkey,
+    lamports: u64,
+) -> Instruction {
+    let (sol_vault, _nonce) = find_sol_vault_address(program_id, faucet);
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new_readonly(*faucet, false),
+            AccountMeta::new(sol_vault, false),
+            AccountMeta::new(*destination, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: FaucetInstruction::WithdrawLamports { lamports }.pack(),
+    }
+}
+
+/// Creates a `MintToOwner` instruction for a faucet without a vault, a lamport drip or claim tracking,
+/// the accounts of those and the admin go at the end, see `mint_tokens_faucet_accounts`
+pub fn mint_to_owner(
+    program_id: &Pubkey,
+    token_program_id: &Pubkey,
+    mint: &Pubkey,
+    recipient: &Pubkey,
+    faucet: &Pubkey,
+    payer: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let (pda, _nonce) = find_mint_authority_address(program_id, faucet);
+    let associated_token_account = find_associated_token_address(recipient, mint, token_program_id);
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(pda, false),
+            AccountMeta::new(*mint, false),
+            AccountMeta::new(associated_token_account, false),
+            AccountMeta::new_readonly(*token_program_id, false),
+            AccountMeta::new(*faucet, false),
+            AccountMeta::new_readonly(*recipient, false),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+        ],
+        data: FaucetInstruction::MintToOwner { amount }.pack(),
+    }
+}
+
+/// Creates an `InitBasket` instruction
+pub fn init_basket(
+    program_id: &Pubkey,
+    basket: &Pubkey,
+    creator: &Pubkey,
+    faucets: &[Pubkey],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*basket, false),
+        AccountMeta::new_readonly(*creator, true),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+    ];
+    accounts.extend(
+        faucets
+            .iter()
+            .map(|faucet| AccountMeta::new_readonly(*faucet, false)),
+    );
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: FaucetInstruction::InitBasket.pack(),
+    }
+}
+
+/// Creates a `MintBasket` instruction, the `MintTokens` accounts of the faucets go in the order
+/// of the basket and without their admin accounts, see `mint_tokens`. The Clock sysvar may be appended.
+/// Fails if there are more amounts than a basket has faucets.
+pub fn mint_basket(
+    program_id: &Pubkey,
+    basket: &Pubkey,
+    mint_tokens_accounts: Vec<AccountMeta>,
+    admin: Option<&Pubkey>,
+    amounts: Vec<u64>,
+) -> Result<Instruction, ProgramError> {
+    if amounts.len() > MAX_BASKET_FAUCETS {
+        return Err(FaucetError::TooManyBasketFaucets.into());
+    }
+    let mut accounts = vec![AccountMeta::new_readonly(*basket, false)];
+    accounts.extend(mint_tokens_accounts);
+    if let Some(admin) = admin {
+        accounts.push(AccountMeta::new_readonly(*admin, true));
+    }
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data: FaucetInstruction::MintBasket { amounts }.pack(),
+    })
+}
+
+/// Creates a `BatchMint` instruction, the vault is required if the faucet has one.
+/// Fails if there are more than `MAX_BATCH_DESTINATIONS` destinations.
+pub fn batch_mint(
+    program_id: &Pubkey,
+    token_program_id: &Pubkey,
+    admin: &Pubkey,
+    faucet: &Pubkey,
+    mint: &Pubkey,
+    vault: Option<&Pubkey>,
+    destinations: Vec<(Pubkey, u64)>,
+) -> Result<Instruction, ProgramError> {
+    if destinations.len() > MAX_BATCH_DESTINATIONS {
+        return Err(FaucetError::TooManyBatchDestinations.into());
+    }
+    let (pda, _nonce) = find_mint_authority_address(program_id, faucet);
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*admin, true),
+        AccountMeta::new(*faucet, false),
+        AccountMeta::new_readonly(pda, false),
+        AccountMeta::new(*mint, false),
+        AccountMeta::new_readonly(*token_program_id, false),
+    ];
+    if let Some(vault) = vault {
+        accounts.push(AccountMeta::new(*vault, false));
+    }
+    accounts.extend(
+        destinations
+            .iter()
+            .map(|(destination, _amount)| AccountMeta::new(*destination, false)),
+    );
+    accounts.push(AccountMeta::new_readonly(sysvar::clock::id(), false));
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data: FaucetInstruction::BatchMint { destinations }.pack(),
+    })
+}
+
+/// Creates a `MigrateFaucet` instruction, the admin only signs for faucets that have one
+pub fn migrate_faucet(
+    program_id: &Pubkey,
+    token_program_id: &Pubkey,
+    faucet: &Pubkey,
+    new_faucet: &Pubkey,
+    mint: &Pubkey,
+    admin: Option<&Pubkey>,
+) -> Instruction {
+    let (pda, _nonce) = find_mint_authority_address(program_id, faucet);
+    let mut accounts = vec![
+        AccountMeta::new(*faucet, false),
+        AccountMeta::new(*new_faucet, false),
+        AccountMeta::new(*mint, false),
+        AccountMeta::new_readonly(*token_program_id, false),
+        AccountMeta::new_readonly(pda, false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+    ];
+    if let Some(admin) = admin {
+        accounts.push(AccountMeta::new_readonly(*admin, true));
+    }
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: FaucetInstruction::MigrateFaucet.pack(),
+    }
+}
+
+/// The instructions that only take the signing admin and the faucet
+fn admin_instruction(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    faucet: &Pubkey,
+    instruction: FaucetInstruction,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new(*faucet, false),
+        ],
+        data: instruction.pack(),
+    }
 }
 
 #[cfg(test)]
@@ -89,9 +1142,48 @@ mod test {
 
     #[test]
     fn test_instruction_unpacking() {
-        // 1 tag, 1 admin, 8 amount
+        // 1 tag, 8 amount
         let check = FaucetInstruction::unpack(&[0, 7, 3, 0, 0, 0, 0, 0, 0]).unwrap();
-        assert_eq!(FaucetInstruction::InitFaucet { amount: 775 }, check);
+        assert_eq!(
+            FaucetInstruction::InitFaucet {
+                config: FaucetConfig {
+                    amount: 775,
+                    ..FaucetConfig::default()
+                },
+                label: None,
+            },
+            check
+        );
+        // 1 tag, 8 amount, 1 cooldown tag, 8 cooldown
+        let check =
+            FaucetInstruction::unpack(&[0, 7, 3, 0, 0, 0, 0, 0, 0, 2, 60, 0, 0, 0, 0, 0, 0, 0])
+                .unwrap();
+        assert_eq!(
+            FaucetInstruction::InitFaucet {
+                config: FaucetConfig {
+                    amount: 775,
+                    cooldown: Some(Cooldown::Seconds(60)),
+                    ..FaucetConfig::default()
+                },
+                label: None,
+            },
+            check
+        );
+        // 1 tag, 8 amount, 1 cooldown tag, 1 recipient limit tag, 8 recipient limit
+        let check =
+            FaucetInstruction::unpack(&[0, 7, 3, 0, 0, 0, 0, 0, 0, 0, 1, 232, 3, 0, 0, 0, 0, 0, 0])
+                .unwrap();
+        assert_eq!(
+            FaucetInstruction::InitFaucet {
+                config: FaucetConfig {
+                    amount: 775,
+                    recipient_limit: Some(1000),
+                    ..FaucetConfig::default()
+                },
+                label: None,
+            },
+            check
+        );
         // 1 tag,  8 amount
         let check = FaucetInstruction::unpack(&[1, 7, 3, 0, 0, 0, 0, 0, 0]).unwrap();
         assert_eq!(FaucetInstruction::MintTokens { amount: 775 }, check);
@@ -99,15 +1191,240 @@ mod test {
         // 1 tag
         let check = FaucetInstruction::unpack(&[2]).unwrap();
         assert_eq!(FaucetInstruction::CloseFaucet, check);
+
+        // 1 tag
+        let check = FaucetInstruction::unpack(&[3]).unwrap();
+        assert_eq!(FaucetInstruction::MigrateMintAuthority, check);
+
+        // 1 tag, 8 amount, 1 cooldown tag, 1 recipient limit tag, 1 max total minted tag, 8 max total minted
+        let check = FaucetInstruction::unpack(&[
+            4, 7, 3, 0, 0, 0, 0, 0, 0, 0, 0, 1, 16, 39, 0, 0, 0, 0, 0, 0,
+        ])
+        .unwrap();
+        assert_eq!(
+            FaucetInstruction::UpdateConfig {
+                config: FaucetConfig {
+                    amount: 775,
+                    max_total_minted: Some(10_000),
+                    ..FaucetConfig::default()
+                }
+            },
+            check
+        );
+
+        // 1 tag, 32 new admin
+        let mut input = vec![5];
+        input.extend_from_slice(&[7; 32]);
+        let check = FaucetInstruction::unpack(&input).unwrap();
+        assert_eq!(
+            FaucetInstruction::ProposeAdmin {
+                new_admin: Pubkey::new_from_array([7; 32])
+            },
+            check
+        );
+
+        // 1 tag
+        let check = FaucetInstruction::unpack(&[6]).unwrap();
+        assert_eq!(FaucetInstruction::AcceptAdmin, check);
+
+        // 1 tag
+        let check = FaucetInstruction::unpack(&[7]).unwrap();
+        assert_eq!(FaucetInstruction::RenounceAdmin, check);
+
+        // 1 tag
+        let check = FaucetInstruction::unpack(&[8]).unwrap();
+        assert_eq!(FaucetInstruction::Pause, check);
+
+        // 1 tag
+        let check = FaucetInstruction::unpack(&[9]).unwrap();
+        assert_eq!(FaucetInstruction::Unpause, check);
+
+        // 1 tag, 8 amount
+        let check = FaucetInstruction::unpack(&[10, 7, 3, 0, 0, 0, 0, 0, 0]).unwrap();
+        assert_eq!(
+            FaucetInstruction::InitVaultFaucet {
+                config: FaucetConfig {
+                    amount: 775,
+                    ..FaucetConfig::default()
+                }
+            },
+            check
+        );
+
+        // 1 tag, 8 amount
+        let check = FaucetInstruction::unpack(&[11, 7, 3, 0, 0, 0, 0, 0, 0]).unwrap();
+        assert_eq!(FaucetInstruction::Deposit { amount: 775 }, check);
+
+        // 1 tag, 8 amount
+        let check = FaucetInstruction::unpack(&[12, 7, 3, 0, 0, 0, 0, 0, 0]).unwrap();
+        assert_eq!(FaucetInstruction::Withdraw { amount: 775 }, check);
+
+        // 1 tag, 8 lamports
+        let check = FaucetInstruction::unpack(&[13, 7, 3, 0, 0, 0, 0, 0, 0]).unwrap();
+        assert_eq!(FaucetInstruction::DripLamports { lamports: 775 }, check);
+
+        // 1 tag, 8 lamports
+        let check = FaucetInstruction::unpack(&[14, 7, 3, 0, 0, 0, 0, 0, 0]).unwrap();
+        assert_eq!(FaucetInstruction::FundSolVault { lamports: 775 }, check);
+
+        // 1 tag, 8 lamports
+        let check = FaucetInstruction::unpack(&[15, 7, 3, 0, 0, 0, 0, 0, 0]).unwrap();
+        assert_eq!(FaucetInstruction::WithdrawLamports { lamports: 775 }, check);
+
+        // 1 tag, 8 amount
+        let check = FaucetInstruction::unpack(&[16, 7, 3, 0, 0, 0, 0, 0, 0]).unwrap();
+        assert_eq!(FaucetInstruction::MintToOwner { amount: 775 }, check);
+
+        let check = FaucetInstruction::unpack(&[17]).unwrap();
+        assert_eq!(FaucetInstruction::InitBasket, check);
+
+        // 1 tag, 1 count, 8 amount per faucet
+        let check =
+            FaucetInstruction::unpack(&[18, 2, 7, 3, 0, 0, 0, 0, 0, 0, 100, 0, 0, 0, 0, 0, 0, 0])
+                .unwrap();
+        assert_eq!(
+            FaucetInstruction::MintBasket {
+                amounts: vec![775, 100]
+            },
+            check
+        );
+
+        // fewer amounts than announced
+        let check = FaucetInstruction::unpack(&[18, 2, 7, 3, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(Err(FaucetError::InvalidInstruction.into()), check);
+
+        // 1 tag, 1 count, 32 destination and 8 amount per destination
+        let destination = Pubkey::new_unique();
+        let mut input = vec![19, 1];
+        input.extend_from_slice(destination.as_ref());
+        input.extend_from_slice(&[7, 3, 0, 0, 0, 0, 0, 0]);
+        let check = FaucetInstruction::unpack(&input).unwrap();
+        assert_eq!(
+            FaucetInstruction::BatchMint {
+                destinations: vec![(destination, 775)]
+            },
+            check
+        );
+
+        let check = FaucetInstruction::unpack(&[20]).unwrap();
+        assert_eq!(FaucetInstruction::MigrateFaucet, check);
+
+        // 1 tag, 8 amount, 5 limits, 8 lamport amount, 1 lamports per claim tag,
+        // 1 label tag, 1 label length, 4 label
+        let mut input = vec![0, 7, 3, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        input.extend_from_slice(&[0; 9]);
+        input.extend_from_slice(&[1, 4, b'u', b's', b'd', b'c']);
+        let check = FaucetInstruction::unpack(&input).unwrap();
+        assert_eq!(
+            FaucetInstruction::InitFaucet {
+                config: FaucetConfig {
+                    amount: 775,
+                    ..FaucetConfig::default()
+                },
+                label: Some(b"usdc".to_vec()),
+            },
+            check
+        );
+
+        // labels are seeds and can't be longer than those
+        let mut input = vec![0, 7, 3, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        input.extend_from_slice(&[0; 9]);
+        input.extend_from_slice(&[1, 33]);
+        input.extend_from_slice(&[b'a'; 33]);
+        let check = FaucetInstruction::unpack(&input);
+        assert_eq!(Err(FaucetError::InvalidInstruction.into()), check);
+    }
+
+    #[test]
+    #[should_panic(expected = "a basket has at most MAX_BASKET_FAUCETS faucets")]
+    fn test_mint_basket_packing_too_many_amounts() {
+        FaucetInstruction::MintBasket {
+            amounts: vec![1; MAX_BASKET_FAUCETS + 1],
+        }
+        .pack();
+    }
+
+    #[test]
+    fn test_mint_basket_too_many_amounts() {
+        let check = mint_basket(
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            vec![],
+            None,
+            vec![1; MAX_BASKET_FAUCETS + 1],
+        );
+        assert_eq!(Err(FaucetError::TooManyBasketFaucets.into()), check);
+    }
+
+    #[test]
+    #[should_panic(expected = "a batch mints to at most MAX_BATCH_DESTINATIONS destinations")]
+    fn test_batch_mint_packing_too_many_destinations() {
+        FaucetInstruction::BatchMint {
+            destinations: vec![(Pubkey::new_unique(), 1); MAX_BATCH_DESTINATIONS + 1],
+        }
+        .pack();
+    }
+
+    #[test]
+    fn test_batch_mint_too_many_destinations() {
+        let check = batch_mint(
+            &Pubkey::new_unique(),
+            &spl_token::id(),
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            None,
+            vec![(Pubkey::new_unique(), 1); MAX_BATCH_DESTINATIONS + 1],
+        );
+        assert_eq!(Err(FaucetError::TooManyBatchDestinations.into()), check);
     }
 
     #[test]
     fn test_instruction_packing() {
-        let check = FaucetInstruction::InitFaucet { amount: 900 };
+        let check = FaucetInstruction::InitFaucet {
+            config: FaucetConfig {
+                amount: 900,
+                ..FaucetConfig::default()
+            },
+            label: None,
+        };
+
+        let packed = check.pack();
+        let mut expect = vec![0];
+        expect.extend_from_slice(&u64::to_le_bytes(900));
+        expect.extend_from_slice(&[0, 0, 0, 0, 0]);
+        expect.extend_from_slice(&u64::to_le_bytes(0));
+        expect.push(0);
+        assert_eq!(packed, expect);
+
+        let check = FaucetInstruction::InitFaucet {
+            config: FaucetConfig {
+                amount: 900,
+                cooldown: Some(Cooldown::Slots(150)),
+                recipient_limit: Some(5000),
+                max_total_minted: Some(1_000_000),
+                cap_admin_mints: true,
+                admin_mints_while_paused: true,
+                lamport_amount: 890_880,
+                lamports_per_claim: Some(10_000),
+            },
+            label: None,
+        };
 
         let packed = check.pack();
         let mut expect = vec![0];
         expect.extend_from_slice(&u64::to_le_bytes(900));
+        expect.push(1);
+        expect.extend_from_slice(&u64::to_le_bytes(150));
+        expect.push(1);
+        expect.extend_from_slice(&u64::to_le_bytes(5000));
+        expect.push(1);
+        expect.extend_from_slice(&u64::to_le_bytes(1_000_000));
+        expect.push(1);
+        expect.push(1);
+        expect.extend_from_slice(&u64::to_le_bytes(890_880));
+        expect.push(1);
+        expect.extend_from_slice(&u64::to_le_bytes(10_000));
         assert_eq!(packed, expect);
 
         let check = FaucetInstruction::MintTokens { amount: 900 };
@@ -121,5 +1438,167 @@ mod test {
 
         let packed = check.pack();
         assert_eq!(packed, vec![2]);
+
+        let check = FaucetInstruction::MigrateMintAuthority;
+
+        let packed = check.pack();
+        assert_eq!(packed, vec![3]);
+
+        let check = FaucetInstruction::UpdateConfig {
+            config: FaucetConfig {
+                amount: 900,
+                cooldown: Some(Cooldown::Seconds(60)),
+                ..FaucetConfig::default()
+            },
+        };
+
+        let packed = check.pack();
+        let mut expect = vec![4];
+        expect.extend_from_slice(&u64::to_le_bytes(900));
+        expect.push(2);
+        expect.extend_from_slice(&u64::to_le_bytes(60));
+        expect.extend_from_slice(&[0, 0, 0, 0]);
+        expect.extend_from_slice(&u64::to_le_bytes(0));
+        expect.push(0);
+        assert_eq!(packed, expect);
+
+        let check = FaucetInstruction::ProposeAdmin {
+            new_admin: Pubkey::new_from_array([7; 32]),
+        };
+
+        let packed = check.pack();
+        let mut expect = vec![5];
+        expect.extend_from_slice(&[7; 32]);
+        assert_eq!(packed, expect);
+
+        let check = FaucetInstruction::AcceptAdmin;
+
+        let packed = check.pack();
+        assert_eq!(packed, vec![6]);
+
+        let check = FaucetInstruction::RenounceAdmin;
+
+        let packed = check.pack();
+        assert_eq!(packed, vec![7]);
+
+        let check = FaucetInstruction::Pause;
+
+        let packed = check.pack();
+        assert_eq!(packed, vec![8]);
+
+        let check = FaucetInstruction::Unpause;
+
+        let packed = check.pack();
+        assert_eq!(packed, vec![9]);
+
+        let check = FaucetInstruction::InitVaultFaucet {
+            config: FaucetConfig {
+                amount: 900,
+                ..FaucetConfig::default()
+            },
+        };
+
+        let packed = check.pack();
+        let mut expect = vec![10];
+        expect.extend_from_slice(&u64::to_le_bytes(900));
+        expect.extend_from_slice(&[0, 0, 0, 0, 0]);
+        expect.extend_from_slice(&u64::to_le_bytes(0));
+        expect.push(0);
+        assert_eq!(packed, expect);
+
+        let check = FaucetInstruction::Deposit { amount: 900 };
+
+        let packed = check.pack();
+        let mut expect = vec![11];
+        expect.extend_from_slice(&u64::to_le_bytes(900));
+        assert_eq!(packed, expect);
+
+        let check = FaucetInstruction::Withdraw { amount: 900 };
+
+        let packed = check.pack();
+        let mut expect = vec![12];
+        expect.extend_from_slice(&u64::to_le_bytes(900));
+        assert_eq!(packed, expect);
+
+        let check = FaucetInstruction::DripLamports { lamports: 900 };
+
+        let packed = check.pack();
+        let mut expect = vec![13];
+        expect.extend_from_slice(&u64::to_le_bytes(900));
+        assert_eq!(packed, expect);
+
+        let check = FaucetInstruction::FundSolVault { lamports: 900 };
+
+        let packed = check.pack();
+        let mut expect = vec![14];
+        expect.extend_from_slice(&u64::to_le_bytes(900));
+        assert_eq!(packed, expect);
+
+        let check = FaucetInstruction::WithdrawLamports { lamports: 900 };
+
+        let packed = check.pack();
+        let mut expect = vec![15];
+        expect.extend_from_slice(&u64::to_le_bytes(900));
+        assert_eq!(packed, expect);
+
+        let check = FaucetInstruction::MintToOwner { amount: 900 };
+
+        let packed = check.pack();
+        let mut expect = vec![16];
+        expect.extend_from_slice(&u64::to_le_bytes(900));
+        assert_eq!(packed, expect);
+
+        let check = FaucetInstruction::InitBasket;
+
+        let packed = check.pack();
+        let expect = vec![17];
+        assert_eq!(packed, expect);
+
+        let check = FaucetInstruction::MintBasket {
+            amounts: vec![900, 25],
+        };
+
+        let packed = check.pack();
+        let mut expect = vec![18, 2];
+        expect.extend_from_slice(&u64::to_le_bytes(900));
+        expect.extend_from_slice(&u64::to_le_bytes(25));
+        assert_eq!(packed, expect);
+
+        let destinations = vec![(Pubkey::new_unique(), 900), (Pubkey::new_unique(), 25)];
+        let check = FaucetInstruction::BatchMint {
+            destinations: destinations.clone(),
+        };
+
+        let packed = check.pack();
+        let mut expect = vec![19, 2];
+        for (destination, amount) in destinations {
+            expect.extend_from_slice(destination.as_ref());
+            expect.extend_from_slice(&u64::to_le_bytes(amount));
+        }
+        assert_eq!(packed, expect);
+
+        let check = FaucetInstruction::MigrateFaucet;
+
+        let packed = check.pack();
+        let expect = vec![20];
+        assert_eq!(packed, expect);
+
+        let check = FaucetInstruction::InitFaucet {
+            config: FaucetConfig {
+                amount: 900,
+                ..FaucetConfig::default()
+            },
+            label: Some(b"usdc".to_vec()),
+        };
+
+        let packed = check.pack();
+        let mut expect = vec![0];
+        expect.extend_from_slice(&u64::to_le_bytes(900));
+        expect.extend_from_slice(&[0, 0, 0, 0, 0]);
+        expect.extend_from_slice(&u64::to_le_bytes(0));
+        expect.push(0);
+        expect.extend_from_slice(&[1, 4]);
+        expect.extend_from_slice(b"usdc");
+        assert_eq!(packed, expect);
     }
 }
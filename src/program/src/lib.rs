@@ -1,9 +1,81 @@
+#[cfg(all(feature = "client", not(target_arch = "bpf")))]
+pub mod client;
 pub mod error;
 pub mod instruction;
 pub mod processor;
 pub mod state;
+pub mod token;
+pub mod validation;
 
-solana_program::declare_id!("4bXpkKSV8swHSnwqtzuboGPaPDeEgAn4Vt8GfarV5rZt");
+use solana_program::pubkey::Pubkey;
+
+// The program id is picked by the build script, see `build.rs`
+include!(concat!(env!("OUT_DIR"), "/program_id.rs"));
 
 #[cfg(not(feature = "no-entrypoint"))]
 mod entrypoint;
+
+/// Seed of the faucet PDAs
+pub const FAUCET_SEED: &[u8] = b"faucet";
+
+/// Derives the canonical faucet of a mint, faucets of the same mint are told apart by their label
+pub fn find_faucet_address(program_id: &Pubkey, mint: &Pubkey, label: &[u8]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[FAUCET_SEED, mint.as_ref(), label], program_id)
+}
+
+/// Seed of the mint authority PDAs
+pub const MINT_AUTHORITY_SEED: &[u8] = b"faucet";
+
+/// Derives the mint authority of a faucet, every faucet has its own
+pub fn find_mint_authority_address(program_id: &Pubkey, faucet: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[MINT_AUTHORITY_SEED, faucet.as_ref()], program_id)
+}
+
+/// Derives the mint authority that was shared by all faucets before
+/// mint authorities were derived per faucet. Only used to migrate mints away from it.
+pub fn find_legacy_mint_authority_address(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[MINT_AUTHORITY_SEED], program_id)
+}
+
+/// Seed of the claim record PDAs
+pub const CLAIM_RECORD_SEED: &[u8] = b"claim";
+
+/// Derives the claim record of a recipient wallet at a faucet
+pub fn find_claim_record_address(
+    program_id: &Pubkey,
+    faucet: &Pubkey,
+    recipient: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[CLAIM_RECORD_SEED, faucet.as_ref(), recipient.as_ref()],
+        program_id,
+    )
+}
+
+/// Seed of the claim record PDAs of lamport drips, which are tracked apart from token claims
+pub const LAMPORT_CLAIM_RECORD_SEED: &[u8] = b"lamport_claim";
+
+/// Derives the claim record of a recipient wallet for lamport drips at a faucet
+pub fn find_lamport_claim_record_address(
+    program_id: &Pubkey,
+    faucet: &Pubkey,
+    recipient: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            LAMPORT_CLAIM_RECORD_SEED,
+            faucet.as_ref(),
+            recipient.as_ref(),
+        ],
+        program_id,
+    )
+}
+
+/// Seed of the SOL vault PDAs
+pub const SOL_VAULT_SEED: &[u8] = b"sol_vault";
+
+/// Derives the SOL vault of a faucet, a system account that lamports are dripped from.
+/// Anyone may fund it with a plain transfer.
+pub fn find_sol_vault_address(program_id: &Pubkey, faucet: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SOL_VAULT_SEED, faucet.as_ref()], program_id)
+}
@@ -1,19 +1,35 @@
 use solana_program::{
     account_info::next_account_info,
     account_info::AccountInfo,
+    clock::Clock,
     entrypoint::ProgramResult,
-    info,
+    msg,
+    program::{invoke, invoke_signed},
     program_error::ProgramError,
     program_option::COption,
     program_pack::Pack,
     pubkey::Pubkey,
-    sysvar::{rent::Rent, Sysvar},
+    system_instruction, system_program,
+    sysvar::{self, rent::Rent, Sysvar},
 };
-use spl_token::state::Mint;
 
 use crate::error::FaucetError;
 use crate::instruction::FaucetInstruction;
-use crate::state::Faucet;
+use crate::state::{Basket, ClaimRecord, Faucet, FaucetConfig, MAX_BASKET_FAUCETS};
+use crate::token::{
+    self, create_associated_token_account, find_associated_token_address, unpack_account,
+    unpack_mint,
+};
+use crate::validation::{
+    check_associated_token_program, check_claim_record, check_config, check_faucet_account,
+    check_mint_account, check_mint_authority, check_signer, check_system_program,
+    check_token_account, check_token_program, check_vault, check_writable,
+};
+use crate::{
+    find_faucet_address, find_legacy_mint_authority_address, find_mint_authority_address,
+    find_sol_vault_address, CLAIM_RECORD_SEED, FAUCET_SEED, LAMPORT_CLAIM_RECORD_SEED,
+    MINT_AUTHORITY_SEED, SOL_VAULT_SEED,
+};
 
 pub struct Processor;
 
@@ -21,51 +37,191 @@ impl Processor {
     pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], input: &[u8]) -> ProgramResult {
         let instruction = FaucetInstruction::unpack(input)?;
         match instruction {
-            FaucetInstruction::InitFaucet { amount } => {
-                info!("Instruction: InitFaucet");
-                Self::process_init_faucet(accounts, amount, program_id)?
+            FaucetInstruction::InitFaucet { config, label } => {
+                msg!("Instruction: InitFaucet");
+                Self::process_init_faucet(accounts, &config, label.as_deref(), false, program_id)?
             }
             FaucetInstruction::MintTokens { amount } => {
-                info!("Instruction: MintTokens");
+                msg!("Instruction: MintTokens");
                 Self::process_mint_tokens(accounts, amount, program_id)?
             }
             FaucetInstruction::CloseFaucet => {
-                info!("Instruction: CloseFaucet");
+                msg!("Instruction: CloseFaucet");
                 Self::process_close_faucet(accounts, program_id)?
             }
+            FaucetInstruction::MigrateMintAuthority => {
+                msg!("Instruction: MigrateMintAuthority");
+                Self::process_migrate_mint_authority(accounts, program_id)?
+            }
+            FaucetInstruction::UpdateConfig { config } => {
+                msg!("Instruction: UpdateConfig");
+                Self::process_update_config(accounts, &config, program_id)?
+            }
+            FaucetInstruction::ProposeAdmin { new_admin } => {
+                msg!("Instruction: ProposeAdmin");
+                Self::process_propose_admin(accounts, &new_admin, program_id)?
+            }
+            FaucetInstruction::AcceptAdmin => {
+                msg!("Instruction: AcceptAdmin");
+                Self::process_accept_admin(accounts, program_id)?
+            }
+            FaucetInstruction::RenounceAdmin => {
+                msg!("Instruction: RenounceAdmin");
+                Self::process_renounce_admin(accounts, program_id)?
+            }
+            FaucetInstruction::Pause => {
+                msg!("Instruction: Pause");
+                Self::process_set_paused(accounts, true, program_id)?
+            }
+            FaucetInstruction::Unpause => {
+                msg!("Instruction: Unpause");
+                Self::process_set_paused(accounts, false, program_id)?
+            }
+            FaucetInstruction::InitVaultFaucet { config } => {
+                msg!("Instruction: InitVaultFaucet");
+                Self::process_init_faucet(accounts, &config, None, true, program_id)?
+            }
+            FaucetInstruction::Deposit { amount } => {
+                msg!("Instruction: Deposit");
+                Self::process_deposit(accounts, amount, program_id)?
+            }
+            FaucetInstruction::Withdraw { amount } => {
+                msg!("Instruction: Withdraw");
+                Self::process_withdraw(accounts, amount, program_id)?
+            }
+            FaucetInstruction::DripLamports { lamports } => {
+                msg!("Instruction: DripLamports");
+                Self::process_drip_lamports(accounts, lamports, program_id)?
+            }
+            FaucetInstruction::FundSolVault { lamports } => {
+                msg!("Instruction: FundSolVault");
+                Self::process_fund_sol_vault(accounts, lamports, program_id)?
+            }
+            FaucetInstruction::WithdrawLamports { lamports } => {
+                msg!("Instruction: WithdrawLamports");
+                Self::process_withdraw_lamports(accounts, lamports, program_id)?
+            }
+            FaucetInstruction::MintToOwner { amount } => {
+                msg!("Instruction: MintToOwner");
+                Self::process_mint_to_owner(accounts, amount, program_id)?
+            }
+            FaucetInstruction::InitBasket => {
+                msg!("Instruction: InitBasket");
+                Self::process_init_basket(accounts, program_id)?
+            }
+            FaucetInstruction::MintBasket { amounts } => {
+                msg!("Instruction: MintBasket");
+                Self::process_mint_basket(accounts, &amounts, program_id)?
+            }
+            FaucetInstruction::BatchMint { destinations } => {
+                msg!("Instruction: BatchMint");
+                Self::process_batch_mint(accounts, &destinations, program_id)?
+            }
+            FaucetInstruction::MigrateFaucet => {
+                msg!("Instruction: MigrateFaucet");
+                Self::process_migrate_faucet(accounts, program_id)?
+            }
         }
         Ok(())
     }
 
     pub fn process_init_faucet(
         accounts: &[AccountInfo],
-        amount: u64,
+        config: &FaucetConfig,
+        label: Option<&[u8]>,
+        with_vault: bool,
         program_id: &Pubkey,
     ) -> ProgramResult {
         let account_info_iter = &mut accounts.iter();
 
-        let (pda, _nonce) = Pubkey::find_program_address(&[b"faucet"], program_id);
-
         let mint_account = next_account_info(account_info_iter)?;
-        let mint_state = Mint::unpack(&mint_account.data.borrow())?;
-
-        if pda
-            != mint_state
-                .mint_authority
-                .ok_or(ProgramError::InvalidAccountData)?
-        {
-            return Err(FaucetError::IncorrectMintAuthority.into());
-        }
+        check_mint_account(mint_account)?;
+        let mint_state = unpack_mint(&mint_account.data.borrow())?;
 
         let faucet_account = next_account_info(account_info_iter)?;
+        check_writable(faucet_account)?;
+        let faucet_nonce = match label {
+            Some(label) => {
+                let (faucet, nonce) = find_faucet_address(program_id, mint_account.key, label);
+                if faucet != *faucet_account.key {
+                    return Err(FaucetError::IncorrectFaucetAddress.into());
+                }
+                Some(nonce)
+            }
+            None => None,
+        };
+
+        let (pda, _nonce) = find_mint_authority_address(program_id, faucet_account.key);
+
+        let vault = if with_vault {
+            let vault_acc = next_account_info(account_info_iter)?;
+            check_token_account(vault_acc, mint_account.owner)?;
+            let vault_state = unpack_account(&vault_acc.data.borrow())?;
+            // nobody but the faucet may move the tokens of the vault
+            if vault_state.mint != *mint_account.key
+                || vault_state.owner != pda
+                || vault_state.delegate.is_some()
+                || vault_state.close_authority.is_some()
+            {
+                return Err(FaucetError::IncorrectVaultAccount.into());
+            }
+            COption::Some(*vault_acc.key)
+        } else {
+            if pda
+                != mint_state
+                    .mint_authority
+                    .ok_or(ProgramError::InvalidAccountData)?
+            {
+                return Err(FaucetError::IncorrectMintAuthority.into());
+            }
+            COption::None
+        };
+
+        check_config(config)?;
+
+        let rent = &Rent::from_account_info(next_account_info(account_info_iter)?)?;
+
+        // faucet accounts that don't exist yet are still owned by the system program
+        if *faucet_account.owner == system_program::id() {
+            let payer_acc = next_account_info(account_info_iter)?;
+            check_signer(payer_acc)?;
+            check_writable(payer_acc)?;
+
+            let system_program_acc = next_account_info(account_info_iter)?;
+            check_system_program(system_program_acc)?;
+
+            msg!("Calling the system program to create the faucet account");
+            match label.zip(faucet_nonce) {
+                Some((label, nonce)) => Self::create_program_account(
+                    program_id,
+                    Faucet::LEN,
+                    rent,
+                    payer_acc,
+                    faucet_account,
+                    system_program_acc,
+                    &[&[FAUCET_SEED, mint_account.key.as_ref(), label, &[nonce]]],
+                )?,
+                None => {
+                    check_signer(faucet_account)?;
+                    Self::create_program_account(
+                        program_id,
+                        Faucet::LEN,
+                        rent,
+                        payer_acc,
+                        faucet_account,
+                        system_program_acc,
+                        &[],
+                    )?
+                }
+            }
+        }
 
+        check_faucet_account(program_id, faucet_account)?;
         let mut faucet = Faucet::unpack_unchecked(&faucet_account.data.borrow())?;
         if faucet.is_initialized {
             return Err(FaucetError::AccountAlreadyInUse.into());
         }
 
-        let rent = &Rent::from_account_info(next_account_info(account_info_iter)?)?;
-
         if !rent.is_exempt(faucet_account.lamports(), faucet_account.data_len()) {
             return Err(FaucetError::AccountNotRentExempt.into());
         }
@@ -79,8 +235,9 @@ impl Processor {
 
         faucet.is_initialized = true;
         faucet.admin = admin_pubkey;
-        faucet.amount = amount;
         faucet.mint = *mint_account.key;
+        faucet.vault = vault;
+        faucet.set_config(config);
 
         Faucet::pack(faucet, &mut faucet_account.data.borrow_mut())?;
 
@@ -95,70 +252,485 @@ impl Processor {
         let account_info_iter = &mut accounts.iter();
 
         let pda_account = next_account_info(account_info_iter)?;
-        let (pda, nonce) = Pubkey::find_program_address(&[b"faucet"], program_id);
-
-        if pda != *pda_account.key {
-            return Err(ProgramError::InvalidAccountData);
-        }
 
         let mint_acc = next_account_info(account_info_iter)?;
+        check_mint_account(mint_acc)?;
+        check_writable(mint_acc)?;
+
         let token_dest_acc = next_account_info(account_info_iter)?;
+        check_token_account(token_dest_acc, mint_acc.owner)?;
+        check_writable(token_dest_acc)?;
+
         let token_program = next_account_info(account_info_iter)?;
+        check_token_program(token_program, mint_acc.owner)?;
 
         let faucet_acc = next_account_info(account_info_iter)?;
+        check_faucet_account(program_id, faucet_acc)?;
+        check_writable(faucet_acc)?;
+
+        let (pda, nonce) = find_mint_authority_address(program_id, faucet_acc.key);
+        check_mint_authority(&pda, pda_account)?;
 
-        let faucet = Faucet::unpack_from_slice(&faucet_acc.data.borrow())?;
+        let mut faucet = Faucet::unpack_versioned(&faucet_acc.data.borrow())?;
 
         if faucet.mint != *mint_acc.key {
             return Err(FaucetError::InvalidMint.into());
         }
 
-        let admin_acc = next_account_info(account_info_iter);
+        let vault_acc = if faucet.vault.is_some() {
+            let vault_acc = next_account_info(account_info_iter)?;
+            check_vault(&faucet.vault, vault_acc)?;
+            check_writable(vault_acc)?;
+            Some(vault_acc)
+        } else {
+            None
+        };
+
+        let lamport_accs = if faucet.lamports_per_claim.is_some() {
+            Some((
+                next_account_info(account_info_iter)?,
+                next_account_info(account_info_iter)?,
+                next_account_info(account_info_iter)?,
+            ))
+        } else {
+            None
+        };
+
+        let claim_record_accs = if faucet.tracks_claims() {
+            Some((
+                next_account_info(account_info_iter)?,
+                next_account_info(account_info_iter)?,
+                next_account_info(account_info_iter)?,
+                next_account_info(account_info_iter)?,
+                next_account_info(account_info_iter)?,
+            ))
+        } else {
+            None
+        };
+
+        let mut admin_acc = next_account_info(account_info_iter);
+        // the Clock sysvar to record the slot of the mint is optional,
+        // it follows the admin account or takes its place if there is none
+        let mut clock_acc = next_account_info(account_info_iter).ok();
+        if let Ok(acc) = admin_acc {
+            if sysvar::clock::check_id(acc.key) {
+                clock_acc = Some(acc);
+                admin_acc = Err(ProgramError::NotEnoughAccountKeys);
+            }
+        }
+
+        let is_admin = match (faucet.admin, admin_acc) {
+            (COption::Some(admin_pubkey), Ok(acc)) => acc.is_signer && admin_pubkey == *acc.key,
+            _ => false,
+        };
+
+        if faucet.paused && !(is_admin && faucet.admin_mints_while_paused) {
+            return Err(FaucetError::FaucetPaused.into());
+        }
+
+        if !is_admin {
+            if amount > faucet.amount {
+                return Err(FaucetError::RequestingTooManyTokens.into());
+            }
+
+            if let Some((claim_record_acc, payer_acc, system_program_acc, clock_acc, rent_acc)) =
+                claim_record_accs
+            {
+                let recipient = unpack_account(&token_dest_acc.data.borrow())?.owner;
+                let clock = Clock::from_account_info(clock_acc)?;
+                let rent = Rent::from_account_info(rent_acc)?;
+
+                let mut claim_record = Self::load_claim_record(
+                    program_id,
+                    &[
+                        CLAIM_RECORD_SEED,
+                        faucet_acc.key.as_ref(),
+                        recipient.as_ref(),
+                    ],
+                    claim_record_acc,
+                    payer_acc,
+                    system_program_acc,
+                    &rent,
+                )?;
+
+                if claim_record.is_cooling_down(&faucet.cooldown, clock.slot, clock.unix_timestamp)
+                {
+                    return Err(FaucetError::CooldownNotElapsed.into());
+                }
+
+                let total_claimed = claim_record
+                    .total_claimed
+                    .checked_add(amount)
+                    .ok_or(FaucetError::Overflow)?;
+                if let Some(recipient_limit) = faucet.recipient_limit {
+                    if total_claimed > recipient_limit {
+                        return Err(FaucetError::RecipientLimitExceeded.into());
+                    }
+                }
+
+                if !claim_record.is_initialized {
+                    faucet.unique_recipients = faucet
+                        .unique_recipients
+                        .checked_add(1)
+                        .ok_or(FaucetError::Overflow)?;
+                }
+                claim_record.is_initialized = true;
+                claim_record.total_claimed = total_claimed;
+                claim_record.last_claim_slot = clock.slot;
+                claim_record.last_claim_timestamp = clock.unix_timestamp;
+                ClaimRecord::pack(claim_record, &mut claim_record_acc.data.borrow_mut())?;
+            }
+        }
+
+        let total_minted = faucet
+            .total_minted
+            .checked_add(amount)
+            .ok_or(FaucetError::Overflow)?;
+        if let Some(max_total_minted) = faucet.max_total_minted {
+            if (!is_admin || faucet.cap_admin_mints) && total_minted > max_total_minted {
+                return Err(FaucetError::FaucetExhausted.into());
+            }
+        }
+        faucet.total_minted = total_minted;
+        faucet.mint_count = faucet
+            .mint_count
+            .checked_add(1)
+            .ok_or(FaucetError::Overflow)?;
+        if let Some(clock_acc) = clock_acc.or_else(|| claim_record_accs.map(|accs| accs.3)) {
+            faucet.last_mint_slot = Clock::from_account_info(clock_acc)?.slot;
+        }
+        Faucet::pack_versioned(faucet, &mut faucet_acc.data.borrow_mut())?;
 
-        if (faucet.admin.is_none()
-            || match admin_acc {
-                Ok(acc) => !acc.is_signer || faucet.admin.unwrap() != *acc.key,
-                Err(_) => true,
-            })
-            && amount > faucet.amount
+        // claims of nothing don't drip, they would drain the SOL vault without counting against any limit
+        let lamport_accs = lamport_accs.filter(|_| amount > 0);
+        if let (Some(lamports), Some((sol_vault_acc, recipient_acc, system_program_acc))) =
+            (faucet.lamports_per_claim, lamport_accs)
         {
-            return Err(FaucetError::RequestingTooManyTokens.into());
+            if unpack_account(&token_dest_acc.data.borrow())?.owner != *recipient_acc.key {
+                return Err(FaucetError::IncorrectRecipientAccount.into());
+            }
+            check_writable(recipient_acc)?;
+            Self::transfer_from_sol_vault(
+                program_id,
+                faucet_acc.key,
+                sol_vault_acc,
+                recipient_acc,
+                system_program_acc,
+                lamports,
+            )?;
         }
 
-        let ix = spl_token::instruction::mint_to(
-            token_program.key,
-            mint_acc.key,
-            token_dest_acc.key,
-            &pda,
-            &[],
+        let signer_seeds: &[&[u8]] = &[MINT_AUTHORITY_SEED, faucet_acc.key.as_ref(), &[nonce]];
+        Self::send_tokens(
+            pda_account,
+            mint_acc,
+            vault_acc,
+            token_dest_acc,
+            token_program,
+            signer_seeds,
             amount,
-        )?;
+        )
+    }
+
+    pub fn process_mint_to_owner(
+        accounts: &[AccountInfo],
+        amount: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let pda_account = next_account_info(account_info_iter)?;
+
+        let mint_acc = next_account_info(account_info_iter)?;
+        check_mint_account(mint_acc)?;
+
+        let token_dest_acc = next_account_info(account_info_iter)?;
+
+        let token_program = next_account_info(account_info_iter)?;
+        check_token_program(token_program, mint_acc.owner)?;
+
+        let faucet_acc = next_account_info(account_info_iter)?;
+
+        let recipient_acc = next_account_info(account_info_iter)?;
+        let payer_acc = next_account_info(account_info_iter)?;
+        let system_program_acc = next_account_info(account_info_iter)?;
+        let rent_acc = next_account_info(account_info_iter)?;
+
+        let ata_program_acc = next_account_info(account_info_iter)?;
+        check_associated_token_program(ata_program_acc)?;
+
+        if find_associated_token_address(recipient_acc.key, mint_acc.key, token_program.key)
+            != *token_dest_acc.key
+        {
+            return Err(FaucetError::IncorrectAssociatedTokenAccount.into());
+        }
+
+        if token_dest_acc.data_is_empty() {
+            // the associated token account program only creates accounts of SPL Token
+            if *token_program.key != spl_token::id() {
+                return Err(FaucetError::UnsupportedAssociatedTokenAccountCreation.into());
+            }
+            check_signer(payer_acc)?;
+            check_writable(payer_acc)?;
+            check_writable(token_dest_acc)?;
+            check_system_program(system_program_acc)?;
+
+            msg!("Calling the associated token account program to create the destination account");
+            invoke(
+                &create_associated_token_account(
+                    payer_acc.key,
+                    recipient_acc.key,
+                    mint_acc.key,
+                    token_program.key,
+                ),
+                &[
+                    payer_acc.clone(),
+                    token_dest_acc.clone(),
+                    recipient_acc.clone(),
+                    mint_acc.clone(),
+                    system_program_acc.clone(),
+                    token_program.clone(),
+                    rent_acc.clone(),
+                    ata_program_acc.clone(),
+                ],
+            )?;
+        }
+
+        // the remaining accounts are the ones MintTokens expects after the faucet account
+        let mut mint_tokens_accounts = vec![
+            pda_account.clone(),
+            mint_acc.clone(),
+            token_dest_acc.clone(),
+            token_program.clone(),
+            faucet_acc.clone(),
+        ];
+        mint_tokens_accounts.extend(account_info_iter.cloned());
+
+        Self::process_mint_tokens(&mint_tokens_accounts, amount, program_id)
+    }
+
+    pub fn process_init_basket(accounts: &[AccountInfo], program_id: &Pubkey) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let basket_acc = next_account_info(account_info_iter)?;
+        check_faucet_account(program_id, basket_acc)?;
+        check_writable(basket_acc)?;
+
+        let mut basket = Basket::unpack_unchecked(&basket_acc.data.borrow())?;
+        if basket.is_initialized {
+            return Err(FaucetError::AccountAlreadyInUse.into());
+        }
+
+        let creator_acc = next_account_info(account_info_iter)?;
+        check_signer(creator_acc)?;
+
+        let rent = &Rent::from_account_info(next_account_info(account_info_iter)?)?;
+
+        if !rent.is_exempt(basket_acc.lamports(), basket_acc.data_len()) {
+            return Err(FaucetError::AccountNotRentExempt.into());
+        }
+
+        let mut faucets = vec![];
+        for faucet_acc in account_info_iter {
+            check_faucet_account(program_id, faucet_acc)?;
+            let faucet = Faucet::unpack_versioned(&faucet_acc.data.borrow())?;
+            // faucets without an admin can be claimed from by anyone, so anyone may basket them
+            if let COption::Some(admin) = faucet.admin {
+                if admin != *creator_acc.key {
+                    return Err(FaucetError::NonAdminBasketCreationAttempt.into());
+                }
+            }
+            faucets.push(*faucet_acc.key);
+        }
+        if faucets.len() > MAX_BASKET_FAUCETS {
+            return Err(FaucetError::TooManyBasketFaucets.into());
+        }
+
+        basket.is_initialized = true;
+        basket.creator = *creator_acc.key;
+        basket.faucets = faucets;
+
+        Basket::pack(basket, &mut basket_acc.data.borrow_mut())?;
 
-        info!("Calling the token program to mint tokens");
-        solana_program::program::invoke_signed(
-            &ix,
-            &[
-                mint_acc.clone(),
-                token_dest_acc.clone(),
-                pda_account.clone(),
-                token_program.clone(),
-            ],
-            &[&[&b"faucet"[..], &[nonce]]],
-        )?;
         Ok(())
     }
 
-    pub fn process_close_faucet(accounts: &[AccountInfo], program_id: &Pubkey) -> ProgramResult {
+    pub fn process_mint_basket(
+        accounts: &[AccountInfo],
+        amounts: &[u64],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let (basket_acc, mut remaining_accs) = accounts
+            .split_first()
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+        check_faucet_account(program_id, basket_acc)?;
+
+        let basket = Basket::unpack(&basket_acc.data.borrow())?;
+        if amounts.len() != basket.faucets.len() {
+            return Err(FaucetError::InvalidInstruction.into());
+        }
+
+        let mut groups = Vec::with_capacity(basket.faucets.len());
+        for faucet_key in &basket.faucets {
+            // the faucet decides how many accounts MintTokens needs for it
+            let faucet_acc = remaining_accs
+                .get(4)
+                .ok_or(ProgramError::NotEnoughAccountKeys)?;
+            if faucet_acc.key != faucet_key {
+                return Err(FaucetError::IncorrectBasketFaucetAccount.into());
+            }
+            check_faucet_account(program_id, faucet_acc)?;
+            let faucet = Faucet::unpack_versioned(&faucet_acc.data.borrow())?;
+
+            let group_len = Self::mint_tokens_accounts_len(&faucet);
+            if remaining_accs.len() < group_len {
+                return Err(ProgramError::NotEnoughAccountKeys);
+            }
+            let (group, rest) = remaining_accs.split_at(group_len);
+            groups.push(group);
+            remaining_accs = rest;
+        }
+
+        // the admin account and the Clock sysvar go to every MintTokens call,
+        // which tells them apart the way it does for a single faucet
+        if remaining_accs.len() > 2 {
+            return Err(FaucetError::InvalidInstruction.into());
+        }
+
+        for (group, &amount) in groups.into_iter().zip(amounts) {
+            let mut mint_tokens_accounts = group.to_vec();
+            mint_tokens_accounts.extend(remaining_accs.iter().cloned());
+            Self::process_mint_tokens(&mint_tokens_accounts, amount, program_id)?;
+        }
+
+        Ok(())
+    }
+
+    /// How many accounts `MintTokens` expects for the faucet, without the admin account
+    fn mint_tokens_accounts_len(faucet: &Faucet) -> usize {
+        let mut len = 5;
+        if faucet.vault.is_some() {
+            len += 1;
+        }
+        if faucet.lamports_per_claim.is_some() {
+            len += 3;
+        }
+        if faucet.tracks_claims() {
+            len += 5;
+        }
+        len
+    }
+
+    pub fn process_batch_mint(
+        accounts: &[AccountInfo],
+        destinations: &[(Pubkey, u64)],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
         let account_info_iter = &mut accounts.iter();
 
         let admin_acc = next_account_info(account_info_iter)?;
+        check_signer(admin_acc)?;
+
         let faucet_acc = next_account_info(account_info_iter)?;
-        let faucet = Faucet::unpack(&faucet_acc.data.borrow())?;
+        check_faucet_account(program_id, faucet_acc)?;
+        check_writable(faucet_acc)?;
+        let mut faucet = Faucet::unpack_versioned(&faucet_acc.data.borrow())?;
+
+        if faucet.admin != COption::Some(*admin_acc.key) {
+            return Err(FaucetError::NonAdminBatchMintAttempt.into());
+        }
+
+        if faucet.paused && !faucet.admin_mints_while_paused {
+            return Err(FaucetError::FaucetPaused.into());
+        }
+
+        let (pda, nonce) = find_mint_authority_address(program_id, faucet_acc.key);
+        let pda_acc = next_account_info(account_info_iter)?;
+        check_mint_authority(&pda, pda_acc)?;
+
+        let mint_acc = next_account_info(account_info_iter)?;
+        check_mint_account(mint_acc)?;
+        check_writable(mint_acc)?;
+        if faucet.mint != *mint_acc.key {
+            return Err(FaucetError::InvalidMint.into());
+        }
+
+        let token_program = next_account_info(account_info_iter)?;
+        check_token_program(token_program, mint_acc.owner)?;
+
+        let vault_acc = if faucet.vault.is_some() {
+            let vault_acc = next_account_info(account_info_iter)?;
+            check_vault(&faucet.vault, vault_acc)?;
+            check_writable(vault_acc)?;
+            Some(vault_acc)
+        } else {
+            None
+        };
+
+        // every destination is checked before the first tokens are sent
+        let mut token_dest_accs = Vec::with_capacity(destinations.len());
+        let mut total_minted = faucet.total_minted;
+        for (destination, amount) in destinations {
+            let token_dest_acc = next_account_info(account_info_iter)?;
+            if token_dest_acc.key != destination {
+                return Err(FaucetError::IncorrectBatchDestinationAccount.into());
+            }
+            check_token_account(token_dest_acc, mint_acc.owner)?;
+            check_writable(token_dest_acc)?;
+            if unpack_account(&token_dest_acc.data.borrow())?.mint != *mint_acc.key {
+                return Err(FaucetError::InvalidMint.into());
+            }
+            token_dest_accs.push(token_dest_acc);
+
+            total_minted = total_minted
+                .checked_add(*amount)
+                .ok_or(FaucetError::Overflow)?;
+        }
+
+        if let Some(max_total_minted) = faucet.max_total_minted {
+            if faucet.cap_admin_mints && total_minted > max_total_minted {
+                return Err(FaucetError::FaucetExhausted.into());
+            }
+        }
+        faucet.total_minted = total_minted;
+        faucet.mint_count = faucet
+            .mint_count
+            .checked_add(destinations.len() as u64)
+            .ok_or(FaucetError::Overflow)?;
+        if let Some(clock_acc) = account_info_iter.next() {
+            faucet.last_mint_slot = Clock::from_account_info(clock_acc)?.slot;
+        }
+        Faucet::pack_versioned(faucet, &mut faucet_acc.data.borrow_mut())?;
 
-        if !admin_acc.is_signer {
-            return Err(ProgramError::MissingRequiredSignature);
+        let signer_seeds: &[&[u8]] = &[MINT_AUTHORITY_SEED, faucet_acc.key.as_ref(), &[nonce]];
+        for (token_dest_acc, (_destination, amount)) in
+            token_dest_accs.into_iter().zip(destinations)
+        {
+            Self::send_tokens(
+                pda_acc,
+                mint_acc,
+                vault_acc,
+                token_dest_acc,
+                token_program,
+                signer_seeds,
+                *amount,
+            )?;
         }
 
+        Ok(())
+    }
+
+    pub fn process_close_faucet(accounts: &[AccountInfo], program_id: &Pubkey) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let admin_acc = next_account_info(account_info_iter)?;
+        check_signer(admin_acc)?;
+
+        let faucet_acc = next_account_info(account_info_iter)?;
+        check_faucet_account(program_id, faucet_acc)?;
+        check_writable(faucet_acc)?;
+        let faucet = Faucet::unpack_versioned(&faucet_acc.data.borrow())?;
+
         match faucet.admin {
             COption::None => return Err(FaucetError::NonClosableFaucetClosureAttempt.into()),
             COption::Some(admin_pubkey) => {
@@ -169,6 +741,117 @@ impl Processor {
         }
 
         let dest_acc = next_account_info(account_info_iter)?;
+        check_writable(dest_acc)?;
+
+        let mint_acc = next_account_info(account_info_iter)?;
+        check_mint_account(mint_acc)?;
+        check_writable(mint_acc)?;
+
+        let spl_program_acc = next_account_info(account_info_iter)?;
+        check_token_program(spl_program_acc, mint_acc.owner)?;
+
+        let (pda, nonce) = find_mint_authority_address(program_id, faucet_acc.key);
+        let pda_acc = next_account_info(account_info_iter)?;
+        check_mint_authority(&pda, pda_acc)?;
+
+        let signer_seeds: &[&[u8]] = &[MINT_AUTHORITY_SEED, faucet_acc.key.as_ref(), &[nonce]];
+
+        let vault_accs = if faucet.vault.is_some() {
+            let vault_acc = next_account_info(account_info_iter)?;
+            check_vault(&faucet.vault, vault_acc)?;
+            check_writable(vault_acc)?;
+
+            let token_dest_acc = next_account_info(account_info_iter)?;
+            check_token_account(token_dest_acc, mint_acc.owner)?;
+            check_writable(token_dest_acc)?;
+            Some((vault_acc, token_dest_acc))
+        } else {
+            None
+        };
+
+        // the SOL vault is swept as well, its lamports would be stuck otherwise.
+        // It only has to be passed if the faucet drips lamports, so faucets that don't keep their accounts.
+        let sol_vault_acc = if faucet.drips_lamports() {
+            Some(next_account_info(account_info_iter)?)
+        } else {
+            account_info_iter.next()
+        };
+        if let Some(sol_vault_acc) = sol_vault_acc {
+            let system_program_acc = next_account_info(account_info_iter)?;
+            let (sol_vault, _nonce) = find_sol_vault_address(program_id, faucet_acc.key);
+            if sol_vault != *sol_vault_acc.key {
+                return Err(FaucetError::IncorrectSolVaultAccount.into());
+            }
+            let sol_vault_lamports = sol_vault_acc.lamports();
+            if sol_vault_lamports > 0 {
+                Self::transfer_from_sol_vault(
+                    program_id,
+                    faucet_acc.key,
+                    sol_vault_acc,
+                    dest_acc,
+                    system_program_acc,
+                    sol_vault_lamports,
+                )?;
+            }
+        }
+
+        if let Some((vault_acc, token_dest_acc)) = vault_accs {
+            let vault_amount = unpack_account(&vault_acc.data.borrow())?.amount;
+            let decimals = unpack_mint(&mint_acc.data.borrow())?.decimals;
+
+            let sweep_ix = token::transfer_checked(
+                spl_program_acc.key,
+                vault_acc.key,
+                mint_acc.key,
+                token_dest_acc.key,
+                &pda,
+                vault_amount,
+                decimals,
+            );
+
+            msg!("Calling the token program to sweep the vault");
+            invoke_signed(
+                &sweep_ix,
+                &[
+                    vault_acc.clone(),
+                    mint_acc.clone(),
+                    token_dest_acc.clone(),
+                    pda_acc.clone(),
+                    spl_program_acc.clone(),
+                ],
+                &[signer_seeds],
+            )?;
+
+            let close_vault_ix =
+                token::close_account(spl_program_acc.key, vault_acc.key, dest_acc.key, &pda);
+
+            msg!("Calling the token program to close the vault");
+            invoke_signed(
+                &close_vault_ix,
+                &[
+                    vault_acc.clone(),
+                    dest_acc.clone(),
+                    pda_acc.clone(),
+                    spl_program_acc.clone(),
+                ],
+                &[signer_seeds],
+            )?;
+        } else {
+            let transfer_authority_ix = token::set_authority(
+                spl_program_acc.key,
+                mint_acc.key,
+                Some(admin_acc.key),
+                spl_token::instruction::AuthorityType::MintTokens,
+                &pda,
+            );
+
+            invoke_signed(
+                &transfer_authority_ix,
+                &[mint_acc.clone(), pda_acc.clone(), spl_program_acc.clone()],
+                &[signer_seeds],
+            )?;
+        }
+
         let dest_starting_lamports = dest_acc.lamports();
         **dest_acc.lamports.borrow_mut() = dest_starting_lamports
             .checked_add(faucet_acc.lamports())
@@ -176,30 +859,725 @@ impl Processor {
 
         **faucet_acc.lamports.borrow_mut() = 0;
 
+        Ok(())
+    }
+
+    pub fn process_migrate_mint_authority(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
         let mint_acc = next_account_info(account_info_iter)?;
+        check_mint_account(mint_acc)?;
+        check_writable(mint_acc)?;
+
+        let faucet_acc = next_account_info(account_info_iter)?;
+        check_faucet_account(program_id, faucet_acc)?;
+        let faucet = Faucet::unpack_versioned(&faucet_acc.data.borrow())?;
+
+        if faucet.mint != *mint_acc.key {
+            return Err(FaucetError::InvalidMint.into());
+        }
+
+        let legacy_pda_acc = next_account_info(account_info_iter)?;
+        let (legacy_pda, legacy_nonce) = find_legacy_mint_authority_address(program_id);
+        check_mint_authority(&legacy_pda, legacy_pda_acc)?;
+
         let spl_program_acc = next_account_info(account_info_iter)?;
+        check_token_program(spl_program_acc, mint_acc.owner)?;
 
-        let (pda, nonce) = Pubkey::find_program_address(&[b"faucet"], program_id);
-        let pda_acc = next_account_info(account_info_iter)?;
-        if pda != *pda_acc.key {
-            return Err(ProgramError::InvalidAccountData);
+        // faucets without an admin may be migrated by anyone,
+        // there is no one else who could do it for them
+        if let COption::Some(admin_pubkey) = faucet.admin {
+            let admin_acc = next_account_info(account_info_iter)?;
+            check_signer(admin_acc)?;
+            if *admin_acc.key != admin_pubkey {
+                return Err(FaucetError::NonAdminMigrationAttempt.into());
+            }
         }
 
-        let transfer_authority_ix = spl_token::instruction::set_authority(
+        let (pda, _nonce) = find_mint_authority_address(program_id, faucet_acc.key);
+
+        let transfer_authority_ix = token::set_authority(
             spl_program_acc.key,
             mint_acc.key,
-            Some(admin_acc.key),
+            Some(&pda),
             spl_token::instruction::AuthorityType::MintTokens,
-            &pda,
-            &[],
-        )?;
+            &legacy_pda,
+        );
 
-        solana_program::program::invoke_signed(
+        msg!("Calling the token program to migrate the mint authority");
+        invoke_signed(
             &transfer_authority_ix,
-            &[mint_acc.clone(), pda_acc.clone(), spl_program_acc.clone()],
-            &[&[&b"faucet"[..], &[nonce]]],
+            &[
+                mint_acc.clone(),
+                legacy_pda_acc.clone(),
+                spl_program_acc.clone(),
+            ],
+            &[&[MINT_AUTHORITY_SEED, &[legacy_nonce]]],
         )?;
 
         Ok(())
     }
+
+    pub fn process_migrate_faucet(accounts: &[AccountInfo], program_id: &Pubkey) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let faucet_acc = next_account_info(account_info_iter)?;
+        check_faucet_account(program_id, faucet_acc)?;
+        check_writable(faucet_acc)?;
+        if Faucet::is_current_layout(&faucet_acc.data.borrow()) {
+            return Err(FaucetError::FaucetAlreadyMigrated.into());
+        }
+        let faucet = Faucet::unpack_versioned(&faucet_acc.data.borrow())?;
+
+        // programs can't resize accounts, the faucet moves to an account of the current layout
+        let new_faucet_acc = next_account_info(account_info_iter)?;
+        check_faucet_account(program_id, new_faucet_acc)?;
+        check_writable(new_faucet_acc)?;
+        if new_faucet_acc.data_len() != Faucet::LEN {
+            return Err(FaucetError::IncorrectFaucetAccountLength.into());
+        }
+        if Faucet::unpack_unchecked(&new_faucet_acc.data.borrow())?.is_initialized {
+            return Err(FaucetError::AccountAlreadyInUse.into());
+        }
+
+        let mint_acc = next_account_info(account_info_iter)?;
+        check_mint_account(mint_acc)?;
+        check_writable(mint_acc)?;
+        if faucet.mint != *mint_acc.key {
+            return Err(FaucetError::InvalidMint.into());
+        }
+
+        let spl_program_acc = next_account_info(account_info_iter)?;
+        check_token_program(spl_program_acc, mint_acc.owner)?;
+
+        let (pda, nonce) = find_mint_authority_address(program_id, faucet_acc.key);
+        let pda_acc = next_account_info(account_info_iter)?;
+        check_mint_authority(&pda, pda_acc)?;
+
+        let rent = &Rent::from_account_info(next_account_info(account_info_iter)?)?;
+
+        // faucets without an admin may be migrated by anyone,
+        // there is no one else who could do it for them
+        if let COption::Some(admin_pubkey) = faucet.admin {
+            let admin_acc = next_account_info(account_info_iter)?;
+            check_signer(admin_acc)?;
+            if *admin_acc.key != admin_pubkey {
+                return Err(FaucetError::NonAdminMigrationAttempt.into());
+            }
+        }
+
+        // the rent of the old account goes to the new one, nobody else may claim it
+        let new_faucet_lamports = new_faucet_acc
+            .lamports()
+            .checked_add(faucet_acc.lamports())
+            .ok_or(FaucetError::Overflow)?;
+        if !rent.is_exempt(new_faucet_lamports, new_faucet_acc.data_len()) {
+            return Err(FaucetError::AccountNotRentExempt.into());
+        }
+        **new_faucet_acc.lamports.borrow_mut() = new_faucet_lamports;
+        **faucet_acc.lamports.borrow_mut() = 0;
+        for byte in faucet_acc.data.borrow_mut().iter_mut() {
+            *byte = 0;
+        }
+
+        Faucet::pack(faucet, &mut new_faucet_acc.data.borrow_mut())?;
+
+        let (new_pda, _nonce) = find_mint_authority_address(program_id, new_faucet_acc.key);
+
+        let transfer_authority_ix = token::set_authority(
+            spl_program_acc.key,
+            mint_acc.key,
+            Some(&new_pda),
+            spl_token::instruction::AuthorityType::MintTokens,
+            &pda,
+        );
+
+        msg!("Calling the token program to hand the mint authority to the migrated faucet");
+        invoke_signed(
+            &transfer_authority_ix,
+            &[mint_acc.clone(), pda_acc.clone(), spl_program_acc.clone()],
+            &[&[MINT_AUTHORITY_SEED, faucet_acc.key.as_ref(), &[nonce]]],
+        )?;
+
+        Ok(())
+    }
+
+    pub fn process_update_config(
+        accounts: &[AccountInfo],
+        config: &FaucetConfig,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let admin_acc = next_account_info(account_info_iter)?;
+        check_signer(admin_acc)?;
+
+        let faucet_acc = next_account_info(account_info_iter)?;
+        check_faucet_account(program_id, faucet_acc)?;
+        check_writable(faucet_acc)?;
+        let mut faucet = Faucet::unpack_versioned(&faucet_acc.data.borrow())?;
+
+        match faucet.admin {
+            COption::None => return Err(FaucetError::NonUpdatableFaucetConfigUpdateAttempt.into()),
+            COption::Some(admin_pubkey) => {
+                if *admin_acc.key != admin_pubkey {
+                    return Err(FaucetError::NonAdminConfigUpdateAttempt.into());
+                }
+            }
+        }
+
+        check_config(config)?;
+        faucet.set_config(config);
+
+        Faucet::pack_versioned(faucet, &mut faucet_acc.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    pub fn process_propose_admin(
+        accounts: &[AccountInfo],
+        new_admin: &Pubkey,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let admin_acc = next_account_info(account_info_iter)?;
+        check_signer(admin_acc)?;
+
+        let faucet_acc = next_account_info(account_info_iter)?;
+        check_faucet_account(program_id, faucet_acc)?;
+        check_writable(faucet_acc)?;
+        let mut faucet = Faucet::unpack_versioned(&faucet_acc.data.borrow())?;
+
+        if faucet.admin != COption::Some(*admin_acc.key) {
+            return Err(FaucetError::NonAdminAdminChangeAttempt.into());
+        }
+
+        faucet.pending_admin = COption::Some(*new_admin);
+
+        Faucet::pack_versioned(faucet, &mut faucet_acc.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    pub fn process_accept_admin(accounts: &[AccountInfo], program_id: &Pubkey) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let new_admin_acc = next_account_info(account_info_iter)?;
+        check_signer(new_admin_acc)?;
+
+        let faucet_acc = next_account_info(account_info_iter)?;
+        check_faucet_account(program_id, faucet_acc)?;
+        check_writable(faucet_acc)?;
+        let mut faucet = Faucet::unpack_versioned(&faucet_acc.data.borrow())?;
+
+        if faucet.pending_admin != COption::Some(*new_admin_acc.key) {
+            return Err(FaucetError::NonPendingAdminAcceptanceAttempt.into());
+        }
+
+        faucet.admin = faucet.pending_admin;
+        faucet.pending_admin = COption::None;
+
+        Faucet::pack_versioned(faucet, &mut faucet_acc.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    pub fn process_renounce_admin(accounts: &[AccountInfo], program_id: &Pubkey) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let admin_acc = next_account_info(account_info_iter)?;
+        check_signer(admin_acc)?;
+
+        let faucet_acc = next_account_info(account_info_iter)?;
+        check_faucet_account(program_id, faucet_acc)?;
+        check_writable(faucet_acc)?;
+        let mut faucet = Faucet::unpack_versioned(&faucet_acc.data.borrow())?;
+
+        if faucet.admin != COption::Some(*admin_acc.key) {
+            return Err(FaucetError::NonAdminAdminChangeAttempt.into());
+        }
+
+        faucet.admin = COption::None;
+        faucet.pending_admin = COption::None;
+
+        Faucet::pack_versioned(faucet, &mut faucet_acc.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    pub fn process_set_paused(
+        accounts: &[AccountInfo],
+        paused: bool,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let admin_acc = next_account_info(account_info_iter)?;
+        check_signer(admin_acc)?;
+
+        let faucet_acc = next_account_info(account_info_iter)?;
+        check_faucet_account(program_id, faucet_acc)?;
+        check_writable(faucet_acc)?;
+        let mut faucet = Faucet::unpack_versioned(&faucet_acc.data.borrow())?;
+
+        if faucet.admin != COption::Some(*admin_acc.key) {
+            return Err(FaucetError::NonAdminPauseAttempt.into());
+        }
+
+        faucet.paused = paused;
+
+        Faucet::pack_versioned(faucet, &mut faucet_acc.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    pub fn process_deposit(
+        accounts: &[AccountInfo],
+        amount: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let source_acc = next_account_info(account_info_iter)?;
+        check_writable(source_acc)?;
+
+        let owner_acc = next_account_info(account_info_iter)?;
+        check_signer(owner_acc)?;
+
+        let vault_acc = next_account_info(account_info_iter)?;
+        check_writable(vault_acc)?;
+
+        let mint_acc = next_account_info(account_info_iter)?;
+        check_mint_account(mint_acc)?;
+        check_token_account(source_acc, mint_acc.owner)?;
+
+        let token_program = next_account_info(account_info_iter)?;
+        check_token_program(token_program, mint_acc.owner)?;
+
+        let faucet_acc = next_account_info(account_info_iter)?;
+        check_faucet_account(program_id, faucet_acc)?;
+        let faucet = Faucet::unpack_versioned(&faucet_acc.data.borrow())?;
+
+        if faucet.mint != *mint_acc.key {
+            return Err(FaucetError::InvalidMint.into());
+        }
+        check_vault(&faucet.vault, vault_acc)?;
+
+        let decimals = unpack_mint(&mint_acc.data.borrow())?.decimals;
+        let ix = token::transfer_checked(
+            token_program.key,
+            source_acc.key,
+            mint_acc.key,
+            vault_acc.key,
+            owner_acc.key,
+            amount,
+            decimals,
+        );
+
+        msg!("Calling the token program to transfer tokens into the vault");
+        invoke(
+            &ix,
+            &[
+                source_acc.clone(),
+                mint_acc.clone(),
+                vault_acc.clone(),
+                owner_acc.clone(),
+                token_program.clone(),
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    pub fn process_withdraw(
+        accounts: &[AccountInfo],
+        amount: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let admin_acc = next_account_info(account_info_iter)?;
+        check_signer(admin_acc)?;
+
+        let faucet_acc = next_account_info(account_info_iter)?;
+        check_faucet_account(program_id, faucet_acc)?;
+        let faucet = Faucet::unpack_versioned(&faucet_acc.data.borrow())?;
+
+        if faucet.admin != COption::Some(*admin_acc.key) {
+            return Err(FaucetError::NonAdminWithdrawalAttempt.into());
+        }
+
+        let vault_acc = next_account_info(account_info_iter)?;
+        check_vault(&faucet.vault, vault_acc)?;
+        check_writable(vault_acc)?;
+
+        let token_dest_acc = next_account_info(account_info_iter)?;
+        check_writable(token_dest_acc)?;
+
+        let mint_acc = next_account_info(account_info_iter)?;
+        check_mint_account(mint_acc)?;
+        check_token_account(token_dest_acc, mint_acc.owner)?;
+        if faucet.mint != *mint_acc.key {
+            return Err(FaucetError::InvalidMint.into());
+        }
+
+        let token_program = next_account_info(account_info_iter)?;
+        check_token_program(token_program, mint_acc.owner)?;
+
+        let (pda, nonce) = find_mint_authority_address(program_id, faucet_acc.key);
+        let pda_acc = next_account_info(account_info_iter)?;
+        check_mint_authority(&pda, pda_acc)?;
+
+        let decimals = unpack_mint(&mint_acc.data.borrow())?.decimals;
+        let ix = token::transfer_checked(
+            token_program.key,
+            vault_acc.key,
+            mint_acc.key,
+            token_dest_acc.key,
+            &pda,
+            amount,
+            decimals,
+        );
+
+        msg!("Calling the token program to transfer tokens out of the vault");
+        invoke_signed(
+            &ix,
+            &[
+                vault_acc.clone(),
+                mint_acc.clone(),
+                token_dest_acc.clone(),
+                pda_acc.clone(),
+                token_program.clone(),
+            ],
+            &[&[MINT_AUTHORITY_SEED, faucet_acc.key.as_ref(), &[nonce]]],
+        )?;
+
+        Ok(())
+    }
+
+    pub fn process_drip_lamports(
+        accounts: &[AccountInfo],
+        lamports: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let sol_vault_acc = next_account_info(account_info_iter)?;
+        check_writable(sol_vault_acc)?;
+
+        let recipient_acc = next_account_info(account_info_iter)?;
+        check_writable(recipient_acc)?;
+
+        let system_program_acc = next_account_info(account_info_iter)?;
+        check_system_program(system_program_acc)?;
+
+        let faucet_acc = next_account_info(account_info_iter)?;
+        check_faucet_account(program_id, faucet_acc)?;
+        let faucet = Faucet::unpack_versioned(&faucet_acc.data.borrow())?;
+
+        let claim_record_accs = if faucet.cooldown.is_some() {
+            Some((
+                next_account_info(account_info_iter)?,
+                next_account_info(account_info_iter)?,
+                next_account_info(account_info_iter)?,
+                next_account_info(account_info_iter)?,
+            ))
+        } else {
+            None
+        };
+
+        let admin_acc = next_account_info(account_info_iter);
+
+        let is_admin = match (faucet.admin, admin_acc) {
+            (COption::Some(admin_pubkey), Ok(acc)) => acc.is_signer && admin_pubkey == *acc.key,
+            _ => false,
+        };
+
+        if faucet.paused && !(is_admin && faucet.admin_mints_while_paused) {
+            return Err(FaucetError::FaucetPaused.into());
+        }
+
+        if !is_admin {
+            if lamports > faucet.lamport_amount {
+                return Err(FaucetError::RequestingTooManyLamports.into());
+            }
+
+            if let Some((claim_record_acc, payer_acc, clock_acc, rent_acc)) = claim_record_accs {
+                let clock = Clock::from_account_info(clock_acc)?;
+                let rent = Rent::from_account_info(rent_acc)?;
+
+                let mut claim_record = Self::load_claim_record(
+                    program_id,
+                    &[
+                        LAMPORT_CLAIM_RECORD_SEED,
+                        faucet_acc.key.as_ref(),
+                        recipient_acc.key.as_ref(),
+                    ],
+                    claim_record_acc,
+                    payer_acc,
+                    system_program_acc,
+                    &rent,
+                )?;
+
+                if claim_record.is_cooling_down(&faucet.cooldown, clock.slot, clock.unix_timestamp)
+                {
+                    return Err(FaucetError::CooldownNotElapsed.into());
+                }
+
+                claim_record.is_initialized = true;
+                claim_record.total_claimed = claim_record
+                    .total_claimed
+                    .checked_add(lamports)
+                    .ok_or(FaucetError::Overflow)?;
+                claim_record.last_claim_slot = clock.slot;
+                claim_record.last_claim_timestamp = clock.unix_timestamp;
+                ClaimRecord::pack(claim_record, &mut claim_record_acc.data.borrow_mut())?;
+            }
+        }
+
+        Self::transfer_from_sol_vault(
+            program_id,
+            faucet_acc.key,
+            sol_vault_acc,
+            recipient_acc,
+            system_program_acc,
+            lamports,
+        )
+    }
+
+    pub fn process_fund_sol_vault(
+        accounts: &[AccountInfo],
+        lamports: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let funder_acc = next_account_info(account_info_iter)?;
+        check_signer(funder_acc)?;
+        check_writable(funder_acc)?;
+
+        let sol_vault_acc = next_account_info(account_info_iter)?;
+        check_writable(sol_vault_acc)?;
+
+        let system_program_acc = next_account_info(account_info_iter)?;
+        check_system_program(system_program_acc)?;
+
+        let faucet_acc = next_account_info(account_info_iter)?;
+        check_faucet_account(program_id, faucet_acc)?;
+
+        let (sol_vault, _nonce) = find_sol_vault_address(program_id, faucet_acc.key);
+        if sol_vault != *sol_vault_acc.key {
+            return Err(FaucetError::IncorrectSolVaultAccount.into());
+        }
+
+        msg!("Calling the system program to fund the SOL vault");
+        invoke(
+            &system_instruction::transfer(funder_acc.key, sol_vault_acc.key, lamports),
+            &[
+                funder_acc.clone(),
+                sol_vault_acc.clone(),
+                system_program_acc.clone(),
+            ],
+        )
+    }
+
+    pub fn process_withdraw_lamports(
+        accounts: &[AccountInfo],
+        lamports: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+
+        let admin_acc = next_account_info(account_info_iter)?;
+        check_signer(admin_acc)?;
+
+        let faucet_acc = next_account_info(account_info_iter)?;
+        check_faucet_account(program_id, faucet_acc)?;
+        let faucet = Faucet::unpack_versioned(&faucet_acc.data.borrow())?;
+
+        if faucet.admin != COption::Some(*admin_acc.key) {
+            return Err(FaucetError::NonAdminWithdrawalAttempt.into());
+        }
+
+        let sol_vault_acc = next_account_info(account_info_iter)?;
+
+        let dest_acc = next_account_info(account_info_iter)?;
+        check_writable(dest_acc)?;
+
+        let system_program_acc = next_account_info(account_info_iter)?;
+
+        Self::transfer_from_sol_vault(
+            program_id,
+            faucet_acc.key,
+            sol_vault_acc,
+            dest_acc,
+            system_program_acc,
+            lamports,
+        )
+    }
+
+    /// Mints tokens to the destination, or transfers them from the vault if the faucet has one,
+    /// signing for the faucet's PDA with its seeds
+    fn send_tokens<'a>(
+        pda_acc: &AccountInfo<'a>,
+        mint_acc: &AccountInfo<'a>,
+        vault_acc: Option<&AccountInfo<'a>>,
+        token_dest_acc: &AccountInfo<'a>,
+        token_program: &AccountInfo<'a>,
+        signer_seeds: &[&[u8]],
+        amount: u64,
+    ) -> ProgramResult {
+        if let Some(vault_acc) = vault_acc {
+            let decimals = unpack_mint(&mint_acc.data.borrow())?.decimals;
+            let ix = token::transfer_checked(
+                token_program.key,
+                vault_acc.key,
+                mint_acc.key,
+                token_dest_acc.key,
+                pda_acc.key,
+                amount,
+                decimals,
+            );
+
+            msg!("Calling the token program to transfer tokens from the vault");
+            return invoke_signed(
+                &ix,
+                &[
+                    vault_acc.clone(),
+                    mint_acc.clone(),
+                    token_dest_acc.clone(),
+                    pda_acc.clone(),
+                    token_program.clone(),
+                ],
+                &[signer_seeds],
+            );
+        }
+
+        let ix = token::mint_to(
+            token_program.key,
+            mint_acc.key,
+            token_dest_acc.key,
+            pda_acc.key,
+            amount,
+        );
+
+        msg!("Calling the token program to mint tokens");
+        invoke_signed(
+            &ix,
+            &[
+                mint_acc.clone(),
+                token_dest_acc.clone(),
+                pda_acc.clone(),
+                token_program.clone(),
+            ],
+            &[signer_seeds],
+        )
+    }
+
+    /// Transfers lamports out of the SOL vault of the faucet, signing for it with its seeds
+    fn transfer_from_sol_vault<'a>(
+        program_id: &Pubkey,
+        faucet: &Pubkey,
+        sol_vault_acc: &AccountInfo<'a>,
+        dest_acc: &AccountInfo<'a>,
+        system_program_acc: &AccountInfo<'a>,
+        lamports: u64,
+    ) -> ProgramResult {
+        let (sol_vault, nonce) = find_sol_vault_address(program_id, faucet);
+        if sol_vault != *sol_vault_acc.key {
+            return Err(FaucetError::IncorrectSolVaultAccount.into());
+        }
+        check_writable(sol_vault_acc)?;
+        check_system_program(system_program_acc)?;
+
+        msg!("Calling the system program to transfer lamports from the SOL vault");
+        invoke_signed(
+            &system_instruction::transfer(sol_vault_acc.key, dest_acc.key, lamports),
+            &[
+                sol_vault_acc.clone(),
+                dest_acc.clone(),
+                system_program_acc.clone(),
+            ],
+            &[&[SOL_VAULT_SEED, faucet.as_ref(), &[nonce]]],
+        )
+    }
+
+    /// Returns the claim record at the PDA of the seeds, creating its account if it doesn't exist yet
+    fn load_claim_record<'a>(
+        program_id: &Pubkey,
+        seeds: &[&[u8]],
+        claim_record_acc: &AccountInfo<'a>,
+        payer_acc: &AccountInfo<'a>,
+        system_program_acc: &AccountInfo<'a>,
+        rent: &Rent,
+    ) -> Result<ClaimRecord, ProgramError> {
+        let (claim_record_pda, nonce) = Pubkey::find_program_address(seeds, program_id);
+        check_claim_record(program_id, &claim_record_pda, claim_record_acc)?;
+        check_writable(claim_record_acc)?;
+
+        if claim_record_acc.data_is_empty() {
+            check_signer(payer_acc)?;
+            check_writable(payer_acc)?;
+            check_system_program(system_program_acc)?;
+
+            let nonce = [nonce];
+            let mut signer_seeds = seeds.to_vec();
+            signer_seeds.push(&nonce);
+            Self::create_program_account(
+                program_id,
+                ClaimRecord::LEN,
+                rent,
+                payer_acc,
+                claim_record_acc,
+                system_program_acc,
+                &[&signer_seeds],
+            )?;
+        }
+
+        ClaimRecord::unpack_unchecked(&claim_record_acc.data.borrow())
+    }
+
+    /// Creates a rent exempt account owned by the faucet program, either at a PDA signed for
+    /// with its seeds or at an address whose keypair signed the transaction.
+    /// Tolerates lamports that were sent to the address before its creation.
+    fn create_program_account<'a>(
+        program_id: &Pubkey,
+        space: usize,
+        rent: &Rent,
+        payer_acc: &AccountInfo<'a>,
+        new_acc: &AccountInfo<'a>,
+        system_program_acc: &AccountInfo<'a>,
+        signer_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        let required_lamports = rent
+            .minimum_balance(space)
+            .saturating_sub(new_acc.lamports());
+
+        if required_lamports > 0 {
+            invoke(
+                &system_instruction::transfer(payer_acc.key, new_acc.key, required_lamports),
+                &[
+                    payer_acc.clone(),
+                    new_acc.clone(),
+                    system_program_acc.clone(),
+                ],
+            )?;
+        }
+
+        invoke_signed(
+            &system_instruction::allocate(new_acc.key, space as u64),
+            &[new_acc.clone(), system_program_acc.clone()],
+            signer_seeds,
+        )?;
+
+        invoke_signed(
+            &system_instruction::assign(new_acc.key, program_id),
+            &[new_acc.clone(), system_program_acc.clone()],
+            signer_seeds,
+        )
+    }
 }
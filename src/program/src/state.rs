@@ -1,12 +1,18 @@
+use solana_program::clock::{Slot, UnixTimestamp};
 use solana_program::program_error::ProgramError;
 use solana_program::program_option::COption;
 use solana_program::program_pack::{IsInitialized, Pack, Sealed};
 use solana_program::pubkey::Pubkey;
 
 use arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs};
+use std::convert::TryFrom;
 
 use crate::error::FaucetError;
 
+/// The layout version of faucets, stored in place of the `is_initialized` byte
+/// of the first layout so that older faucets read as version 1
+pub const FAUCET_VERSION: u8 = 2;
+
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct Faucet {
@@ -14,6 +20,108 @@ pub struct Faucet {
     pub admin: COption<Pubkey>,
     pub mint: Pubkey,
     pub amount: u64,
+    /// how long a recipient has to wait between two claims
+    pub cooldown: Option<Cooldown>,
+    /// how many tokens a recipient may receive over the lifetime of the faucet
+    pub recipient_limit: Option<u64>,
+    /// how many tokens the faucet may mint over its lifetime
+    pub max_total_minted: Option<u64>,
+    pub total_minted: u64,
+    /// whether mints of the admin count against `max_total_minted`
+    pub cap_admin_mints: bool,
+    /// the admin proposed by the current admin, becomes admin once it accepts
+    pub pending_admin: COption<Pubkey>,
+    /// whether minting is currently suspended
+    pub paused: bool,
+    /// whether the admin may still mint while the faucet is paused
+    pub admin_mints_while_paused: bool,
+    /// the token account owned by the faucet's PDA that tokens are transferred from,
+    /// faucets without a vault mint their tokens instead
+    pub vault: COption<Pubkey>,
+    /// all other accounts may only drip this many lamports from the SOL vault per ix
+    pub lamport_amount: u64,
+    /// how many lamports from the SOL vault accompany every token claim
+    pub lamports_per_claim: Option<u64>,
+    /// how many mints the faucet has done
+    pub mint_count: u64,
+    /// the slot of the last mint, only known if the Clock sysvar was passed along
+    pub last_mint_slot: Slot,
+    /// how many recipients have claim records at the faucet
+    pub unique_recipients: u64,
+}
+
+/// The layout of faucets before they had any limits besides the amount,
+/// faucets of this layout can't store any of the newer fields, not even the stats of a mint,
+/// so they have to be moved into the current layout with `MigrateFaucet` before they can mint again
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct FaucetV1 {
+    pub is_initialized: bool,
+    pub admin: COption<Pubkey>,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+/// The limits of a faucet that are set by its creator and may be changed by its admin
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct FaucetConfig {
+    /// all other accounts may only mint this amount per ix
+    pub amount: u64,
+    /// all other accounts have to wait this long between two mints
+    pub cooldown: Option<Cooldown>,
+    /// all other accounts may only receive this amount in total
+    pub recipient_limit: Option<u64>,
+    /// the faucet may only mint this amount in total
+    pub max_total_minted: Option<u64>,
+    /// whether the admin is bound by `max_total_minted` as well
+    pub cap_admin_mints: bool,
+    /// whether the admin may still mint while the faucet is paused
+    pub admin_mints_while_paused: bool,
+    /// all other accounts may only drip this many lamports per ix
+    pub lamport_amount: u64,
+    /// every token claim also drips this many lamports to the recipient's wallet,
+    /// only allowed together with a cooldown or a recipient limit
+    pub lamports_per_claim: Option<u64>,
+}
+
+impl FaucetConfig {
+    /// Whether claims of non-admins have to be tracked in claim records
+    pub fn tracks_claims(&self) -> bool {
+        self.cooldown.is_some() || self.recipient_limit.is_some()
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Cooldown {
+    Slots(u64),
+    Seconds(u64),
+}
+
+/// Tracks the claims of one recipient at one faucet,
+/// lives at the PDA derived from the faucet and the recipient's wallet
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ClaimRecord {
+    pub is_initialized: bool,
+    pub last_claim_slot: Slot,
+    pub last_claim_timestamp: UnixTimestamp,
+    pub total_claimed: u64,
+}
+
+/// How many faucets a basket may hold at most
+pub const MAX_BASKET_FAUCETS: usize = 10;
+
+/// A set of faucets whose tokens are claimed together with `MintBasket`,
+/// every faucet keeps its own mint, limits and admin
+#[repr(C)]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Basket {
+    pub is_initialized: bool,
+    /// Created the basket, the admin of every faucet of the basket that has one
+    pub creator: Pubkey,
+    pub faucets: Vec<Pubkey>,
 }
 
 impl Sealed for Faucet {}
@@ -24,13 +132,220 @@ impl IsInitialized for Faucet {
     }
 }
 
+impl Faucet {
+    /// Unpacks a faucet of any layout, faucets of older layouts get the defaults of the newer fields.
+    /// The layout is told by the version byte, older faucets may be stored in accounts
+    /// that have room for the current layout.
+    pub fn unpack_versioned(src: &[u8]) -> Result<Self, ProgramError> {
+        if Self::is_current_layout(src) {
+            return Self::unpack(src);
+        }
+        let src = src
+            .get(..FaucetV1::LEN)
+            .ok_or(FaucetError::IncorrectInitializationData)?;
+        Ok(FaucetV1::unpack(src)?.into())
+    }
+
+    /// Whether the data holds a faucet of the current layout
+    pub fn is_current_layout(src: &[u8]) -> bool {
+        src.first() == Some(&FAUCET_VERSION)
+    }
+
+    /// Packs the faucet in the layout of the account it is stored in.
+    /// Any field a faucet of an older layout can't store, its stats included,
+    /// requires migrating the faucet first, so nothing is dropped silently.
+    pub fn pack_versioned(faucet: Self, dst: &mut [u8]) -> Result<(), ProgramError> {
+        if Self::is_current_layout(dst) {
+            return Self::pack(faucet, dst);
+        }
+        let faucet_v1 = FaucetV1 {
+            is_initialized: faucet.is_initialized,
+            admin: faucet.admin,
+            mint: faucet.mint,
+            amount: faucet.amount,
+        };
+        if Faucet::from(faucet_v1) != faucet {
+            return Err(FaucetError::FaucetNotMigrated.into());
+        }
+        let dst = dst
+            .get_mut(..FaucetV1::LEN)
+            .ok_or(FaucetError::IncorrectInitializationData)?;
+        FaucetV1::pack(faucet_v1, dst)
+    }
+
+    pub fn set_config(&mut self, config: &FaucetConfig) {
+        self.amount = config.amount;
+        self.cooldown = config.cooldown;
+        self.recipient_limit = config.recipient_limit;
+        self.max_total_minted = config.max_total_minted;
+        self.cap_admin_mints = config.cap_admin_mints;
+        self.admin_mints_while_paused = config.admin_mints_while_paused;
+        self.lamport_amount = config.lamport_amount;
+        self.lamports_per_claim = config.lamports_per_claim;
+    }
+
+    pub fn config(&self) -> FaucetConfig {
+        FaucetConfig {
+            amount: self.amount,
+            cooldown: self.cooldown,
+            recipient_limit: self.recipient_limit,
+            max_total_minted: self.max_total_minted,
+            cap_admin_mints: self.cap_admin_mints,
+            admin_mints_while_paused: self.admin_mints_while_paused,
+            lamport_amount: self.lamport_amount,
+            lamports_per_claim: self.lamports_per_claim,
+        }
+    }
+
+    /// Whether claims of non-admins have to be tracked in claim records
+    pub fn tracks_claims(&self) -> bool {
+        self.config().tracks_claims()
+    }
+
+    /// Whether the faucet hands out lamports of its SOL vault
+    pub fn drips_lamports(&self) -> bool {
+        self.lamport_amount > 0 || self.lamports_per_claim.is_some()
+    }
+}
+
 impl Pack for Faucet {
-    const LEN: usize = 77;
+    // the last 92 bytes are reserved for the fields of later layouts,
+    // programs can't resize accounts so later faucets can only be migrated in place
+    const LEN: usize = 320;
     fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
         if src.len() < Faucet::LEN {
             return Err(FaucetError::IncorrectInitializationData.into());
         }
         let src = array_ref![src, 0, Faucet::LEN];
+        let (
+            version,
+            admin,
+            amount,
+            mint,
+            cooldown,
+            recipient_limit,
+            max_total_minted,
+            total_minted,
+            cap_admin_mints,
+            pending_admin,
+            paused,
+            admin_mints_while_paused,
+            vault,
+            lamport_amount,
+            lamports_per_claim,
+            mint_count,
+            last_mint_slot,
+            unique_recipients,
+            _reserved,
+        ) = array_refs![src, 1, 36, 8, 32, 9, 9, 9, 8, 1, 36, 1, 1, 36, 8, 9, 8, 8, 8, 92];
+
+        let is_initialized = match version {
+            [0] => false,
+            [FAUCET_VERSION] => true,
+            _ => return Err(FaucetError::IncorrectInitializationData.into()),
+        };
+        Ok(Self {
+            is_initialized,
+            admin: unpack_coption_key(admin)?,
+            amount: u64::from_le_bytes(*amount),
+            mint: Pubkey::new_from_array(*mint),
+            cooldown: unpack_cooldown(cooldown)?,
+            recipient_limit: unpack_option_u64(recipient_limit)?,
+            max_total_minted: unpack_option_u64(max_total_minted)?,
+            total_minted: u64::from_le_bytes(*total_minted),
+            cap_admin_mints: unpack_bool(cap_admin_mints)?,
+            pending_admin: unpack_coption_key(pending_admin)?,
+            paused: unpack_bool(paused)?,
+            admin_mints_while_paused: unpack_bool(admin_mints_while_paused)?,
+            vault: unpack_coption_key(vault)?,
+            lamport_amount: u64::from_le_bytes(*lamport_amount),
+            lamports_per_claim: unpack_option_u64(lamports_per_claim)?,
+            mint_count: u64::from_le_bytes(*mint_count),
+            last_mint_slot: u64::from_le_bytes(*last_mint_slot),
+            unique_recipients: u64::from_le_bytes(*unique_recipients),
+        })
+    }
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let dst = array_mut_ref![dst, 0, Faucet::LEN];
+        let (
+            version_dst,
+            admin_dst,
+            amount_dst,
+            mint_dst,
+            cooldown_dst,
+            recipient_limit_dst,
+            max_total_minted_dst,
+            total_minted_dst,
+            cap_admin_mints_dst,
+            pending_admin_dst,
+            paused_dst,
+            admin_mints_while_paused_dst,
+            vault_dst,
+            lamport_amount_dst,
+            lamports_per_claim_dst,
+            mint_count_dst,
+            last_mint_slot_dst,
+            unique_recipients_dst,
+            _reserved_dst,
+        ) = mut_array_refs!(dst, 1, 36, 8, 32, 9, 9, 9, 8, 1, 36, 1, 1, 36, 8, 9, 8, 8, 8, 92);
+        let &Faucet {
+            is_initialized,
+            ref admin,
+            ref mint,
+            amount,
+            ref cooldown,
+            ref recipient_limit,
+            ref max_total_minted,
+            total_minted,
+            cap_admin_mints,
+            ref pending_admin,
+            paused,
+            admin_mints_while_paused,
+            ref vault,
+            lamport_amount,
+            ref lamports_per_claim,
+            mint_count,
+            last_mint_slot,
+            unique_recipients,
+        } = self;
+
+        pack_coption_key(admin, admin_dst);
+        version_dst[0] = if is_initialized { FAUCET_VERSION } else { 0 };
+        *amount_dst = amount.to_le_bytes();
+        *mint_dst = mint.to_bytes();
+        pack_cooldown(cooldown, cooldown_dst);
+        pack_option_u64(recipient_limit, recipient_limit_dst);
+        pack_option_u64(max_total_minted, max_total_minted_dst);
+        *total_minted_dst = total_minted.to_le_bytes();
+        cap_admin_mints_dst[0] = cap_admin_mints as u8;
+        pack_coption_key(pending_admin, pending_admin_dst);
+        paused_dst[0] = paused as u8;
+        admin_mints_while_paused_dst[0] = admin_mints_while_paused as u8;
+        pack_coption_key(vault, vault_dst);
+        *lamport_amount_dst = lamport_amount.to_le_bytes();
+        pack_option_u64(lamports_per_claim, lamports_per_claim_dst);
+        *mint_count_dst = mint_count.to_le_bytes();
+        *last_mint_slot_dst = last_mint_slot.to_le_bytes();
+        *unique_recipients_dst = unique_recipients.to_le_bytes();
+    }
+}
+
+impl Sealed for FaucetV1 {}
+
+impl IsInitialized for FaucetV1 {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl Pack for FaucetV1 {
+    const LEN: usize = 77;
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        if src.len() < FaucetV1::LEN {
+            return Err(FaucetError::IncorrectInitializationData.into());
+        }
+        let src = array_ref![src, 0, FaucetV1::LEN];
         let (is_initialized, admin, amount, mint) = array_refs![src, 1, 36, 8, 32];
 
         let is_initialized = match is_initialized {
@@ -47,10 +362,10 @@ impl Pack for Faucet {
     }
 
     fn pack_into_slice(&self, dst: &mut [u8]) {
-        let dst = array_mut_ref![dst, 0, Faucet::LEN];
+        let dst = array_mut_ref![dst, 0, FaucetV1::LEN];
         let (is_initialized_dst, admin_dst, amount_dst, mint_dst) =
             mut_array_refs!(dst, 1, 36, 8, 32);
-        let &Faucet {
+        let &FaucetV1 {
             is_initialized,
             ref admin,
             ref mint,
@@ -64,6 +379,146 @@ impl Pack for Faucet {
     }
 }
 
+impl From<FaucetV1> for Faucet {
+    fn from(faucet: FaucetV1) -> Self {
+        Self {
+            is_initialized: faucet.is_initialized,
+            admin: faucet.admin,
+            mint: faucet.mint,
+            amount: faucet.amount,
+            ..Faucet::default()
+        }
+    }
+}
+
+impl ClaimRecord {
+    /// Whether the recipient still has to wait before claiming again
+    pub fn is_cooling_down(
+        &self,
+        cooldown: &Option<Cooldown>,
+        slot: Slot,
+        unix_timestamp: UnixTimestamp,
+    ) -> bool {
+        if !self.is_initialized {
+            return false;
+        }
+        match cooldown {
+            Some(Cooldown::Slots(slots)) => self.last_claim_slot.saturating_add(*slots) > slot,
+            Some(Cooldown::Seconds(seconds)) => {
+                // cooldowns beyond i64::MAX seconds never end
+                let seconds = i64::try_from(*seconds).unwrap_or(i64::MAX);
+                self.last_claim_timestamp.saturating_add(seconds) > unix_timestamp
+            }
+            None => false,
+        }
+    }
+}
+
+impl Sealed for ClaimRecord {}
+
+impl IsInitialized for ClaimRecord {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl Pack for ClaimRecord {
+    const LEN: usize = 25;
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        if src.len() < ClaimRecord::LEN {
+            return Err(FaucetError::IncorrectInitializationData.into());
+        }
+        let src = array_ref![src, 0, ClaimRecord::LEN];
+        let (is_initialized, last_claim_slot, last_claim_timestamp, total_claimed) =
+            array_refs![src, 1, 8, 8, 8];
+
+        let is_initialized = match is_initialized {
+            [0] => false,
+            [1] => true,
+            _ => return Err(FaucetError::IncorrectInitializationData.into()),
+        };
+        Ok(Self {
+            is_initialized,
+            last_claim_slot: u64::from_le_bytes(*last_claim_slot),
+            last_claim_timestamp: i64::from_le_bytes(*last_claim_timestamp),
+            total_claimed: u64::from_le_bytes(*total_claimed),
+        })
+    }
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let dst = array_mut_ref![dst, 0, ClaimRecord::LEN];
+        let (is_initialized_dst, last_claim_slot_dst, last_claim_timestamp_dst, total_claimed_dst) =
+            mut_array_refs!(dst, 1, 8, 8, 8);
+        let &ClaimRecord {
+            is_initialized,
+            last_claim_slot,
+            last_claim_timestamp,
+            total_claimed,
+        } = self;
+
+        is_initialized_dst[0] = is_initialized as u8;
+        *last_claim_slot_dst = last_claim_slot.to_le_bytes();
+        *last_claim_timestamp_dst = last_claim_timestamp.to_le_bytes();
+        *total_claimed_dst = total_claimed.to_le_bytes();
+    }
+}
+
+impl Sealed for Basket {}
+
+impl IsInitialized for Basket {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl Pack for Basket {
+    const LEN: usize = 354;
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        if src.len() < Basket::LEN {
+            return Err(FaucetError::IncorrectInitializationData.into());
+        }
+        let src = array_ref![src, 0, Basket::LEN];
+        let (is_initialized, creator, faucet_count, faucets) = array_refs![src, 1, 32, 1, 320];
+
+        let is_initialized = match is_initialized {
+            [0] => false,
+            [1] => true,
+            _ => return Err(FaucetError::IncorrectInitializationData.into()),
+        };
+        let faucet_count = faucet_count[0] as usize;
+        if faucet_count > MAX_BASKET_FAUCETS {
+            return Err(FaucetError::IncorrectInitializationData.into());
+        }
+        Ok(Self {
+            is_initialized,
+            creator: Pubkey::new_from_array(*creator),
+            faucets: faucets
+                .chunks(32)
+                .take(faucet_count)
+                .map(Pubkey::new)
+                .collect(),
+        })
+    }
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let dst = array_mut_ref![dst, 0, Basket::LEN];
+        let (is_initialized_dst, creator_dst, faucet_count_dst, faucets_dst) =
+            mut_array_refs![dst, 1, 32, 1, 320];
+        let Basket {
+            is_initialized,
+            ref creator,
+            ref faucets,
+        } = *self;
+
+        is_initialized_dst[0] = is_initialized as u8;
+        creator_dst.copy_from_slice(creator.as_ref());
+        faucet_count_dst[0] = faucets.len() as u8;
+        for (faucet_dst, faucet) in faucets_dst.chunks_mut(32).zip(faucets) {
+            faucet_dst.copy_from_slice(faucet.as_ref());
+        }
+    }
+}
+
 // Helpers
 fn pack_coption_key(src: &COption<Pubkey>, dst: &mut [u8; 36]) {
     let (tag, body) = mut_array_refs![dst, 4, 32];
@@ -85,3 +540,57 @@ fn unpack_coption_key(src: &[u8; 36]) -> Result<COption<Pubkey>, ProgramError> {
         _ => Err(ProgramError::InvalidAccountData),
     }
 }
+fn pack_cooldown(src: &Option<Cooldown>, dst: &mut [u8; 9]) {
+    let (tag, body) = mut_array_refs![dst, 1, 8];
+    match src {
+        None => {
+            *tag = [0];
+            *body = [0; 8];
+        }
+        Some(Cooldown::Slots(slots)) => {
+            *tag = [1];
+            *body = slots.to_le_bytes();
+        }
+        Some(Cooldown::Seconds(seconds)) => {
+            *tag = [2];
+            *body = seconds.to_le_bytes();
+        }
+    }
+}
+fn unpack_cooldown(src: &[u8; 9]) -> Result<Option<Cooldown>, ProgramError> {
+    let (tag, body) = array_refs![src, 1, 8];
+    match *tag {
+        [0] => Ok(None),
+        [1] => Ok(Some(Cooldown::Slots(u64::from_le_bytes(*body)))),
+        [2] => Ok(Some(Cooldown::Seconds(u64::from_le_bytes(*body)))),
+        _ => Err(ProgramError::InvalidAccountData),
+    }
+}
+fn pack_option_u64(src: &Option<u64>, dst: &mut [u8; 9]) {
+    let (tag, body) = mut_array_refs![dst, 1, 8];
+    match src {
+        Some(value) => {
+            *tag = [1];
+            *body = value.to_le_bytes();
+        }
+        None => {
+            *tag = [0];
+            *body = [0; 8];
+        }
+    }
+}
+fn unpack_option_u64(src: &[u8; 9]) -> Result<Option<u64>, ProgramError> {
+    let (tag, body) = array_refs![src, 1, 8];
+    match *tag {
+        [0] => Ok(None),
+        [1] => Ok(Some(u64::from_le_bytes(*body))),
+        _ => Err(ProgramError::InvalidAccountData),
+    }
+}
+fn unpack_bool(src: &[u8; 1]) -> Result<bool, ProgramError> {
+    match src {
+        [0] => Ok(false),
+        [1] => Ok(true),
+        _ => Err(ProgramError::InvalidAccountData),
+    }
+}
@@ -0,0 +1,401 @@
+//! Support for mints of both the SPL Token and the Token-2022 program
+
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    system_program, sysvar,
+};
+use spl_token::{
+    instruction::{AuthorityType, TokenInstruction},
+    state::{Account, Mint},
+};
+
+/// The Token-2022 program, a superset of the SPL Token program that supports extensions
+pub mod spl_token_2022 {
+    solana_program::declare_id!("TokenzQdBNbLqP5VEhdkAS6EHFLC1WENmXpRJVuDTFr");
+}
+
+/// Account type of Token-2022 mints with extensions, stored right after the base account layout
+const ACCOUNT_TYPE_MINT: u8 = 1;
+/// Account type of Token-2022 token accounts with extensions
+const ACCOUNT_TYPE_ACCOUNT: u8 = 2;
+
+/// Length of the type and the length of an extension, which precede its value
+const EXTENSION_HEADER_LEN: usize = 4;
+
+/// Whether the id is one of the token programs the faucet can mint with
+pub fn is_token_program(program_id: &Pubkey) -> bool {
+    *program_id == spl_token::id() || spl_token_2022::check_id(program_id)
+}
+
+/// Unpacks the base state of a mint, ignoring any Token-2022 extensions
+///
+/// Token-2022 pads the base mint to the length of a token account,
+/// followed by the account type and the extensions
+pub fn unpack_mint(data: &[u8]) -> Result<Mint, ProgramError> {
+    if data.len() == Mint::LEN {
+        return Mint::unpack(data);
+    }
+    if data.len() <= Account::LEN
+        || data[Account::LEN] != ACCOUNT_TYPE_MINT
+        || data[Mint::LEN..Account::LEN].iter().any(|&byte| byte != 0)
+    {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    check_extensions(&data[Account::LEN + 1..])?;
+    Mint::unpack(&data[..Mint::LEN])
+}
+
+/// Checks that the type-length-value entries of the extensions don't run past the end of the data.
+/// An extension of type 0 is uninitialized and ends them, like in Token-2022.
+fn check_extensions(extensions: &[u8]) -> Result<(), ProgramError> {
+    let mut rest = extensions;
+    while rest.len() >= 2 {
+        let extension_type = u16::from_le_bytes([rest[0], rest[1]]);
+        if extension_type == 0 {
+            return Ok(());
+        }
+        let length = rest
+            .get(2..EXTENSION_HEADER_LEN)
+            .map(|length| u16::from_le_bytes([length[0], length[1]]) as usize)
+            .ok_or(ProgramError::InvalidAccountData)?;
+        rest = rest
+            .get(EXTENSION_HEADER_LEN + length..)
+            .ok_or(ProgramError::InvalidAccountData)?;
+    }
+    Ok(())
+}
+
+/// Derives the associated token account of the wallet for a mint of the given token program
+pub fn find_associated_token_address(
+    wallet: &Pubkey,
+    mint: &Pubkey,
+    token_program_id: &Pubkey,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &[wallet.as_ref(), token_program_id.as_ref(), mint.as_ref()],
+        &spl_associated_token_account::id(),
+    )
+    .0
+}
+
+/// Creates an instruction that creates the associated token account of the wallet.
+/// Unlike `spl_associated_token_account::create_associated_token_account`,
+/// it passes the token program owning the mint instead of always passing SPL Token.
+pub fn create_associated_token_account(
+    payer: &Pubkey,
+    wallet: &Pubkey,
+    mint: &Pubkey,
+    token_program_id: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: spl_associated_token_account::id(),
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(
+                find_associated_token_address(wallet, mint, token_program_id),
+                false,
+            ),
+            AccountMeta::new_readonly(*wallet, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(*token_program_id, false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+        ],
+        data: vec![],
+    }
+}
+
+/// Creates a `MintTo` instruction of the given token program.
+///
+/// The builders of `spl_token::instruction` reject any program id other than SPL Token's in newer
+/// versions of the crate, the instructions the faucet invokes are shared by both programs, so they are built here.
+pub fn mint_to(
+    token_program_id: &Pubkey,
+    mint: &Pubkey,
+    destination: &Pubkey,
+    authority: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    Instruction {
+        program_id: *token_program_id,
+        accounts: vec![
+            AccountMeta::new(*mint, false),
+            AccountMeta::new(*destination, false),
+            AccountMeta::new_readonly(*authority, true),
+        ],
+        data: TokenInstruction::MintTo { amount }.pack(),
+    }
+}
+
+/// Creates a `TransferChecked` instruction of the given token program
+pub fn transfer_checked(
+    token_program_id: &Pubkey,
+    source: &Pubkey,
+    mint: &Pubkey,
+    destination: &Pubkey,
+    authority: &Pubkey,
+    amount: u64,
+    decimals: u8,
+) -> Instruction {
+    Instruction {
+        program_id: *token_program_id,
+        accounts: vec![
+            AccountMeta::new(*source, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(*destination, false),
+            AccountMeta::new_readonly(*authority, true),
+        ],
+        data: TokenInstruction::TransferChecked { amount, decimals }.pack(),
+    }
+}
+
+/// Creates a `SetAuthority` instruction of the given token program
+pub fn set_authority(
+    token_program_id: &Pubkey,
+    owned: &Pubkey,
+    new_authority: Option<&Pubkey>,
+    authority_type: AuthorityType,
+    authority: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: *token_program_id,
+        accounts: vec![
+            AccountMeta::new(*owned, false),
+            AccountMeta::new_readonly(*authority, true),
+        ],
+        data: TokenInstruction::SetAuthority {
+            authority_type,
+            new_authority: new_authority.cloned().into(),
+        }
+        .pack(),
+    }
+}
+
+/// Creates a `CloseAccount` instruction of the given token program
+pub fn close_account(
+    token_program_id: &Pubkey,
+    account: &Pubkey,
+    destination: &Pubkey,
+    authority: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: *token_program_id,
+        accounts: vec![
+            AccountMeta::new(*account, false),
+            AccountMeta::new(*destination, false),
+            AccountMeta::new_readonly(*authority, true),
+        ],
+        data: TokenInstruction::CloseAccount.pack(),
+    }
+}
+
+/// Unpacks the base state of a token account, ignoring any Token-2022 extensions
+pub fn unpack_account(data: &[u8]) -> Result<Account, ProgramError> {
+    if data.len() == Account::LEN {
+        return Account::unpack(data);
+    }
+    if data.len() <= Account::LEN || data[Account::LEN] != ACCOUNT_TYPE_ACCOUNT {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    check_extensions(&data[Account::LEN + 1..])?;
+    Account::unpack(&data[..Account::LEN])
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use solana_program::program_option::COption;
+    use spl_token::state::AccountState;
+
+    /// A base mint padded to the length of a token account, followed by the account type and the extensions
+    fn mint_data(account_type: u8, extensions: &[u8]) -> Vec<u8> {
+        let mut data = vec![0; Account::LEN];
+        Mint {
+            mint_authority: COption::Some(Pubkey::new_from_array([7; 32])),
+            decimals: 6,
+            is_initialized: true,
+            ..Mint::default()
+        }
+        .pack_into_slice(&mut data[..Mint::LEN]);
+        data.push(account_type);
+        data.extend_from_slice(extensions);
+        data
+    }
+
+    /// A base token account followed by the account type and the extensions
+    fn account_data(account_type: u8, extensions: &[u8]) -> Vec<u8> {
+        let mut data = vec![0; Account::LEN];
+        Account {
+            mint: Pubkey::new_from_array([1; 32]),
+            owner: Pubkey::new_from_array([2; 32]),
+            amount: 100,
+            state: AccountState::Initialized,
+            ..Account::default()
+        }
+        .pack_into_slice(&mut data);
+        data.push(account_type);
+        data.extend_from_slice(extensions);
+        data
+    }
+
+    #[test]
+    fn test_unpack_mint_with_extensions() {
+        // transfer fee config, 108 bytes long
+        let mut extensions = vec![1, 0, 108, 0];
+        extensions.extend_from_slice(&[0; 108]);
+        let mint = unpack_mint(&mint_data(ACCOUNT_TYPE_MINT, &extensions)).unwrap();
+        assert_eq!(6, mint.decimals);
+
+        // uninitialized extension space at the end
+        extensions.extend_from_slice(&[0; 10]);
+        assert!(unpack_mint(&mint_data(ACCOUNT_TYPE_MINT, &extensions)).is_ok());
+    }
+
+    #[test]
+    fn test_unpack_mint_malformed_extensions() {
+        // wrong account type
+        assert_eq!(
+            Err(ProgramError::InvalidAccountData),
+            unpack_mint(&mint_data(ACCOUNT_TYPE_ACCOUNT, &[]))
+        );
+
+        // data in the padding between the base mint and the account type
+        let mut data = mint_data(ACCOUNT_TYPE_MINT, &[]);
+        data[Mint::LEN] = 1;
+        assert_eq!(Err(ProgramError::InvalidAccountData), unpack_mint(&data));
+
+        // extension value shorter than its length
+        let mut extensions = vec![1, 0, 108, 0];
+        extensions.extend_from_slice(&[0; 100]);
+        assert_eq!(
+            Err(ProgramError::InvalidAccountData),
+            unpack_mint(&mint_data(ACCOUNT_TYPE_MINT, &extensions))
+        );
+
+        // extension type without a length
+        assert_eq!(
+            Err(ProgramError::InvalidAccountData),
+            unpack_mint(&mint_data(ACCOUNT_TYPE_MINT, &[1, 0, 108]))
+        );
+
+        // neither the base layout nor long enough for an account type
+        assert_eq!(
+            Err(ProgramError::InvalidAccountData),
+            unpack_mint(&[0; Account::LEN])
+        );
+    }
+
+    #[test]
+    fn test_unpack_account_with_extensions() {
+        // immutable owner, no value
+        let account = unpack_account(&account_data(ACCOUNT_TYPE_ACCOUNT, &[7, 0, 0, 0])).unwrap();
+        assert_eq!(100, account.amount);
+        assert_eq!(Pubkey::new_from_array([2; 32]), account.owner);
+    }
+
+    #[test]
+    fn test_unpack_account_malformed_extensions() {
+        // wrong account type
+        assert_eq!(
+            Err(ProgramError::InvalidAccountData),
+            unpack_account(&account_data(ACCOUNT_TYPE_MINT, &[7, 0, 0, 0]))
+        );
+
+        // extension value running past the end of the data
+        assert_eq!(
+            Err(ProgramError::InvalidAccountData),
+            unpack_account(&account_data(ACCOUNT_TYPE_ACCOUNT, &[7, 0, 8, 0, 1, 2]))
+        );
+
+        // truncated base layout
+        assert_eq!(
+            Err(ProgramError::InvalidAccountData),
+            unpack_account(&[0; Account::LEN - 1])
+        );
+    }
+
+    #[test]
+    fn test_token_instructions_of_token_2022() {
+        let [mint, source, destination, authority] = [
+            Pubkey::new_from_array([1; 32]),
+            Pubkey::new_from_array([2; 32]),
+            Pubkey::new_from_array([3; 32]),
+            Pubkey::new_from_array([4; 32]),
+        ];
+
+        // the same instructions as SPL Token's, only invoking Token-2022
+        let ix = mint_to(&spl_token_2022::id(), &mint, &destination, &authority, 5);
+        let spl_ix = spl_token::instruction::mint_to(
+            &spl_token::id(),
+            &mint,
+            &destination,
+            &authority,
+            &[],
+            5,
+        )
+        .unwrap();
+        assert_eq!(spl_token_2022::id(), ix.program_id);
+        assert_eq!(spl_ix.accounts, ix.accounts);
+        assert_eq!(spl_ix.data, ix.data);
+
+        let ix = transfer_checked(
+            &spl_token_2022::id(),
+            &source,
+            &mint,
+            &destination,
+            &authority,
+            5,
+            6,
+        );
+        let spl_ix = spl_token::instruction::transfer_checked(
+            &spl_token::id(),
+            &source,
+            &mint,
+            &destination,
+            &authority,
+            &[],
+            5,
+            6,
+        )
+        .unwrap();
+        assert_eq!(spl_token_2022::id(), ix.program_id);
+        assert_eq!(spl_ix.accounts, ix.accounts);
+        assert_eq!(spl_ix.data, ix.data);
+
+        let ix = set_authority(
+            &spl_token_2022::id(),
+            &mint,
+            Some(&destination),
+            AuthorityType::MintTokens,
+            &authority,
+        );
+        let spl_ix = spl_token::instruction::set_authority(
+            &spl_token::id(),
+            &mint,
+            Some(&destination),
+            AuthorityType::MintTokens,
+            &authority,
+            &[],
+        )
+        .unwrap();
+        assert_eq!(spl_token_2022::id(), ix.program_id);
+        assert_eq!(spl_ix.accounts, ix.accounts);
+        assert_eq!(spl_ix.data, ix.data);
+
+        let ix = close_account(&spl_token_2022::id(), &source, &destination, &authority);
+        let spl_ix = spl_token::instruction::close_account(
+            &spl_token::id(),
+            &source,
+            &destination,
+            &authority,
+            &[],
+        )
+        .unwrap();
+        assert_eq!(spl_token_2022::id(), ix.program_id);
+        assert_eq!(spl_ix.accounts, ix.accounts);
+        assert_eq!(spl_ix.data, ix.data);
+    }
+}
@@ -0,0 +1,117 @@
+//! Account validation shared by all instruction handlers
+
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_option::COption, pubkey::Pubkey,
+    system_program,
+};
+
+use crate::error::FaucetError;
+use crate::state::FaucetConfig;
+use crate::token::is_token_program;
+
+/// Checks that the faucet account is owned by the faucet program
+pub fn check_faucet_account(program_id: &Pubkey, faucet_acc: &AccountInfo) -> ProgramResult {
+    if faucet_acc.owner != program_id {
+        return Err(FaucetError::IncorrectFaucetAccountOwner.into());
+    }
+    Ok(())
+}
+
+/// Checks that the mint account is owned by one of the token programs
+pub fn check_mint_account(mint_acc: &AccountInfo) -> ProgramResult {
+    if !is_token_program(mint_acc.owner) {
+        return Err(FaucetError::IncorrectMintAccountOwner.into());
+    }
+    Ok(())
+}
+
+/// Checks that the token account is owned by the token program of the mint
+pub fn check_token_account(token_acc: &AccountInfo, token_program_id: &Pubkey) -> ProgramResult {
+    if token_acc.owner != token_program_id {
+        return Err(FaucetError::IncorrectTokenAccountOwner.into());
+    }
+    Ok(())
+}
+
+/// Checks that the account is the token program of the mint
+pub fn check_token_program(
+    token_program: &AccountInfo,
+    token_program_id: &Pubkey,
+) -> ProgramResult {
+    if token_program.key != token_program_id {
+        return Err(FaucetError::IncorrectTokenProgramId.into());
+    }
+    Ok(())
+}
+
+/// Checks that the account is the expected mint authority PDA
+pub fn check_mint_authority(pda: &Pubkey, pda_acc: &AccountInfo) -> ProgramResult {
+    if pda != pda_acc.key {
+        return Err(FaucetError::IncorrectMintAuthorityAccount.into());
+    }
+    Ok(())
+}
+
+/// Checks that the account is the expected claim record PDA
+/// and, once it has been created, that it is owned by the faucet program
+pub fn check_claim_record(
+    program_id: &Pubkey,
+    pda: &Pubkey,
+    claim_record_acc: &AccountInfo,
+) -> ProgramResult {
+    if pda != claim_record_acc.key
+        || (!claim_record_acc.data_is_empty() && claim_record_acc.owner != program_id)
+    {
+        return Err(FaucetError::IncorrectClaimRecordAccount.into());
+    }
+    Ok(())
+}
+
+/// Checks that the account is the vault of the faucet
+pub fn check_vault(vault: &COption<Pubkey>, vault_acc: &AccountInfo) -> ProgramResult {
+    if *vault != COption::Some(*vault_acc.key) {
+        return Err(FaucetError::IncorrectVaultAccount.into());
+    }
+    Ok(())
+}
+
+/// Checks that the account is the System program
+pub fn check_system_program(system_program: &AccountInfo) -> ProgramResult {
+    if !system_program::check_id(system_program.key) {
+        return Err(FaucetError::IncorrectSystemProgramId.into());
+    }
+    Ok(())
+}
+
+/// Checks that the account is the Associated Token Account program
+pub fn check_associated_token_program(ata_program: &AccountInfo) -> ProgramResult {
+    if *ata_program.key != spl_associated_token_account::id() {
+        return Err(FaucetError::IncorrectAssociatedTokenProgramId.into());
+    }
+    Ok(())
+}
+
+/// Checks that a config only drips lamports with claims that are tracked,
+/// without a cooldown or a recipient limit every claim could drip them again
+pub fn check_config(config: &FaucetConfig) -> ProgramResult {
+    if config.lamports_per_claim.is_some() && !config.tracks_claims() {
+        return Err(FaucetError::UntrackedLamportsPerClaim.into());
+    }
+    Ok(())
+}
+
+/// Checks that the account was passed as writable
+pub fn check_writable(acc: &AccountInfo) -> ProgramResult {
+    if !acc.is_writable {
+        return Err(FaucetError::AccountNotWritable.into());
+    }
+    Ok(())
+}
+
+/// Checks that the account signed the transaction
+pub fn check_signer(acc: &AccountInfo) -> ProgramResult {
+    if !acc.is_signer {
+        return Err(FaucetError::AccountNotSigner.into());
+    }
+    Ok(())
+}
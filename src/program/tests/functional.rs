@@ -2,7 +2,9 @@
 #![cfg(feature = "test-bpf")]
 
 use solana_program::{
-    hash::Hash, instruction::*, program_option::COption, program_pack::Pack, pubkey::Pubkey, sysvar,
+    account_info::AccountInfo, entrypoint::ProgramResult, hash::Hash, instruction::*,
+    program_error::ProgramError, program_option::COption, program_pack::Pack, pubkey::Pubkey,
+    rent::Rent, sysvar,
 };
 use solana_program_test::*;
 use solana_sdk::{
@@ -10,6 +12,10 @@ use solana_sdk::{
     signature::{Keypair, Signer},
     transaction::{Transaction, TransactionError},
 };
+use spl_token::{
+    error::TokenError,
+    instruction::{AuthorityType, TokenInstruction},
+};
 use spl_token_faucet::*;
 
 // PUBKEY VALID TOKEN MINT
@@ -26,6 +32,13 @@ const VALID_TOKEN_ACCOUNT_PUBKEY: Pubkey = Pubkey::new_from_array([
     10, 61, 93, 10, 117, 76, 173, 43, 112, 121, 185, 139,
 ]);
 
+// OWNER OF THE VALID TOKEN ACCOUNT
+// 5vBrLAPeMjJr9UfssGbjUaBmWtrXTg2vZuMN6L4c8HE6
+const TOKEN_ACCOUNT_OWNER_PUBKEY: Pubkey = Pubkey::new_from_array([
+    73, 14, 204, 159, 193, 240, 34, 192, 30, 52, 43, 238, 160, 228, 178, 91, 60, 113, 107, 81, 192,
+    56, 61, 203, 88, 79, 142, 171, 43, 78, 222, 159,
+]);
+
 // SECOND MINT
 // 8YPF8izyYFqbcu3x9q8BpQ4dcU9PgNFGaVkLrqdzKJsL
 const SECOND_MINT_PUBKEY: Pubkey = Pubkey::new_from_array([
@@ -47,12 +60,197 @@ fn program_test() -> ProgramTest {
         processor!(spl_token::processor::Processor::process),
     );
 
+    // Add Associated Token Account program
+    pc.add_program(
+        "spl_associated_token_account",
+        spl_associated_token_account::id(),
+        processor!(spl_associated_token_account::processor::process_instruction),
+    );
+
     // Dial down the BPF compute budget to detect if the program gets bloated in the future
     pc.set_bpf_compute_max_units(50_000);
 
     pc
 }
 
+fn mint_account(mint_authority: &Pubkey) -> Account {
+    let mut data = vec![0; spl_token::state::Mint::LEN];
+    spl_token::state::Mint::pack(
+        spl_token::state::Mint {
+            mint_authority: COption::Some(*mint_authority),
+            supply: 0,
+            decimals: 0,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        },
+        &mut data,
+    )
+    .unwrap();
+    Account {
+        lamports: 1461600,
+        data,
+        owner: spl_token::id(),
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+/// A Token-2022 mint with a (zeroed) transfer fee extension
+fn token_2022_mint_account(mint_authority: &Pubkey) -> Account {
+    let mut account = mint_account(mint_authority);
+    account.data.resize(spl_token::state::Account::LEN, 0);
+    // account type mint
+    account.data.push(1);
+    // extension type transfer fee config, 108 bytes long
+    account.data.extend_from_slice(&[1, 0, 108, 0]);
+    account.data.extend_from_slice(&[0; 108]);
+    account.owner = token::spl_token_2022::id();
+    account
+}
+
+fn token_account(mint: &Pubkey, owner: &Pubkey, amount: u64) -> Account {
+    let mut account = Account::new(
+        Rent::default().minimum_balance(spl_token::state::Account::LEN),
+        spl_token::state::Account::LEN,
+        &spl_token::id(),
+    );
+    spl_token::state::Account {
+        mint: *mint,
+        owner: *owner,
+        amount,
+        state: spl_token::state::AccountState::Initialized,
+        ..spl_token::state::Account::default()
+    }
+    .pack_into_slice(&mut account.data);
+    account
+}
+
+/// A Token-2022 token account with an (empty) immutable owner extension
+fn token_2022_token_account(mint: &Pubkey, owner: &Pubkey, amount: u64) -> Account {
+    let mut account = token_account(mint, owner, amount);
+    // account type account
+    account.data.push(2);
+    // extension type immutable owner, without a value
+    account.data.extend_from_slice(&[7, 0, 0, 0]);
+    account.owner = token::spl_token_2022::id();
+    account
+}
+
+/// Stands in for Token-2022 with the instructions the faucet sends it.
+/// Unlike the SPL Token program, it accepts accounts with extensions, only their base state is changed.
+fn token_2022_stand_in(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    input: &[u8],
+) -> ProgramResult {
+    fn check_authority(authority: &COption<Pubkey>, authority_acc: &AccountInfo) -> ProgramResult {
+        if *authority != COption::Some(*authority_acc.key) || !authority_acc.is_signer {
+            return Err(TokenError::OwnerMismatch.into());
+        }
+        Ok(())
+    }
+    let mint_len = spl_token::state::Mint::LEN;
+    let account_len = spl_token::state::Account::LEN;
+
+    match TokenInstruction::unpack(input)? {
+        TokenInstruction::MintTo { amount } => {
+            let (mint_acc, dest_acc, authority_acc) = (&accounts[0], &accounts[1], &accounts[2]);
+            let mut mint = token::unpack_mint(&mint_acc.data.borrow())?;
+            check_authority(&mint.mint_authority, authority_acc)?;
+            let mut dest = token::unpack_account(&dest_acc.data.borrow())?;
+            mint.supply += amount;
+            dest.amount += amount;
+            mint.pack_into_slice(&mut mint_acc.data.borrow_mut()[..mint_len]);
+            dest.pack_into_slice(&mut dest_acc.data.borrow_mut()[..account_len]);
+        }
+        TokenInstruction::SetAuthority {
+            authority_type: AuthorityType::MintTokens,
+            new_authority,
+        } => {
+            let (mint_acc, authority_acc) = (&accounts[0], &accounts[1]);
+            let mut mint = token::unpack_mint(&mint_acc.data.borrow())?;
+            check_authority(&mint.mint_authority, authority_acc)?;
+            mint.mint_authority = new_authority;
+            mint.pack_into_slice(&mut mint_acc.data.borrow_mut()[..mint_len]);
+        }
+        TokenInstruction::TransferChecked { amount, decimals } => {
+            let (source_acc, mint_acc, dest_acc, authority_acc) =
+                (&accounts[0], &accounts[1], &accounts[2], &accounts[3]);
+            if token::unpack_mint(&mint_acc.data.borrow())?.decimals != decimals {
+                return Err(TokenError::MintDecimalsMismatch.into());
+            }
+            let mut source = token::unpack_account(&source_acc.data.borrow())?;
+            check_authority(&COption::Some(source.owner), authority_acc)?;
+            let mut dest = token::unpack_account(&dest_acc.data.borrow())?;
+            source.amount = source
+                .amount
+                .checked_sub(amount)
+                .ok_or(TokenError::InsufficientFunds)?;
+            dest.amount += amount;
+            source.pack_into_slice(&mut source_acc.data.borrow_mut()[..account_len]);
+            dest.pack_into_slice(&mut dest_acc.data.borrow_mut()[..account_len]);
+        }
+        TokenInstruction::CloseAccount => {
+            let (account_acc, dest_acc, authority_acc) = (&accounts[0], &accounts[1], &accounts[2]);
+            let account = token::unpack_account(&account_acc.data.borrow())?;
+            check_authority(&COption::Some(account.owner), authority_acc)?;
+            if account.amount != 0 {
+                return Err(TokenError::NonNativeHasBalance.into());
+            }
+            **dest_acc.lamports.borrow_mut() += account_acc.lamports();
+            **account_acc.lamports.borrow_mut() = 0;
+            account_acc
+                .data
+                .borrow_mut()
+                .iter_mut()
+                .for_each(|byte| *byte = 0);
+        }
+        _ => return Err(ProgramError::InvalidInstructionData),
+    }
+    Ok(())
+}
+
+/// The program test with the Token-2022 stand-in
+fn token_2022_program_test() -> ProgramTest {
+    let mut pc = program_test();
+    pc.add_program(
+        "token_2022_stand_in",
+        token::spl_token_2022::id(),
+        processor!(token_2022_stand_in),
+    );
+    pc
+}
+
+fn faucet_lamports() -> u64 {
+    Rent::default().minimum_balance(state::Faucet::LEN)
+}
+
+fn faucet_account(faucet: state::Faucet) -> Account {
+    let mut account = Account::new(faucet_lamports(), state::Faucet::LEN, &id());
+    state::Faucet::pack(faucet, &mut account.data).unwrap();
+    account
+}
+
+fn initialized_faucet(admin: Option<Pubkey>, mint: Pubkey, amount: u64) -> state::Faucet {
+    state::Faucet {
+        is_initialized: true,
+        admin: admin.into(),
+        mint,
+        amount,
+        ..state::Faucet::default()
+    }
+}
+
+fn claim_record_account(claim_record: state::ClaimRecord) -> Account {
+    let mut account = Account::new(
+        Rent::default().minimum_balance(state::ClaimRecord::LEN),
+        state::ClaimRecord::LEN,
+        &id(),
+    );
+    state::ClaimRecord::pack(claim_record, &mut account.data).unwrap();
+    account
+}
+
 async fn create_faucet(
     pc: ProgramTest,
     admin: Option<Pubkey>,
@@ -61,14 +259,14 @@ async fn create_faucet(
     let faucet_pubkey = Pubkey::new_unique();
     pc.add_account(
         faucet_pubkey,
-        Account::new(1426800, spl_token_faucet::state::Faucet::LEN, &id()),
-    );
-    pc.add_account_with_file_data(
-        VALID_MINT_PUBKEY,
-        1461600,
-        spl_token::id(),
-        "valid-token-mint-data.bin",
+        Account::new(
+            faucet_lamports(),
+            spl_token_faucet::state::Faucet::LEN,
+            &id(),
+        ),
     );
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_pubkey);
+    pc.add_account(VALID_MINT_PUBKEY, mint_account(&pda));
     let (mut banks_client, payer, recent_blockhash) = pc.start().await;
     let rent = banks_client.get_rent().await.unwrap();
     let expected_token_account_balance = rent.minimum_balance(spl_token_faucet::state::Faucet::LEN);
@@ -78,22 +276,14 @@ async fn create_faucet(
         expected_token_account_balance,
     );
 
-    let mut accounts = vec![
-        AccountMeta::new_readonly(VALID_MINT_PUBKEY, false),
-        AccountMeta::new(faucet_pubkey, false),
-        AccountMeta::new_readonly(sysvar::rent::id(), false),
-    ];
-
-    if let Some(admin_pubkey) = admin {
-        accounts.push(AccountMeta::new_readonly(admin_pubkey, false));
-    }
-
     let mut init_faucet_tx = Transaction::new_with_payer(
-        &[Instruction {
-            program_id: id(),
-            accounts,
-            data: vec![0, 10, 0, 0, 0, 0, 0, 0, 0],
-        }],
+        &[instruction::init_faucet(
+            &id(),
+            &VALID_MINT_PUBKEY,
+            &faucet_pubkey,
+            admin.as_ref(),
+            10,
+        )],
         Some(&payer.pubkey()),
     );
 
@@ -113,14 +303,14 @@ async fn test_happy_flow_init_faucet_no_admin() {
     let faucet_pubkey = Pubkey::new_unique();
     pc.add_account(
         faucet_pubkey,
-        Account::new(1426800, spl_token_faucet::state::Faucet::LEN, &id()),
-    );
-    pc.add_account_with_file_data(
-        token_mint_address,
-        1461600,
-        spl_token::id(),
-        "valid-token-mint-data.bin",
+        Account::new(
+            faucet_lamports(),
+            spl_token_faucet::state::Faucet::LEN,
+            &id(),
+        ),
     );
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_pubkey);
+    pc.add_account(token_mint_address, mint_account(&pda));
     let (mut banks_client, payer, recent_blockhash) = pc.start().await;
     let rent = banks_client.get_rent().await.unwrap();
     let expected_token_account_balance = rent.minimum_balance(spl_token_faucet::state::Faucet::LEN);
@@ -173,14 +363,14 @@ async fn test_happy_flow_init_faucet_with_admin() {
     let admin_pubkey = Pubkey::new_unique();
     pc.add_account(
         faucet_pubkey,
-        Account::new(1426800, spl_token_faucet::state::Faucet::LEN, &id()),
-    );
-    pc.add_account_with_file_data(
-        token_mint_address,
-        1461600,
-        spl_token::id(),
-        "valid-token-mint-data.bin",
+        Account::new(
+            faucet_lamports(),
+            spl_token_faucet::state::Faucet::LEN,
+            &id(),
+        ),
     );
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_pubkey);
+    pc.add_account(token_mint_address, mint_account(&pda));
     let (mut banks_client, payer, recent_blockhash) = pc.start().await;
     let rent = banks_client.get_rent().await.unwrap();
     let expected_token_account_balance = rent.minimum_balance(spl_token_faucet::state::Faucet::LEN);
@@ -235,7 +425,11 @@ async fn test_mint_authority_not_owned_by_pda() {
     let faucet_pubkey = Pubkey::new_unique();
     pc.add_account(
         faucet_pubkey,
-        Account::new(1426800, spl_token_faucet::state::Faucet::LEN, &id()),
+        Account::new(
+            faucet_lamports(),
+            spl_token_faucet::state::Faucet::LEN,
+            &id(),
+        ),
     );
     pc.add_account_with_file_data(
         token_mint_address,
@@ -287,14 +481,14 @@ async fn test_faucet_already_initialized() {
     let faucet_pubkey = Pubkey::new_unique();
     pc.add_account(
         faucet_pubkey,
-        Account::new(1426800, spl_token_faucet::state::Faucet::LEN, &id()),
-    );
-    pc.add_account_with_file_data(
-        token_mint_address,
-        1461600,
-        spl_token::id(),
-        "valid-token-mint-data.bin",
+        Account::new(
+            faucet_lamports(),
+            spl_token_faucet::state::Faucet::LEN,
+            &id(),
+        ),
     );
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_pubkey);
+    pc.add_account(token_mint_address, mint_account(&pda));
     let (mut banks_client, payer, recent_blockhash) = pc.start().await;
     let rent = banks_client.get_rent().await.unwrap();
     let expected_token_account_balance = rent.minimum_balance(spl_token_faucet::state::Faucet::LEN);
@@ -356,20 +550,16 @@ async fn test_happy_flow_mint_tokens() {
     );
     let (mut banks_client, payer, recent_blockhash, faucet_pubkey) = create_faucet(pc, None).await;
 
-    let (pda, _nonce) = Pubkey::find_program_address(&[b"faucet"], &id());
-
     let mut mint_tokens_tx = Transaction::new_with_payer(
-        &[Instruction {
-            program_id: id(),
-            accounts: vec![
-                AccountMeta::new_readonly(pda, false),
-                AccountMeta::new(token_mint_address, false),
-                AccountMeta::new(token_account_address, false),
-                AccountMeta::new_readonly(spl_token::id(), false),
-                AccountMeta::new_readonly(faucet_pubkey, false),
-            ],
-            data: vec![1, 5, 0, 0, 0, 0, 0, 0, 0],
-        }],
+        &[instruction::mint_tokens(
+            &id(),
+            &spl_token::id(),
+            &token_mint_address,
+            &token_account_address,
+            &faucet_pubkey,
+            None,
+            5,
+        )],
         Some(&payer.pubkey()),
     );
 
@@ -408,7 +598,7 @@ async fn test_mint_too_many_tokens() {
     );
     let (mut banks_client, payer, recent_blockhash, faucet_pubkey) = create_faucet(pc, None).await;
 
-    let (pda, _nonce) = Pubkey::find_program_address(&[b"faucet"], &id());
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_pubkey);
 
     let mut mint_tokens_tx = Transaction::new_with_payer(
         &[Instruction {
@@ -418,7 +608,7 @@ async fn test_mint_too_many_tokens() {
                 AccountMeta::new(token_mint_address, false),
                 AccountMeta::new(token_account_address, false),
                 AccountMeta::new_readonly(spl_token::id(), false),
-                AccountMeta::new_readonly(faucet_pubkey, false),
+                AccountMeta::new(faucet_pubkey, false),
             ],
             data: vec![1, 11, 0, 0, 0, 0, 0, 0, 0],
         }],
@@ -468,7 +658,7 @@ async fn test_mint_happy_flow_admin_may_mint_too_many_tokens() {
     let (mut banks_client, payer, recent_blockhash, faucet_pubkey) =
         create_faucet(pc, Some(admin_keypair.pubkey())).await;
 
-    let (pda, _nonce) = Pubkey::find_program_address(&[b"faucet"], &id());
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_pubkey);
 
     let mut mint_tokens_tx = Transaction::new_with_payer(
         &[Instruction {
@@ -478,7 +668,7 @@ async fn test_mint_happy_flow_admin_may_mint_too_many_tokens() {
                 AccountMeta::new(token_mint_address, false),
                 AccountMeta::new(token_account_address, false),
                 AccountMeta::new_readonly(spl_token::id(), false),
-                AccountMeta::new_readonly(faucet_pubkey, false),
+                AccountMeta::new(faucet_pubkey, false),
                 AccountMeta::new_readonly(admin_keypair.pubkey(), true),
             ],
             data: vec![1, 11, 0, 0, 0, 0, 0, 0, 0],
@@ -524,7 +714,7 @@ async fn test_mint_tokens_admin_included_but_didnt_sign() {
     let (mut banks_client, payer, recent_blockhash, faucet_pubkey) =
         create_faucet(pc, Some(admin_keypair.pubkey())).await;
 
-    let (pda, _nonce) = Pubkey::find_program_address(&[b"faucet"], &id());
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_pubkey);
 
     let mut mint_tokens_tx = Transaction::new_with_payer(
         &[Instruction {
@@ -534,7 +724,7 @@ async fn test_mint_tokens_admin_included_but_didnt_sign() {
                 AccountMeta::new(token_mint_address, false),
                 AccountMeta::new(token_account_address, false),
                 AccountMeta::new_readonly(spl_token::id(), false),
-                AccountMeta::new_readonly(faucet_pubkey, false),
+                AccountMeta::new(faucet_pubkey, false),
                 AccountMeta::new_readonly(admin_keypair.pubkey(), false),
             ],
             data: vec![1, 11, 0, 0, 0, 0, 0, 0, 0],
@@ -586,7 +776,7 @@ async fn test_mint_tokens_impostor_admin_included_and_signed() {
     let (mut banks_client, payer, recent_blockhash, faucet_pubkey) =
         create_faucet(pc, Some(admin_keypair.pubkey())).await;
 
-    let (pda, _nonce) = Pubkey::find_program_address(&[b"faucet"], &id());
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_pubkey);
 
     let impostor_admin_keypair = Keypair::new();
     let mut mint_tokens_tx = Transaction::new_with_payer(
@@ -597,7 +787,7 @@ async fn test_mint_tokens_impostor_admin_included_and_signed() {
                 AccountMeta::new(token_mint_address, false),
                 AccountMeta::new(token_account_address, false),
                 AccountMeta::new_readonly(spl_token::id(), false),
-                AccountMeta::new_readonly(faucet_pubkey, false),
+                AccountMeta::new(faucet_pubkey, false),
                 AccountMeta::new_readonly(impostor_admin_keypair.pubkey(), true),
             ],
             data: vec![1, 11, 0, 0, 0, 0, 0, 0, 0],
@@ -654,7 +844,7 @@ async fn test_mint_tokens_invalid_mint() {
     let (mut banks_client, payer, recent_blockhash, faucet_pubkey) =
         create_faucet(pc, Some(admin_keypair.pubkey())).await;
 
-    let (pda, _nonce) = Pubkey::find_program_address(&[b"faucet"], &id());
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_pubkey);
 
     let mut mint_tokens_tx = Transaction::new_with_payer(
         &[Instruction {
@@ -664,7 +854,7 @@ async fn test_mint_tokens_invalid_mint() {
                 AccountMeta::new(SECOND_MINT_PUBKEY, false),
                 AccountMeta::new(token_account_address, false),
                 AccountMeta::new_readonly(spl_token::id(), false),
-                AccountMeta::new_readonly(faucet_pubkey, false),
+                AccountMeta::new(faucet_pubkey, false),
                 AccountMeta::new_readonly(admin_keypair.pubkey(), true),
             ],
             data: vec![1, 11, 0, 0, 0, 0, 0, 0, 0],
@@ -704,20 +894,15 @@ async fn test_happy_flow_close_faucet() {
     let admin_keypair = Keypair::new();
     let (mut banks_client, payer, recent_blockhash, faucet_pubkey) =
         create_faucet(program_test(), Some(admin_keypair.pubkey())).await;
-    let (pda, _nonce) = Pubkey::find_program_address(&[b"faucet"], &id());
     let mut close_faucet_tx = Transaction::new_with_payer(
-        &[Instruction {
-            program_id: id(),
-            accounts: vec![
-                AccountMeta::new_readonly(admin_keypair.pubkey(), true),
-                AccountMeta::new(faucet_pubkey, false),
-                AccountMeta::new(payer.pubkey(), false),
-                AccountMeta::new(VALID_MINT_PUBKEY, false),
-                AccountMeta::new_readonly(spl_token::id(), false),
-                AccountMeta::new_readonly(pda, false),
-            ],
-            data: vec![2, 11, 0, 0, 0, 0, 0, 0, 0],
-        }],
+        &[instruction::close_faucet(
+            &id(),
+            &spl_token::id(),
+            &admin_keypair.pubkey(),
+            &faucet_pubkey,
+            &payer.pubkey(),
+            &VALID_MINT_PUBKEY,
+        )],
         Some(&payer.pubkey()),
     );
 
@@ -729,13 +914,157 @@ async fn test_happy_flow_close_faucet() {
         .unwrap();
 }
 
+#[tokio::test]
+async fn test_happy_flow_close_faucet_sweeps_sol_vault() {
+    // GIVEN
+    let admin_keypair = Keypair::new();
+    let rent_destination = Pubkey::new_unique();
+    let faucet_pubkey = Pubkey::new_unique();
+    let mut pc = program_test();
+    pc.add_account(
+        faucet_pubkey,
+        faucet_account(initialized_faucet(
+            Some(admin_keypair.pubkey()),
+            VALID_MINT_PUBKEY,
+            10,
+        )),
+    );
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_pubkey);
+    pc.add_account(VALID_MINT_PUBKEY, mint_account(&pda));
+    let (sol_vault, _nonce) = find_sol_vault_address(&id(), &faucet_pubkey);
+    pc.add_account(
+        sol_vault,
+        Account::new(5_000_000, 0, &solana_program::system_program::id()),
+    );
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+    let faucet_lamports = banks_client
+        .get_account(faucet_pubkey)
+        .await
+        .unwrap()
+        .unwrap()
+        .lamports;
+
+    let mut close_faucet_ix = instruction::close_faucet(
+        &id(),
+        &spl_token::id(),
+        &admin_keypair.pubkey(),
+        &faucet_pubkey,
+        &rent_destination,
+        &VALID_MINT_PUBKEY,
+    );
+    close_faucet_ix
+        .accounts
+        .extend(instruction::close_faucet_sol_vault_accounts(
+            &id(),
+            &faucet_pubkey,
+        ));
+    let mut close_faucet_tx =
+        Transaction::new_with_payer(&[close_faucet_ix], Some(&payer.pubkey()));
+    close_faucet_tx.sign(&[&payer, &admin_keypair], recent_blockhash);
+
+    // WHEN
+    banks_client
+        .process_transaction(close_faucet_tx)
+        .await
+        .unwrap();
+
+    // THEN
+    assert_eq!(None, banks_client.get_account(sol_vault).await.unwrap());
+    assert_eq!(
+        faucet_lamports + 5_000_000,
+        banks_client
+            .get_account(rent_destination)
+            .await
+            .unwrap()
+            .unwrap()
+            .lamports
+    );
+}
+
+#[tokio::test]
+async fn test_close_faucet_incorrect_sol_vault() {
+    // GIVEN
+    let admin_keypair = Keypair::new();
+    let (mut banks_client, payer, recent_blockhash, faucet_pubkey) =
+        create_faucet(program_test(), Some(admin_keypair.pubkey())).await;
+    let mut close_faucet_ix = instruction::close_faucet(
+        &id(),
+        &spl_token::id(),
+        &admin_keypair.pubkey(),
+        &faucet_pubkey,
+        &payer.pubkey(),
+        &VALID_MINT_PUBKEY,
+    );
+    close_faucet_ix.accounts.extend(vec![
+        AccountMeta::new(Pubkey::new_unique(), false),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+    ]);
+    let mut close_faucet_tx =
+        Transaction::new_with_payer(&[close_faucet_ix], Some(&payer.pubkey()));
+
+    close_faucet_tx.sign(&[&payer, &admin_keypair], recent_blockhash);
+
+    // WHEN THEN
+    let error = banks_client
+        .process_transaction(close_faucet_tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        TransactionError::InstructionError(0, InstructionError::Custom(0x1f)),
+        error
+    );
+}
+
+#[tokio::test]
+async fn test_close_faucet_dripping_lamports_without_sol_vault() {
+    // GIVEN
+    let admin_keypair = Keypair::new();
+    let faucet_pubkey = Pubkey::new_unique();
+    let mut pc = program_test();
+    pc.add_account(
+        faucet_pubkey,
+        faucet_account(state::Faucet {
+            lamports_per_claim: Some(1_000),
+            ..initialized_faucet(Some(admin_keypair.pubkey()), VALID_MINT_PUBKEY, 10)
+        }),
+    );
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_pubkey);
+    pc.add_account(VALID_MINT_PUBKEY, mint_account(&pda));
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut close_faucet_tx = Transaction::new_with_payer(
+        &[instruction::close_faucet(
+            &id(),
+            &spl_token::id(),
+            &admin_keypair.pubkey(),
+            &faucet_pubkey,
+            &payer.pubkey(),
+            &VALID_MINT_PUBKEY,
+        )],
+        Some(&payer.pubkey()),
+    );
+    close_faucet_tx.sign(&[&payer, &admin_keypair], recent_blockhash);
+
+    // WHEN THEN
+    let error = banks_client
+        .process_transaction(close_faucet_tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        TransactionError::InstructionError(0, InstructionError::NotEnoughAccountKeys),
+        error
+    );
+}
+
 #[tokio::test]
 async fn test_close_faucet_admin_didnt_sign() {
     // GIVEN
     let admin_keypair = Keypair::new();
     let (mut banks_client, payer, recent_blockhash, faucet_pubkey) =
         create_faucet(program_test(), Some(admin_keypair.pubkey())).await;
-    let (pda, _nonce) = Pubkey::find_program_address(&[b"faucet"], &id());
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_pubkey);
     let mut close_faucet_tx = Transaction::new_with_payer(
         &[Instruction {
             program_id: id(),
@@ -761,10 +1090,7 @@ async fn test_close_faucet_admin_didnt_sign() {
         .unwrap();
 
     assert_eq!(
-        TransactionError::InstructionError(
-            0,
-            solana_program::instruction::InstructionError::MissingRequiredSignature
-        ),
+        TransactionError::InstructionError(0, InstructionError::Custom(0x11)),
         error
     );
 }
@@ -775,7 +1101,7 @@ async fn test_close_faucet_admin_sign_not_closable() {
     let admin_keypair = Keypair::new();
     let (mut banks_client, payer, recent_blockhash, faucet_pubkey) =
         create_faucet(program_test(), None).await;
-    let (pda, _nonce) = Pubkey::find_program_address(&[b"faucet"], &id());
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_pubkey);
     let mut close_faucet_tx = Transaction::new_with_payer(
         &[Instruction {
             program_id: id(),
@@ -812,7 +1138,7 @@ async fn test_close_faucet_impostor_admin() {
     let admin_keypair = Keypair::new();
     let (mut banks_client, payer, recent_blockhash, faucet_pubkey) =
         create_faucet(program_test(), Some(admin_keypair.pubkey())).await;
-    let (pda, _nonce) = Pubkey::find_program_address(&[b"faucet"], &id());
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_pubkey);
 
     let impostor_admin_keypair = Keypair::new();
     let mut close_faucet_tx = Transaction::new_with_payer(
@@ -844,3 +1170,4400 @@ async fn test_close_faucet_impostor_admin() {
         error
     );
 }
+
+#[tokio::test]
+async fn test_init_faucet_legacy_mint_authority() {
+    // GIVEN
+    let token_mint_address = VALID_MINT_PUBKEY;
+    let mut pc = program_test();
+    let faucet_pubkey = Pubkey::new_unique();
+    pc.add_account(
+        faucet_pubkey,
+        Account::new(
+            faucet_lamports(),
+            spl_token_faucet::state::Faucet::LEN,
+            &id(),
+        ),
+    );
+    // mint authority is the legacy PDA shared by all faucets
+    pc.add_account_with_file_data(
+        token_mint_address,
+        1461600,
+        spl_token::id(),
+        "valid-token-mint-data.bin",
+    );
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut transaction = Transaction::new_with_payer(
+        &[Instruction {
+            program_id: id(),
+            accounts: vec![
+                AccountMeta::new_readonly(token_mint_address, false),
+                AccountMeta::new(faucet_pubkey, false),
+                AccountMeta::new_readonly(sysvar::rent::id(), false),
+            ],
+            data: vec![0, 1, 0, 0, 0, 0, 0, 0, 0],
+        }],
+        Some(&payer.pubkey()),
+    );
+
+    transaction.sign(&[&payer], recent_blockhash);
+
+    // WHEN THEN
+    let result = banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        TransactionError::InstructionError(0, InstructionError::Custom(0x09)),
+        result
+    );
+}
+
+#[tokio::test]
+async fn test_happy_flow_migrate_mint_authority() {
+    // GIVEN
+    let admin_keypair = Keypair::new();
+    let faucet_pubkey = Pubkey::new_unique();
+    let mut pc = program_test();
+    pc.add_account(
+        faucet_pubkey,
+        faucet_account(initialized_faucet(
+            Some(admin_keypair.pubkey()),
+            VALID_MINT_PUBKEY,
+            10,
+        )),
+    );
+    pc.add_account_with_file_data(
+        VALID_MINT_PUBKEY,
+        1461600,
+        spl_token::id(),
+        "valid-token-mint-data.bin",
+    );
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+    let mut migrate_tx = Transaction::new_with_payer(
+        &[instruction::migrate_mint_authority(
+            &id(),
+            &spl_token::id(),
+            &VALID_MINT_PUBKEY,
+            &faucet_pubkey,
+            Some(&admin_keypair.pubkey()),
+        )],
+        Some(&payer.pubkey()),
+    );
+
+    migrate_tx.sign(&[&payer, &admin_keypair], recent_blockhash);
+
+    // WHEN
+    banks_client.process_transaction(migrate_tx).await.unwrap();
+
+    // THEN
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_pubkey);
+    let mint = banks_client
+        .get_account(VALID_MINT_PUBKEY)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        COption::Some(pda),
+        spl_token::state::Mint::unpack_from_slice(&mint.data)
+            .unwrap()
+            .mint_authority
+    );
+}
+
+#[tokio::test]
+async fn test_migrate_mint_authority_impostor_admin() {
+    // GIVEN
+    let admin_keypair = Keypair::new();
+    let faucet_pubkey = Pubkey::new_unique();
+    let mut pc = program_test();
+    pc.add_account(
+        faucet_pubkey,
+        faucet_account(initialized_faucet(
+            Some(admin_keypair.pubkey()),
+            VALID_MINT_PUBKEY,
+            10,
+        )),
+    );
+    pc.add_account_with_file_data(
+        VALID_MINT_PUBKEY,
+        1461600,
+        spl_token::id(),
+        "valid-token-mint-data.bin",
+    );
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+    let impostor_admin_keypair = Keypair::new();
+    let mut migrate_tx = Transaction::new_with_payer(
+        &[instruction::migrate_mint_authority(
+            &id(),
+            &spl_token::id(),
+            &VALID_MINT_PUBKEY,
+            &faucet_pubkey,
+            Some(&impostor_admin_keypair.pubkey()),
+        )],
+        Some(&payer.pubkey()),
+    );
+
+    migrate_tx.sign(&[&payer, &impostor_admin_keypair], recent_blockhash);
+
+    // WHEN THEN
+    let error = banks_client
+        .process_transaction(migrate_tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        TransactionError::InstructionError(0, InstructionError::Custom(0x0a)),
+        error
+    );
+}
+
+#[tokio::test]
+async fn test_init_faucet_untracked_lamports_per_claim() {
+    // GIVEN
+    let faucet_pubkey = Pubkey::new_unique();
+    let mut pc = program_test();
+    pc.add_account(
+        faucet_pubkey,
+        Account::new(faucet_lamports(), state::Faucet::LEN, &id()),
+    );
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_pubkey);
+    pc.add_account(VALID_MINT_PUBKEY, mint_account(&pda));
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut init_faucet_tx = Transaction::new_with_payer(
+        &[Instruction {
+            program_id: id(),
+            accounts: vec![
+                AccountMeta::new_readonly(VALID_MINT_PUBKEY, false),
+                AccountMeta::new(faucet_pubkey, false),
+                AccountMeta::new_readonly(sysvar::rent::id(), false),
+            ],
+            data: instruction::FaucetInstruction::InitFaucet {
+                config: state::FaucetConfig {
+                    amount: 10,
+                    lamports_per_claim: Some(5_000_000),
+                    ..state::FaucetConfig::default()
+                },
+                label: None,
+            }
+            .pack(),
+        }],
+        Some(&payer.pubkey()),
+    );
+
+    init_faucet_tx.sign(&[&payer], recent_blockhash);
+
+    // WHEN THEN
+    let error = banks_client
+        .process_transaction(init_faucet_tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        TransactionError::InstructionError(0, InstructionError::Custom(0x2b)),
+        error
+    );
+}
+
+#[tokio::test]
+async fn test_init_faucet_mint_not_owned_by_token_program() {
+    // GIVEN
+    let token_mint_address = Pubkey::new_unique();
+    let mut pc = program_test();
+    let faucet_pubkey = Pubkey::new_unique();
+    pc.add_account(
+        faucet_pubkey,
+        Account::new(
+            faucet_lamports(),
+            spl_token_faucet::state::Faucet::LEN,
+            &id(),
+        ),
+    );
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_pubkey);
+    let mut forged_mint = mint_account(&pda);
+    forged_mint.owner = Pubkey::new_unique();
+    pc.add_account(token_mint_address, forged_mint);
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut transaction = Transaction::new_with_payer(
+        &[Instruction {
+            program_id: id(),
+            accounts: vec![
+                AccountMeta::new_readonly(token_mint_address, false),
+                AccountMeta::new(faucet_pubkey, false),
+                AccountMeta::new_readonly(sysvar::rent::id(), false),
+            ],
+            data: vec![0, 1, 0, 0, 0, 0, 0, 0, 0],
+        }],
+        Some(&payer.pubkey()),
+    );
+
+    transaction.sign(&[&payer], recent_blockhash);
+
+    // WHEN THEN
+    let result = banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        TransactionError::InstructionError(0, InstructionError::Custom(0x0c)),
+        result
+    );
+}
+
+#[tokio::test]
+async fn test_mint_tokens_faucet_not_owned_by_program() {
+    // GIVEN
+    let token_account_address = VALID_TOKEN_ACCOUNT_PUBKEY;
+    let mut pc = program_test();
+    pc.add_account_with_file_data(
+        token_account_address,
+        1000000000,
+        spl_token::id(),
+        "valid-token-account-data.bin",
+    );
+    let forged_faucet_pubkey = Pubkey::new_unique();
+    let mut forged_faucet = faucet_account(initialized_faucet(None, VALID_MINT_PUBKEY, u64::MAX));
+    forged_faucet.owner = Pubkey::new_unique();
+    pc.add_account(forged_faucet_pubkey, forged_faucet);
+    let (pda, _nonce) = find_mint_authority_address(&id(), &forged_faucet_pubkey);
+    pc.add_account(VALID_MINT_PUBKEY, mint_account(&pda));
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut mint_tokens_tx = Transaction::new_with_payer(
+        &[Instruction {
+            program_id: id(),
+            accounts: vec![
+                AccountMeta::new_readonly(pda, false),
+                AccountMeta::new(VALID_MINT_PUBKEY, false),
+                AccountMeta::new(token_account_address, false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+                AccountMeta::new(forged_faucet_pubkey, false),
+            ],
+            data: vec![1, 11, 0, 0, 0, 0, 0, 0, 0],
+        }],
+        Some(&payer.pubkey()),
+    );
+
+    mint_tokens_tx.sign(&[&payer], recent_blockhash);
+
+    // WHEN THEN
+    let error = banks_client
+        .process_transaction(mint_tokens_tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        TransactionError::InstructionError(0, InstructionError::Custom(0x0b)),
+        error
+    );
+}
+
+#[tokio::test]
+async fn test_mint_tokens_incorrect_token_program() {
+    // GIVEN
+    let token_account_address = VALID_TOKEN_ACCOUNT_PUBKEY;
+    let mut pc = program_test();
+    pc.add_account_with_file_data(
+        token_account_address,
+        1000000000,
+        spl_token::id(),
+        "valid-token-account-data.bin",
+    );
+    let (mut banks_client, payer, recent_blockhash, faucet_pubkey) = create_faucet(pc, None).await;
+
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_pubkey);
+
+    let mut mint_tokens_tx = Transaction::new_with_payer(
+        &[Instruction {
+            program_id: id(),
+            accounts: vec![
+                AccountMeta::new_readonly(pda, false),
+                AccountMeta::new(VALID_MINT_PUBKEY, false),
+                AccountMeta::new(token_account_address, false),
+                AccountMeta::new_readonly(Pubkey::new_unique(), false),
+                AccountMeta::new(faucet_pubkey, false),
+            ],
+            data: vec![1, 5, 0, 0, 0, 0, 0, 0, 0],
+        }],
+        Some(&payer.pubkey()),
+    );
+
+    mint_tokens_tx.sign(&[&payer], recent_blockhash);
+
+    // WHEN THEN
+    let error = banks_client
+        .process_transaction(mint_tokens_tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        TransactionError::InstructionError(0, InstructionError::Custom(0x0e)),
+        error
+    );
+}
+
+#[tokio::test]
+async fn test_mint_tokens_destination_not_writable() {
+    // GIVEN
+    let token_account_address = VALID_TOKEN_ACCOUNT_PUBKEY;
+    let mut pc = program_test();
+    pc.add_account_with_file_data(
+        token_account_address,
+        1000000000,
+        spl_token::id(),
+        "valid-token-account-data.bin",
+    );
+    let (mut banks_client, payer, recent_blockhash, faucet_pubkey) = create_faucet(pc, None).await;
+
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_pubkey);
+
+    let mut mint_tokens_tx = Transaction::new_with_payer(
+        &[Instruction {
+            program_id: id(),
+            accounts: vec![
+                AccountMeta::new_readonly(pda, false),
+                AccountMeta::new(VALID_MINT_PUBKEY, false),
+                AccountMeta::new_readonly(token_account_address, false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+                AccountMeta::new(faucet_pubkey, false),
+            ],
+            data: vec![1, 5, 0, 0, 0, 0, 0, 0, 0],
+        }],
+        Some(&payer.pubkey()),
+    );
+
+    mint_tokens_tx.sign(&[&payer], recent_blockhash);
+
+    // WHEN THEN
+    let error = banks_client
+        .process_transaction(mint_tokens_tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        TransactionError::InstructionError(0, InstructionError::Custom(0x10)),
+        error
+    );
+}
+
+#[tokio::test]
+async fn test_mint_tokens_cooldown_not_elapsed() {
+    // GIVEN
+    let token_account_address = VALID_TOKEN_ACCOUNT_PUBKEY;
+    let faucet_pubkey = Pubkey::new_unique();
+    let mut pc = program_test();
+    pc.add_account_with_file_data(
+        token_account_address,
+        1000000000,
+        spl_token::id(),
+        "valid-token-account-data.bin",
+    );
+    pc.add_account(
+        faucet_pubkey,
+        faucet_account(state::Faucet {
+            cooldown: Some(state::Cooldown::Slots(1_000_000)),
+            ..initialized_faucet(None, VALID_MINT_PUBKEY, 10)
+        }),
+    );
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_pubkey);
+    pc.add_account(VALID_MINT_PUBKEY, mint_account(&pda));
+    let (claim_record, _nonce) =
+        find_claim_record_address(&id(), &faucet_pubkey, &TOKEN_ACCOUNT_OWNER_PUBKEY);
+    pc.add_account(
+        claim_record,
+        claim_record_account(state::ClaimRecord {
+            is_initialized: true,
+            last_claim_slot: 0,
+            last_claim_timestamp: 0,
+            total_claimed: 0,
+        }),
+    );
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut mint_tokens_tx = Transaction::new_with_payer(
+        &[Instruction {
+            program_id: id(),
+            accounts: vec![
+                AccountMeta::new_readonly(pda, false),
+                AccountMeta::new(VALID_MINT_PUBKEY, false),
+                AccountMeta::new(token_account_address, false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+                AccountMeta::new(faucet_pubkey, false),
+                AccountMeta::new(claim_record, false),
+                AccountMeta::new(payer.pubkey(), true),
+                AccountMeta::new_readonly(solana_program::system_program::id(), false),
+                AccountMeta::new_readonly(sysvar::clock::id(), false),
+                AccountMeta::new_readonly(sysvar::rent::id(), false),
+            ],
+            data: vec![1, 5, 0, 0, 0, 0, 0, 0, 0],
+        }],
+        Some(&payer.pubkey()),
+    );
+
+    mint_tokens_tx.sign(&[&payer], recent_blockhash);
+
+    // WHEN THEN
+    let error = banks_client
+        .process_transaction(mint_tokens_tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        TransactionError::InstructionError(0, InstructionError::Custom(0x12)),
+        error
+    );
+}
+
+#[tokio::test]
+async fn test_mint_tokens_cooldown_beyond_i64_max_seconds() {
+    // GIVEN
+    let token_account_address = VALID_TOKEN_ACCOUNT_PUBKEY;
+    let faucet_pubkey = Pubkey::new_unique();
+    let mut pc = program_test();
+    pc.add_account_with_file_data(
+        token_account_address,
+        1000000000,
+        spl_token::id(),
+        "valid-token-account-data.bin",
+    );
+    pc.add_account(
+        faucet_pubkey,
+        faucet_account(state::Faucet {
+            cooldown: Some(state::Cooldown::Seconds(u64::MAX)),
+            ..initialized_faucet(None, VALID_MINT_PUBKEY, 10)
+        }),
+    );
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_pubkey);
+    pc.add_account(VALID_MINT_PUBKEY, mint_account(&pda));
+    let (claim_record, _nonce) =
+        find_claim_record_address(&id(), &faucet_pubkey, &TOKEN_ACCOUNT_OWNER_PUBKEY);
+    pc.add_account(
+        claim_record,
+        claim_record_account(state::ClaimRecord {
+            is_initialized: true,
+            last_claim_slot: 0,
+            last_claim_timestamp: 0,
+            total_claimed: 0,
+        }),
+    );
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut mint_tokens_tx = Transaction::new_with_payer(
+        &[Instruction {
+            program_id: id(),
+            accounts: vec![
+                AccountMeta::new_readonly(pda, false),
+                AccountMeta::new(VALID_MINT_PUBKEY, false),
+                AccountMeta::new(token_account_address, false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+                AccountMeta::new(faucet_pubkey, false),
+                AccountMeta::new(claim_record, false),
+                AccountMeta::new(payer.pubkey(), true),
+                AccountMeta::new_readonly(solana_program::system_program::id(), false),
+                AccountMeta::new_readonly(sysvar::clock::id(), false),
+                AccountMeta::new_readonly(sysvar::rent::id(), false),
+            ],
+            data: vec![1, 5, 0, 0, 0, 0, 0, 0, 0],
+        }],
+        Some(&payer.pubkey()),
+    );
+
+    mint_tokens_tx.sign(&[&payer], recent_blockhash);
+
+    // WHEN THEN
+    let error = banks_client
+        .process_transaction(mint_tokens_tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        TransactionError::InstructionError(0, InstructionError::Custom(0x12)),
+        error
+    );
+}
+
+#[tokio::test]
+async fn test_happy_flow_mint_tokens_cooldown_elapsed() {
+    // GIVEN
+    let token_account_address = VALID_TOKEN_ACCOUNT_PUBKEY;
+    let faucet_pubkey = Pubkey::new_unique();
+    let mut pc = program_test();
+    pc.add_account_with_file_data(
+        token_account_address,
+        1000000000,
+        spl_token::id(),
+        "valid-token-account-data.bin",
+    );
+    pc.add_account(
+        faucet_pubkey,
+        faucet_account(state::Faucet {
+            cooldown: Some(state::Cooldown::Slots(1)),
+            ..initialized_faucet(None, VALID_MINT_PUBKEY, 10)
+        }),
+    );
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_pubkey);
+    pc.add_account(VALID_MINT_PUBKEY, mint_account(&pda));
+    // the last claim happened in the genesis slot, the bank starts at least one slot later
+    let (claim_record, _nonce) =
+        find_claim_record_address(&id(), &faucet_pubkey, &TOKEN_ACCOUNT_OWNER_PUBKEY);
+    pc.add_account(
+        claim_record,
+        claim_record_account(state::ClaimRecord {
+            is_initialized: true,
+            last_claim_slot: 0,
+            last_claim_timestamp: 0,
+            total_claimed: 0,
+        }),
+    );
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut mint_tokens_tx = Transaction::new_with_payer(
+        &[Instruction {
+            program_id: id(),
+            accounts: vec![
+                AccountMeta::new_readonly(pda, false),
+                AccountMeta::new(VALID_MINT_PUBKEY, false),
+                AccountMeta::new(token_account_address, false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+                AccountMeta::new(faucet_pubkey, false),
+                AccountMeta::new(claim_record, false),
+                AccountMeta::new(payer.pubkey(), true),
+                AccountMeta::new_readonly(solana_program::system_program::id(), false),
+                AccountMeta::new_readonly(sysvar::clock::id(), false),
+                AccountMeta::new_readonly(sysvar::rent::id(), false),
+            ],
+            data: vec![1, 5, 0, 0, 0, 0, 0, 0, 0],
+        }],
+        Some(&payer.pubkey()),
+    );
+
+    mint_tokens_tx.sign(&[&payer], recent_blockhash);
+
+    // WHEN
+    banks_client
+        .process_transaction(mint_tokens_tx)
+        .await
+        .unwrap();
+
+    // THEN
+    let acc = banks_client
+        .get_account(claim_record)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(
+        state::ClaimRecord::unpack_from_slice(&acc.data)
+            .unwrap()
+            .last_claim_slot
+            > 0
+    );
+}
+
+#[tokio::test]
+async fn test_happy_flow_mint_tokens_creates_claim_record() {
+    // GIVEN
+    let token_account_address = VALID_TOKEN_ACCOUNT_PUBKEY;
+    let faucet_pubkey = Pubkey::new_unique();
+    let mut pc = program_test();
+    pc.add_account_with_file_data(
+        token_account_address,
+        1000000000,
+        spl_token::id(),
+        "valid-token-account-data.bin",
+    );
+    pc.add_account(
+        faucet_pubkey,
+        faucet_account(state::Faucet {
+            cooldown: Some(state::Cooldown::Seconds(3600)),
+            ..initialized_faucet(None, VALID_MINT_PUBKEY, 10)
+        }),
+    );
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_pubkey);
+    pc.add_account(VALID_MINT_PUBKEY, mint_account(&pda));
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+    let (claim_record, _nonce) =
+        find_claim_record_address(&id(), &faucet_pubkey, &TOKEN_ACCOUNT_OWNER_PUBKEY);
+
+    let mint_tokens_ix = Instruction {
+        program_id: id(),
+        accounts: vec![
+            AccountMeta::new_readonly(pda, false),
+            AccountMeta::new(VALID_MINT_PUBKEY, false),
+            AccountMeta::new(token_account_address, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new(faucet_pubkey, false),
+            AccountMeta::new(claim_record, false),
+            AccountMeta::new(payer.pubkey(), true),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+        ],
+        data: vec![1, 5, 0, 0, 0, 0, 0, 0, 0],
+    };
+    let mut mint_tokens_tx =
+        Transaction::new_with_payer(&[mint_tokens_ix.clone()], Some(&payer.pubkey()));
+    mint_tokens_tx.sign(&[&payer], recent_blockhash);
+
+    // WHEN
+    banks_client
+        .process_transaction(mint_tokens_tx)
+        .await
+        .unwrap();
+
+    // THEN
+    let acc = banks_client
+        .get_account(claim_record)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(id(), acc.owner);
+    assert!(
+        state::ClaimRecord::unpack_from_slice(&acc.data)
+            .unwrap()
+            .is_initialized
+    );
+
+    // the second claim within the hour is rejected
+    let mut second_mint_tokens_tx = Transaction::new_with_payer(
+        &[
+            // differentiates the transaction from the first one
+            Instruction {
+                data: vec![1, 4, 0, 0, 0, 0, 0, 0, 0],
+                ..mint_tokens_ix
+            },
+        ],
+        Some(&payer.pubkey()),
+    );
+    second_mint_tokens_tx.sign(&[&payer], recent_blockhash);
+    let error = banks_client
+        .process_transaction(second_mint_tokens_tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        TransactionError::InstructionError(0, InstructionError::Custom(0x12)),
+        error
+    );
+}
+
+#[tokio::test]
+async fn test_mint_tokens_incorrect_claim_record() {
+    // GIVEN
+    let token_account_address = VALID_TOKEN_ACCOUNT_PUBKEY;
+    let faucet_pubkey = Pubkey::new_unique();
+    let mut pc = program_test();
+    pc.add_account_with_file_data(
+        token_account_address,
+        1000000000,
+        spl_token::id(),
+        "valid-token-account-data.bin",
+    );
+    pc.add_account(
+        faucet_pubkey,
+        faucet_account(state::Faucet {
+            cooldown: Some(state::Cooldown::Slots(1_000_000)),
+            ..initialized_faucet(None, VALID_MINT_PUBKEY, 10)
+        }),
+    );
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_pubkey);
+    pc.add_account(VALID_MINT_PUBKEY, mint_account(&pda));
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    // a fresh claim record for every claim would circumvent the cooldown
+    let mut mint_tokens_tx = Transaction::new_with_payer(
+        &[Instruction {
+            program_id: id(),
+            accounts: vec![
+                AccountMeta::new_readonly(pda, false),
+                AccountMeta::new(VALID_MINT_PUBKEY, false),
+                AccountMeta::new(token_account_address, false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+                AccountMeta::new(faucet_pubkey, false),
+                AccountMeta::new(Pubkey::new_unique(), false),
+                AccountMeta::new(payer.pubkey(), true),
+                AccountMeta::new_readonly(solana_program::system_program::id(), false),
+                AccountMeta::new_readonly(sysvar::clock::id(), false),
+                AccountMeta::new_readonly(sysvar::rent::id(), false),
+            ],
+            data: vec![1, 5, 0, 0, 0, 0, 0, 0, 0],
+        }],
+        Some(&payer.pubkey()),
+    );
+
+    mint_tokens_tx.sign(&[&payer], recent_blockhash);
+
+    // WHEN THEN
+    let error = banks_client
+        .process_transaction(mint_tokens_tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        TransactionError::InstructionError(0, InstructionError::Custom(0x13)),
+        error
+    );
+}
+
+#[tokio::test]
+async fn test_mint_tokens_recipient_limit_exceeded() {
+    // GIVEN
+    let token_account_address = VALID_TOKEN_ACCOUNT_PUBKEY;
+    let faucet_pubkey = Pubkey::new_unique();
+    let mut pc = program_test();
+    pc.add_account_with_file_data(
+        token_account_address,
+        1000000000,
+        spl_token::id(),
+        "valid-token-account-data.bin",
+    );
+    pc.add_account(
+        faucet_pubkey,
+        faucet_account(state::Faucet {
+            recipient_limit: Some(1000),
+            ..initialized_faucet(None, VALID_MINT_PUBKEY, 10)
+        }),
+    );
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_pubkey);
+    pc.add_account(VALID_MINT_PUBKEY, mint_account(&pda));
+    let (claim_record, _nonce) =
+        find_claim_record_address(&id(), &faucet_pubkey, &TOKEN_ACCOUNT_OWNER_PUBKEY);
+    pc.add_account(
+        claim_record,
+        claim_record_account(state::ClaimRecord {
+            is_initialized: true,
+            last_claim_slot: 0,
+            last_claim_timestamp: 0,
+            total_claimed: 998,
+        }),
+    );
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut mint_tokens_tx = Transaction::new_with_payer(
+        &[Instruction {
+            program_id: id(),
+            accounts: vec![
+                AccountMeta::new_readonly(pda, false),
+                AccountMeta::new(VALID_MINT_PUBKEY, false),
+                AccountMeta::new(token_account_address, false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+                AccountMeta::new(faucet_pubkey, false),
+                AccountMeta::new(claim_record, false),
+                AccountMeta::new(payer.pubkey(), true),
+                AccountMeta::new_readonly(solana_program::system_program::id(), false),
+                AccountMeta::new_readonly(sysvar::clock::id(), false),
+                AccountMeta::new_readonly(sysvar::rent::id(), false),
+            ],
+            data: vec![1, 5, 0, 0, 0, 0, 0, 0, 0],
+        }],
+        Some(&payer.pubkey()),
+    );
+
+    mint_tokens_tx.sign(&[&payer], recent_blockhash);
+
+    // WHEN THEN
+    let error = banks_client
+        .process_transaction(mint_tokens_tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        TransactionError::InstructionError(0, InstructionError::Custom(0x15)),
+        error
+    );
+}
+
+#[tokio::test]
+async fn test_happy_flow_mint_tokens_up_to_recipient_limit() {
+    // GIVEN
+    let token_account_address = VALID_TOKEN_ACCOUNT_PUBKEY;
+    let faucet_pubkey = Pubkey::new_unique();
+    let mut pc = program_test();
+    pc.add_account_with_file_data(
+        token_account_address,
+        1000000000,
+        spl_token::id(),
+        "valid-token-account-data.bin",
+    );
+    pc.add_account(
+        faucet_pubkey,
+        faucet_account(state::Faucet {
+            recipient_limit: Some(1000),
+            ..initialized_faucet(None, VALID_MINT_PUBKEY, 10)
+        }),
+    );
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_pubkey);
+    pc.add_account(VALID_MINT_PUBKEY, mint_account(&pda));
+    let (claim_record, _nonce) =
+        find_claim_record_address(&id(), &faucet_pubkey, &TOKEN_ACCOUNT_OWNER_PUBKEY);
+    pc.add_account(
+        claim_record,
+        claim_record_account(state::ClaimRecord {
+            is_initialized: true,
+            last_claim_slot: 0,
+            last_claim_timestamp: 0,
+            total_claimed: 995,
+        }),
+    );
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut mint_tokens_tx = Transaction::new_with_payer(
+        &[Instruction {
+            program_id: id(),
+            accounts: vec![
+                AccountMeta::new_readonly(pda, false),
+                AccountMeta::new(VALID_MINT_PUBKEY, false),
+                AccountMeta::new(token_account_address, false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+                AccountMeta::new(faucet_pubkey, false),
+                AccountMeta::new(claim_record, false),
+                AccountMeta::new(payer.pubkey(), true),
+                AccountMeta::new_readonly(solana_program::system_program::id(), false),
+                AccountMeta::new_readonly(sysvar::clock::id(), false),
+                AccountMeta::new_readonly(sysvar::rent::id(), false),
+            ],
+            data: vec![1, 5, 0, 0, 0, 0, 0, 0, 0],
+        }],
+        Some(&payer.pubkey()),
+    );
+
+    mint_tokens_tx.sign(&[&payer], recent_blockhash);
+
+    // WHEN
+    banks_client
+        .process_transaction(mint_tokens_tx)
+        .await
+        .unwrap();
+
+    // THEN
+    let acc = banks_client
+        .get_account(claim_record)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        1000,
+        state::ClaimRecord::unpack_from_slice(&acc.data)
+            .unwrap()
+            .total_claimed
+    );
+}
+
+#[tokio::test]
+async fn test_mint_tokens_faucet_not_writable() {
+    // GIVEN
+    let token_account_address = VALID_TOKEN_ACCOUNT_PUBKEY;
+    let admin_keypair = Keypair::new();
+    let faucet_pubkey = Pubkey::new_unique();
+    let mut pc = program_test();
+    pc.add_account_with_file_data(
+        token_account_address,
+        1000000000,
+        spl_token::id(),
+        "valid-token-account-data.bin",
+    );
+    pc.add_account(
+        faucet_pubkey,
+        faucet_account(initialized_faucet(
+            Some(admin_keypair.pubkey()),
+            VALID_MINT_PUBKEY,
+            10,
+        )),
+    );
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_pubkey);
+    pc.add_account(VALID_MINT_PUBKEY, mint_account(&pda));
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut mint_tokens_tx = Transaction::new_with_payer(
+        &[Instruction {
+            program_id: id(),
+            accounts: vec![
+                AccountMeta::new_readonly(pda, false),
+                AccountMeta::new(VALID_MINT_PUBKEY, false),
+                AccountMeta::new(token_account_address, false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+                AccountMeta::new_readonly(faucet_pubkey, false),
+            ],
+            data: vec![1, 5, 0, 0, 0, 0, 0, 0, 0],
+        }],
+        Some(&payer.pubkey()),
+    );
+
+    mint_tokens_tx.sign(&[&payer], recent_blockhash);
+
+    // WHEN THEN
+    let error = banks_client
+        .process_transaction(mint_tokens_tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        TransactionError::InstructionError(0, InstructionError::Custom(0x10)),
+        error
+    );
+}
+
+#[tokio::test]
+async fn test_mint_tokens_faucet_exhausted() {
+    // GIVEN
+    let token_account_address = VALID_TOKEN_ACCOUNT_PUBKEY;
+    let admin_keypair = Keypair::new();
+    let faucet_pubkey = Pubkey::new_unique();
+    let mut pc = program_test();
+    pc.add_account_with_file_data(
+        token_account_address,
+        1000000000,
+        spl_token::id(),
+        "valid-token-account-data.bin",
+    );
+    pc.add_account(
+        faucet_pubkey,
+        faucet_account(state::Faucet {
+            max_total_minted: Some(100),
+            total_minted: 98,
+            cap_admin_mints: false,
+            ..initialized_faucet(Some(admin_keypair.pubkey()), VALID_MINT_PUBKEY, 10)
+        }),
+    );
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_pubkey);
+    pc.add_account(VALID_MINT_PUBKEY, mint_account(&pda));
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut mint_tokens_tx = Transaction::new_with_payer(
+        &[Instruction {
+            program_id: id(),
+            accounts: vec![
+                AccountMeta::new_readonly(pda, false),
+                AccountMeta::new(VALID_MINT_PUBKEY, false),
+                AccountMeta::new(token_account_address, false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+                AccountMeta::new(faucet_pubkey, false),
+            ],
+            data: vec![1, 5, 0, 0, 0, 0, 0, 0, 0],
+        }],
+        Some(&payer.pubkey()),
+    );
+
+    mint_tokens_tx.sign(&[&payer], recent_blockhash);
+
+    // WHEN THEN
+    let error = banks_client
+        .process_transaction(mint_tokens_tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        TransactionError::InstructionError(0, InstructionError::Custom(0x16)),
+        error
+    );
+}
+
+#[tokio::test]
+async fn test_mint_tokens_faucet_exhausted_admin_capped() {
+    // GIVEN
+    let token_account_address = VALID_TOKEN_ACCOUNT_PUBKEY;
+    let admin_keypair = Keypair::new();
+    let faucet_pubkey = Pubkey::new_unique();
+    let mut pc = program_test();
+    pc.add_account_with_file_data(
+        token_account_address,
+        1000000000,
+        spl_token::id(),
+        "valid-token-account-data.bin",
+    );
+    pc.add_account(
+        faucet_pubkey,
+        faucet_account(state::Faucet {
+            max_total_minted: Some(100),
+            total_minted: 98,
+            cap_admin_mints: true,
+            ..initialized_faucet(Some(admin_keypair.pubkey()), VALID_MINT_PUBKEY, 10)
+        }),
+    );
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_pubkey);
+    pc.add_account(VALID_MINT_PUBKEY, mint_account(&pda));
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut mint_tokens_tx = Transaction::new_with_payer(
+        &[Instruction {
+            program_id: id(),
+            accounts: vec![
+                AccountMeta::new_readonly(pda, false),
+                AccountMeta::new(VALID_MINT_PUBKEY, false),
+                AccountMeta::new(token_account_address, false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+                AccountMeta::new(faucet_pubkey, false),
+                AccountMeta::new_readonly(admin_keypair.pubkey(), true),
+            ],
+            data: vec![1, 5, 0, 0, 0, 0, 0, 0, 0],
+        }],
+        Some(&payer.pubkey()),
+    );
+
+    mint_tokens_tx.sign(&[&payer, &admin_keypair], recent_blockhash);
+
+    // WHEN THEN
+    let error = banks_client
+        .process_transaction(mint_tokens_tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        TransactionError::InstructionError(0, InstructionError::Custom(0x16)),
+        error
+    );
+}
+
+#[tokio::test]
+async fn test_mint_tokens_paused() {
+    // GIVEN
+    let token_account_address = VALID_TOKEN_ACCOUNT_PUBKEY;
+    let admin_keypair = Keypair::new();
+    let faucet_pubkey = Pubkey::new_unique();
+    let mut pc = program_test();
+    pc.add_account_with_file_data(
+        token_account_address,
+        1000000000,
+        spl_token::id(),
+        "valid-token-account-data.bin",
+    );
+    pc.add_account(
+        faucet_pubkey,
+        faucet_account(state::Faucet {
+            paused: true,
+            ..initialized_faucet(Some(admin_keypair.pubkey()), VALID_MINT_PUBKEY, 10)
+        }),
+    );
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_pubkey);
+    pc.add_account(VALID_MINT_PUBKEY, mint_account(&pda));
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut mint_tokens_tx = Transaction::new_with_payer(
+        &[Instruction {
+            program_id: id(),
+            accounts: vec![
+                AccountMeta::new_readonly(pda, false),
+                AccountMeta::new(VALID_MINT_PUBKEY, false),
+                AccountMeta::new(token_account_address, false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+                AccountMeta::new(faucet_pubkey, false),
+            ],
+            data: vec![1, 5, 0, 0, 0, 0, 0, 0, 0],
+        }],
+        Some(&payer.pubkey()),
+    );
+
+    mint_tokens_tx.sign(&[&payer], recent_blockhash);
+
+    // WHEN THEN
+    let error = banks_client
+        .process_transaction(mint_tokens_tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        TransactionError::InstructionError(0, InstructionError::Custom(0x1c)),
+        error
+    );
+}
+
+#[tokio::test]
+async fn test_mint_tokens_paused_admin_not_allowed() {
+    // GIVEN
+    let token_account_address = VALID_TOKEN_ACCOUNT_PUBKEY;
+    let admin_keypair = Keypair::new();
+    let faucet_pubkey = Pubkey::new_unique();
+    let mut pc = program_test();
+    pc.add_account_with_file_data(
+        token_account_address,
+        1000000000,
+        spl_token::id(),
+        "valid-token-account-data.bin",
+    );
+    pc.add_account(
+        faucet_pubkey,
+        faucet_account(state::Faucet {
+            paused: true,
+            ..initialized_faucet(Some(admin_keypair.pubkey()), VALID_MINT_PUBKEY, 10)
+        }),
+    );
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_pubkey);
+    pc.add_account(VALID_MINT_PUBKEY, mint_account(&pda));
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut mint_tokens_tx = Transaction::new_with_payer(
+        &[Instruction {
+            program_id: id(),
+            accounts: vec![
+                AccountMeta::new_readonly(pda, false),
+                AccountMeta::new(VALID_MINT_PUBKEY, false),
+                AccountMeta::new(token_account_address, false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+                AccountMeta::new(faucet_pubkey, false),
+                AccountMeta::new_readonly(admin_keypair.pubkey(), true),
+            ],
+            data: vec![1, 5, 0, 0, 0, 0, 0, 0, 0],
+        }],
+        Some(&payer.pubkey()),
+    );
+
+    mint_tokens_tx.sign(&[&payer, &admin_keypair], recent_blockhash);
+
+    // WHEN THEN
+    let error = banks_client
+        .process_transaction(mint_tokens_tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        TransactionError::InstructionError(0, InstructionError::Custom(0x1c)),
+        error
+    );
+}
+
+#[tokio::test]
+async fn test_happy_flow_pause_and_unpause() {
+    // GIVEN
+    let admin_keypair = Keypair::new();
+    let (mut banks_client, payer, recent_blockhash, faucet_pubkey) =
+        create_faucet(program_test(), Some(admin_keypair.pubkey())).await;
+    let mut pause_tx = Transaction::new_with_payer(
+        &[instruction::pause(
+            &id(),
+            &admin_keypair.pubkey(),
+            &faucet_pubkey,
+        )],
+        Some(&payer.pubkey()),
+    );
+    pause_tx.sign(&[&payer, &admin_keypair], recent_blockhash);
+
+    // WHEN
+    banks_client.process_transaction(pause_tx).await.unwrap();
+
+    // THEN
+    let faucet_acc = banks_client
+        .get_account(faucet_pubkey)
+        .await
+        .unwrap()
+        .unwrap();
+    let faucet = state::Faucet::unpack_from_slice(&faucet_acc.data).unwrap();
+    assert!(faucet.paused);
+
+    // WHEN
+    let mut unpause_tx = Transaction::new_with_payer(
+        &[instruction::unpause(
+            &id(),
+            &admin_keypair.pubkey(),
+            &faucet_pubkey,
+        )],
+        Some(&payer.pubkey()),
+    );
+    unpause_tx.sign(&[&payer, &admin_keypair], recent_blockhash);
+    banks_client.process_transaction(unpause_tx).await.unwrap();
+
+    // THEN
+    let faucet_acc = banks_client
+        .get_account(faucet_pubkey)
+        .await
+        .unwrap()
+        .unwrap();
+    let faucet = state::Faucet::unpack_from_slice(&faucet_acc.data).unwrap();
+    assert!(!faucet.paused);
+}
+
+#[tokio::test]
+async fn test_pause_impostor_admin() {
+    // GIVEN
+    let admin_keypair = Keypair::new();
+    let impostor_admin_keypair = Keypair::new();
+    let (mut banks_client, payer, recent_blockhash, faucet_pubkey) =
+        create_faucet(program_test(), Some(admin_keypair.pubkey())).await;
+    let mut pause_tx = Transaction::new_with_payer(
+        &[Instruction {
+            program_id: id(),
+            accounts: vec![
+                AccountMeta::new_readonly(impostor_admin_keypair.pubkey(), true),
+                AccountMeta::new(faucet_pubkey, false),
+            ],
+            data: vec![8],
+        }],
+        Some(&payer.pubkey()),
+    );
+
+    pause_tx.sign(&[&payer, &impostor_admin_keypair], recent_blockhash);
+
+    // WHEN THEN
+    let error = banks_client
+        .process_transaction(pause_tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        TransactionError::InstructionError(0, InstructionError::Custom(0x1b)),
+        error
+    );
+}
+
+#[tokio::test]
+async fn test_happy_flow_mint_tokens_faucet_exhausted_admin_not_capped() {
+    // GIVEN
+    let token_account_address = VALID_TOKEN_ACCOUNT_PUBKEY;
+    let admin_keypair = Keypair::new();
+    let faucet_pubkey = Pubkey::new_unique();
+    let mut pc = program_test();
+    pc.add_account_with_file_data(
+        token_account_address,
+        1000000000,
+        spl_token::id(),
+        "valid-token-account-data.bin",
+    );
+    pc.add_account(
+        faucet_pubkey,
+        faucet_account(state::Faucet {
+            max_total_minted: Some(100),
+            total_minted: 98,
+            cap_admin_mints: false,
+            ..initialized_faucet(Some(admin_keypair.pubkey()), VALID_MINT_PUBKEY, 10)
+        }),
+    );
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_pubkey);
+    pc.add_account(VALID_MINT_PUBKEY, mint_account(&pda));
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut mint_tokens_tx = Transaction::new_with_payer(
+        &[Instruction {
+            program_id: id(),
+            accounts: vec![
+                AccountMeta::new_readonly(pda, false),
+                AccountMeta::new(VALID_MINT_PUBKEY, false),
+                AccountMeta::new(token_account_address, false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+                AccountMeta::new(faucet_pubkey, false),
+                AccountMeta::new_readonly(admin_keypair.pubkey(), true),
+            ],
+            data: vec![1, 5, 0, 0, 0, 0, 0, 0, 0],
+        }],
+        Some(&payer.pubkey()),
+    );
+
+    mint_tokens_tx.sign(&[&payer, &admin_keypair], recent_blockhash);
+
+    // WHEN
+    banks_client
+        .process_transaction(mint_tokens_tx)
+        .await
+        .unwrap();
+
+    // THEN
+    let acc = banks_client
+        .get_account(faucet_pubkey)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        103,
+        state::Faucet::unpack_from_slice(&acc.data)
+            .unwrap()
+            .total_minted
+    );
+}
+
+#[tokio::test]
+async fn test_happy_flow_update_config() {
+    // GIVEN
+    let admin_keypair = Keypair::new();
+    let (mut banks_client, payer, recent_blockhash, faucet_pubkey) =
+        create_faucet(program_test(), Some(admin_keypair.pubkey())).await;
+    let mut update_config_tx = Transaction::new_with_payer(
+        &[instruction::update_config(
+            &id(),
+            &admin_keypair.pubkey(),
+            &faucet_pubkey,
+            state::FaucetConfig {
+                amount: 20,
+                recipient_limit: Some(100),
+                ..state::FaucetConfig::default()
+            },
+        )],
+        Some(&payer.pubkey()),
+    );
+
+    update_config_tx.sign(&[&payer, &admin_keypair], recent_blockhash);
+
+    // WHEN
+    banks_client
+        .process_transaction(update_config_tx)
+        .await
+        .unwrap();
+
+    // THEN
+    let faucet_acc = banks_client
+        .get_account(faucet_pubkey)
+        .await
+        .unwrap()
+        .unwrap();
+    let faucet = state::Faucet::unpack_from_slice(&faucet_acc.data).unwrap();
+    assert_eq!(20, faucet.amount);
+    assert_eq!(Some(100), faucet.recipient_limit);
+    assert_eq!(VALID_MINT_PUBKEY, faucet.mint);
+    assert_eq!(COption::Some(admin_keypair.pubkey()), faucet.admin);
+}
+
+#[tokio::test]
+async fn test_update_config_untracked_lamports_per_claim() {
+    // GIVEN
+    let admin_keypair = Keypair::new();
+    let (mut banks_client, payer, recent_blockhash, faucet_pubkey) =
+        create_faucet(program_test(), Some(admin_keypair.pubkey())).await;
+    let mut update_config_tx = Transaction::new_with_payer(
+        &[instruction::update_config(
+            &id(),
+            &admin_keypair.pubkey(),
+            &faucet_pubkey,
+            state::FaucetConfig {
+                amount: 20,
+                lamports_per_claim: Some(5_000_000),
+                ..state::FaucetConfig::default()
+            },
+        )],
+        Some(&payer.pubkey()),
+    );
+
+    update_config_tx.sign(&[&payer, &admin_keypair], recent_blockhash);
+
+    // WHEN THEN
+    let error = banks_client
+        .process_transaction(update_config_tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        TransactionError::InstructionError(0, InstructionError::Custom(0x2b)),
+        error
+    );
+}
+
+#[tokio::test]
+async fn test_update_config_impostor_admin() {
+    // GIVEN
+    let admin_keypair = Keypair::new();
+    let (mut banks_client, payer, recent_blockhash, faucet_pubkey) =
+        create_faucet(program_test(), Some(admin_keypair.pubkey())).await;
+
+    let impostor_admin_keypair = Keypair::new();
+    let mut update_config_tx = Transaction::new_with_payer(
+        &[Instruction {
+            program_id: id(),
+            accounts: vec![
+                AccountMeta::new_readonly(impostor_admin_keypair.pubkey(), true),
+                AccountMeta::new(faucet_pubkey, false),
+            ],
+            data: instruction::FaucetInstruction::UpdateConfig {
+                config: state::FaucetConfig {
+                    amount: 20,
+                    recipient_limit: Some(100),
+                    ..state::FaucetConfig::default()
+                },
+            }
+            .pack(),
+        }],
+        Some(&payer.pubkey()),
+    );
+
+    update_config_tx.sign(&[&payer, &impostor_admin_keypair], recent_blockhash);
+
+    // WHEN THEN
+    let error = banks_client
+        .process_transaction(update_config_tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        TransactionError::InstructionError(0, InstructionError::Custom(0x17)),
+        error
+    );
+}
+
+#[tokio::test]
+async fn test_update_config_not_updatable() {
+    // GIVEN
+    let admin_keypair = Keypair::new();
+    let (mut banks_client, payer, recent_blockhash, faucet_pubkey) =
+        create_faucet(program_test(), None).await;
+    let mut update_config_tx = Transaction::new_with_payer(
+        &[Instruction {
+            program_id: id(),
+            accounts: vec![
+                AccountMeta::new_readonly(admin_keypair.pubkey(), true),
+                AccountMeta::new(faucet_pubkey, false),
+            ],
+            data: instruction::FaucetInstruction::UpdateConfig {
+                config: state::FaucetConfig {
+                    amount: 20,
+                    recipient_limit: Some(100),
+                    ..state::FaucetConfig::default()
+                },
+            }
+            .pack(),
+        }],
+        Some(&payer.pubkey()),
+    );
+
+    update_config_tx.sign(&[&payer, &admin_keypair], recent_blockhash);
+
+    // WHEN THEN
+    let error = banks_client
+        .process_transaction(update_config_tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        TransactionError::InstructionError(0, InstructionError::Custom(0x18)),
+        error
+    );
+}
+
+#[tokio::test]
+async fn test_happy_flow_propose_and_accept_admin() {
+    // GIVEN
+    let admin_keypair = Keypair::new();
+    let new_admin_keypair = Keypair::new();
+    let (mut banks_client, payer, recent_blockhash, faucet_pubkey) =
+        create_faucet(program_test(), Some(admin_keypair.pubkey())).await;
+    let mut rotate_admin_tx = Transaction::new_with_payer(
+        &[
+            instruction::propose_admin(
+                &id(),
+                &admin_keypair.pubkey(),
+                &faucet_pubkey,
+                &new_admin_keypair.pubkey(),
+            ),
+            instruction::accept_admin(&id(), &new_admin_keypair.pubkey(), &faucet_pubkey),
+        ],
+        Some(&payer.pubkey()),
+    );
+
+    rotate_admin_tx.sign(
+        &[&payer, &admin_keypair, &new_admin_keypair],
+        recent_blockhash,
+    );
+
+    // WHEN
+    banks_client
+        .process_transaction(rotate_admin_tx)
+        .await
+        .unwrap();
+
+    // THEN
+    let faucet_acc = banks_client
+        .get_account(faucet_pubkey)
+        .await
+        .unwrap()
+        .unwrap();
+    let faucet = state::Faucet::unpack_from_slice(&faucet_acc.data).unwrap();
+    assert_eq!(COption::Some(new_admin_keypair.pubkey()), faucet.admin);
+    assert_eq!(COption::None, faucet.pending_admin);
+}
+
+#[tokio::test]
+async fn test_propose_admin_impostor_admin() {
+    // GIVEN
+    let admin_keypair = Keypair::new();
+    let impostor_admin_keypair = Keypair::new();
+    let (mut banks_client, payer, recent_blockhash, faucet_pubkey) =
+        create_faucet(program_test(), Some(admin_keypair.pubkey())).await;
+    let mut propose_admin_tx = Transaction::new_with_payer(
+        &[instruction::propose_admin(
+            &id(),
+            &impostor_admin_keypair.pubkey(),
+            &faucet_pubkey,
+            &impostor_admin_keypair.pubkey(),
+        )],
+        Some(&payer.pubkey()),
+    );
+
+    propose_admin_tx.sign(&[&payer, &impostor_admin_keypair], recent_blockhash);
+
+    // WHEN THEN
+    let error = banks_client
+        .process_transaction(propose_admin_tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        TransactionError::InstructionError(0, InstructionError::Custom(0x19)),
+        error
+    );
+}
+
+#[tokio::test]
+async fn test_accept_admin_not_proposed() {
+    // GIVEN
+    let admin_keypair = Keypair::new();
+    let new_admin_keypair = Keypair::new();
+    let impostor_admin_keypair = Keypair::new();
+    let (mut banks_client, payer, recent_blockhash, faucet_pubkey) =
+        create_faucet(program_test(), Some(admin_keypair.pubkey())).await;
+    let mut rotate_admin_tx = Transaction::new_with_payer(
+        &[
+            instruction::propose_admin(
+                &id(),
+                &admin_keypair.pubkey(),
+                &faucet_pubkey,
+                &new_admin_keypair.pubkey(),
+            ),
+            instruction::accept_admin(&id(), &impostor_admin_keypair.pubkey(), &faucet_pubkey),
+        ],
+        Some(&payer.pubkey()),
+    );
+
+    rotate_admin_tx.sign(
+        &[&payer, &admin_keypair, &impostor_admin_keypair],
+        recent_blockhash,
+    );
+
+    // WHEN THEN
+    let error = banks_client
+        .process_transaction(rotate_admin_tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        TransactionError::InstructionError(1, InstructionError::Custom(0x1a)),
+        error
+    );
+}
+
+#[tokio::test]
+async fn test_happy_flow_renounce_admin() {
+    // GIVEN
+    let admin_keypair = Keypair::new();
+    let (mut banks_client, payer, recent_blockhash, faucet_pubkey) =
+        create_faucet(program_test(), Some(admin_keypair.pubkey())).await;
+    let mut renounce_admin_tx = Transaction::new_with_payer(
+        &[Instruction {
+            program_id: id(),
+            accounts: vec![
+                AccountMeta::new_readonly(admin_keypair.pubkey(), true),
+                AccountMeta::new(faucet_pubkey, false),
+            ],
+            data: vec![7],
+        }],
+        Some(&payer.pubkey()),
+    );
+
+    renounce_admin_tx.sign(&[&payer, &admin_keypair], recent_blockhash);
+
+    // WHEN
+    banks_client
+        .process_transaction(renounce_admin_tx)
+        .await
+        .unwrap();
+
+    // THEN
+    let faucet_acc = banks_client
+        .get_account(faucet_pubkey)
+        .await
+        .unwrap()
+        .unwrap();
+    let faucet = state::Faucet::unpack_from_slice(&faucet_acc.data).unwrap();
+    assert_eq!(COption::None, faucet.admin);
+}
+
+#[tokio::test]
+async fn test_happy_flow_init_faucet_token_2022_mint_with_extensions() {
+    // GIVEN
+    let token_mint_address = Pubkey::new_unique();
+    let mut pc = program_test();
+    let faucet_pubkey = Pubkey::new_unique();
+    pc.add_account(
+        faucet_pubkey,
+        Account::new(faucet_lamports(), state::Faucet::LEN, &id()),
+    );
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_pubkey);
+    pc.add_account(token_mint_address, token_2022_mint_account(&pda));
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut transaction = Transaction::new_with_payer(
+        &[Instruction {
+            program_id: id(),
+            accounts: vec![
+                AccountMeta::new_readonly(token_mint_address, false),
+                AccountMeta::new(faucet_pubkey, false),
+                AccountMeta::new_readonly(sysvar::rent::id(), false),
+            ],
+            data: vec![0, 1, 0, 0, 0, 0, 0, 0, 0],
+        }],
+        Some(&payer.pubkey()),
+    );
+
+    transaction.sign(&[&payer], recent_blockhash);
+
+    // WHEN
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    // THEN
+    let faucet_acc = banks_client
+        .get_account(faucet_pubkey)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        token_mint_address,
+        state::Faucet::unpack_from_slice(&faucet_acc.data)
+            .unwrap()
+            .mint
+    );
+}
+
+#[tokio::test]
+async fn test_happy_flow_mint_tokens_token_2022() {
+    // GIVEN
+    let token_account_address = VALID_TOKEN_ACCOUNT_PUBKEY;
+    let faucet_pubkey = Pubkey::new_unique();
+    let mut pc = program_test();
+    // the SPL Token program stands in for Token-2022, which shares its instructions
+    pc.add_program(
+        "spl_token",
+        token::spl_token_2022::id(),
+        processor!(spl_token::processor::Processor::process),
+    );
+    pc.add_account_with_file_data(
+        token_account_address,
+        1000000000,
+        token::spl_token_2022::id(),
+        "valid-token-account-data.bin",
+    );
+    pc.add_account(
+        faucet_pubkey,
+        faucet_account(initialized_faucet(None, VALID_MINT_PUBKEY, 10)),
+    );
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_pubkey);
+    let mut mint = mint_account(&pda);
+    mint.owner = token::spl_token_2022::id();
+    pc.add_account(VALID_MINT_PUBKEY, mint);
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut mint_tokens_tx = Transaction::new_with_payer(
+        &[Instruction {
+            program_id: id(),
+            accounts: vec![
+                AccountMeta::new_readonly(pda, false),
+                AccountMeta::new(VALID_MINT_PUBKEY, false),
+                AccountMeta::new(token_account_address, false),
+                AccountMeta::new_readonly(token::spl_token_2022::id(), false),
+                AccountMeta::new(faucet_pubkey, false),
+            ],
+            data: vec![1, 5, 0, 0, 0, 0, 0, 0, 0],
+        }],
+        Some(&payer.pubkey()),
+    );
+
+    mint_tokens_tx.sign(&[&payer], recent_blockhash);
+
+    // WHEN
+    banks_client
+        .process_transaction(mint_tokens_tx)
+        .await
+        .unwrap();
+
+    // THEN
+    let acc = banks_client
+        .get_account(token_account_address)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        5,
+        spl_token::state::Account::unpack_from_slice(&acc.data)
+            .unwrap()
+            .amount
+    );
+}
+
+#[tokio::test]
+async fn test_mint_tokens_token_program_not_owning_mint() {
+    // GIVEN
+    let token_account_address = VALID_TOKEN_ACCOUNT_PUBKEY;
+    let faucet_pubkey = Pubkey::new_unique();
+    let mut pc = program_test();
+    pc.add_account_with_file_data(
+        token_account_address,
+        1000000000,
+        token::spl_token_2022::id(),
+        "valid-token-account-data.bin",
+    );
+    pc.add_account(
+        faucet_pubkey,
+        faucet_account(initialized_faucet(None, VALID_MINT_PUBKEY, 10)),
+    );
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_pubkey);
+    pc.add_account(VALID_MINT_PUBKEY, token_2022_mint_account(&pda));
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut mint_tokens_tx = Transaction::new_with_payer(
+        &[Instruction {
+            program_id: id(),
+            accounts: vec![
+                AccountMeta::new_readonly(pda, false),
+                AccountMeta::new(VALID_MINT_PUBKEY, false),
+                AccountMeta::new(token_account_address, false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+                AccountMeta::new(faucet_pubkey, false),
+            ],
+            data: vec![1, 5, 0, 0, 0, 0, 0, 0, 0],
+        }],
+        Some(&payer.pubkey()),
+    );
+
+    mint_tokens_tx.sign(&[&payer], recent_blockhash);
+
+    // WHEN THEN
+    let error = banks_client
+        .process_transaction(mint_tokens_tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        TransactionError::InstructionError(0, InstructionError::Custom(0x0e)),
+        error
+    );
+}
+
+#[tokio::test]
+async fn test_happy_flow_mint_tokens_token_2022_with_extensions() {
+    // GIVEN
+    let faucet_pubkey = Pubkey::new_unique();
+    let dest_pubkey = Pubkey::new_unique();
+    let mut pc = token_2022_program_test();
+    pc.add_account(
+        faucet_pubkey,
+        faucet_account(initialized_faucet(None, VALID_MINT_PUBKEY, 10)),
+    );
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_pubkey);
+    pc.add_account(VALID_MINT_PUBKEY, token_2022_mint_account(&pda));
+    let dest = token_2022_token_account(&VALID_MINT_PUBKEY, &TOKEN_ACCOUNT_OWNER_PUBKEY, 0);
+    let extensions = dest.data[spl_token::state::Account::LEN..].to_vec();
+    pc.add_account(dest_pubkey, dest);
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut mint_tokens_tx = Transaction::new_with_payer(
+        &[instruction::mint_tokens(
+            &id(),
+            &token::spl_token_2022::id(),
+            &VALID_MINT_PUBKEY,
+            &dest_pubkey,
+            &faucet_pubkey,
+            None,
+            5,
+        )],
+        Some(&payer.pubkey()),
+    );
+
+    mint_tokens_tx.sign(&[&payer], recent_blockhash);
+
+    // WHEN
+    banks_client
+        .process_transaction(mint_tokens_tx)
+        .await
+        .unwrap();
+
+    // THEN
+    let dest_acc = banks_client
+        .get_account(dest_pubkey)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(5, token::unpack_account(&dest_acc.data).unwrap().amount);
+    assert_eq!(
+        extensions,
+        dest_acc.data[spl_token::state::Account::LEN..].to_vec()
+    );
+    let mint_acc = banks_client
+        .get_account(VALID_MINT_PUBKEY)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(5, token::unpack_mint(&mint_acc.data).unwrap().supply);
+}
+
+#[tokio::test]
+async fn test_mint_tokens_token_2022_with_extensions_cooldown_not_elapsed() {
+    // GIVEN
+    let faucet_pubkey = Pubkey::new_unique();
+    let dest_pubkey = Pubkey::new_unique();
+    let mut pc = token_2022_program_test();
+    pc.add_account(
+        faucet_pubkey,
+        faucet_account(state::Faucet {
+            cooldown: Some(state::Cooldown::Slots(1_000_000)),
+            ..initialized_faucet(None, VALID_MINT_PUBKEY, 10)
+        }),
+    );
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_pubkey);
+    pc.add_account(VALID_MINT_PUBKEY, token_2022_mint_account(&pda));
+    pc.add_account(
+        dest_pubkey,
+        token_2022_token_account(&VALID_MINT_PUBKEY, &TOKEN_ACCOUNT_OWNER_PUBKEY, 0),
+    );
+    // the claim record is found by the owner of the destination, read past its extensions
+    let (claim_record, _nonce) =
+        find_claim_record_address(&id(), &faucet_pubkey, &TOKEN_ACCOUNT_OWNER_PUBKEY);
+    pc.add_account(
+        claim_record,
+        claim_record_account(state::ClaimRecord {
+            is_initialized: true,
+            last_claim_slot: 0,
+            last_claim_timestamp: 0,
+            total_claimed: 0,
+        }),
+    );
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut mint_tokens_ix = instruction::mint_tokens(
+        &id(),
+        &token::spl_token_2022::id(),
+        &VALID_MINT_PUBKEY,
+        &dest_pubkey,
+        &faucet_pubkey,
+        None,
+        5,
+    );
+    mint_tokens_ix.accounts.extend(vec![
+        AccountMeta::new(claim_record, false),
+        AccountMeta::new(payer.pubkey(), true),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+    ]);
+    let mut mint_tokens_tx = Transaction::new_with_payer(&[mint_tokens_ix], Some(&payer.pubkey()));
+
+    mint_tokens_tx.sign(&[&payer], recent_blockhash);
+
+    // WHEN THEN
+    let error = banks_client
+        .process_transaction(mint_tokens_tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        TransactionError::InstructionError(0, InstructionError::Custom(0x12)),
+        error
+    );
+}
+
+#[tokio::test]
+async fn test_happy_flow_close_faucet_token_2022_with_extensions() {
+    // GIVEN
+    let admin_keypair = Keypair::new();
+    let faucet_pubkey = Pubkey::new_unique();
+    let mut pc = token_2022_program_test();
+    pc.add_account(
+        faucet_pubkey,
+        faucet_account(initialized_faucet(
+            Some(admin_keypair.pubkey()),
+            VALID_MINT_PUBKEY,
+            10,
+        )),
+    );
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_pubkey);
+    pc.add_account(VALID_MINT_PUBKEY, token_2022_mint_account(&pda));
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut close_faucet_tx = Transaction::new_with_payer(
+        &[instruction::close_faucet(
+            &id(),
+            &token::spl_token_2022::id(),
+            &admin_keypair.pubkey(),
+            &faucet_pubkey,
+            &payer.pubkey(),
+            &VALID_MINT_PUBKEY,
+        )],
+        Some(&payer.pubkey()),
+    );
+
+    close_faucet_tx.sign(&[&payer, &admin_keypair], recent_blockhash);
+
+    // WHEN
+    banks_client
+        .process_transaction(close_faucet_tx)
+        .await
+        .unwrap();
+
+    // THEN
+    let mint_acc = banks_client
+        .get_account(VALID_MINT_PUBKEY)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        COption::Some(admin_keypair.pubkey()),
+        token::unpack_mint(&mint_acc.data).unwrap().mint_authority
+    );
+    assert_eq!(None, banks_client.get_account(faucet_pubkey).await.unwrap());
+}
+
+#[tokio::test]
+async fn test_happy_flow_close_vault_faucet_token_2022_with_extensions() {
+    // GIVEN
+    let admin_keypair = Keypair::new();
+    let faucet_pubkey = Pubkey::new_unique();
+    let vault_pubkey = Pubkey::new_unique();
+    let dest_pubkey = Pubkey::new_unique();
+    let mut pc = token_2022_program_test();
+    pc.add_account(
+        faucet_pubkey,
+        faucet_account(state::Faucet {
+            vault: COption::Some(vault_pubkey),
+            ..initialized_faucet(Some(admin_keypair.pubkey()), VALID_MINT_PUBKEY, 10)
+        }),
+    );
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_pubkey);
+    pc.add_account(
+        VALID_MINT_PUBKEY,
+        token_2022_mint_account(&Pubkey::new_unique()),
+    );
+    pc.add_account(
+        vault_pubkey,
+        token_2022_token_account(&VALID_MINT_PUBKEY, &pda, 100),
+    );
+    pc.add_account(
+        dest_pubkey,
+        token_2022_token_account(&VALID_MINT_PUBKEY, &admin_keypair.pubkey(), 0),
+    );
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut close_faucet_ix = instruction::close_faucet(
+        &id(),
+        &token::spl_token_2022::id(),
+        &admin_keypair.pubkey(),
+        &faucet_pubkey,
+        &payer.pubkey(),
+        &VALID_MINT_PUBKEY,
+    );
+    close_faucet_ix.accounts.splice(
+        6..6,
+        vec![
+            AccountMeta::new(vault_pubkey, false),
+            AccountMeta::new(dest_pubkey, false),
+        ],
+    );
+    let mut close_faucet_tx =
+        Transaction::new_with_payer(&[close_faucet_ix], Some(&payer.pubkey()));
+
+    close_faucet_tx.sign(&[&payer, &admin_keypair], recent_blockhash);
+
+    // WHEN
+    banks_client
+        .process_transaction(close_faucet_tx)
+        .await
+        .unwrap();
+
+    // THEN
+    let dest_acc = banks_client
+        .get_account(dest_pubkey)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(100, token::unpack_account(&dest_acc.data).unwrap().amount);
+    assert_eq!(None, banks_client.get_account(vault_pubkey).await.unwrap());
+}
+
+#[tokio::test]
+async fn test_happy_flow_init_vault_faucet() {
+    // GIVEN
+    let faucet_pubkey = Pubkey::new_unique();
+    let vault_pubkey = Pubkey::new_unique();
+    let mut pc = program_test();
+    pc.add_account(
+        faucet_pubkey,
+        Account::new(faucet_lamports(), state::Faucet::LEN, &id()),
+    );
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_pubkey);
+    pc.add_account(VALID_MINT_PUBKEY, mint_account(&Pubkey::new_unique()));
+    pc.add_account(vault_pubkey, token_account(&VALID_MINT_PUBKEY, &pda, 100));
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut init_faucet_tx = Transaction::new_with_payer(
+        &[instruction::init_vault_faucet(
+            &id(),
+            &VALID_MINT_PUBKEY,
+            &faucet_pubkey,
+            &vault_pubkey,
+            None,
+            state::FaucetConfig {
+                amount: 10,
+                ..state::FaucetConfig::default()
+            },
+        )],
+        Some(&payer.pubkey()),
+    );
+
+    init_faucet_tx.sign(&[&payer], recent_blockhash);
+
+    // WHEN
+    banks_client
+        .process_transaction(init_faucet_tx)
+        .await
+        .unwrap();
+
+    // THEN
+    let faucet_acc = banks_client
+        .get_account(faucet_pubkey)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        COption::Some(vault_pubkey),
+        state::Faucet::unpack_from_slice(&faucet_acc.data)
+            .unwrap()
+            .vault
+    );
+}
+
+#[tokio::test]
+async fn test_init_vault_faucet_vault_not_owned_by_pda() {
+    // GIVEN
+    let faucet_pubkey = Pubkey::new_unique();
+    let vault_pubkey = Pubkey::new_unique();
+    let mut pc = program_test();
+    pc.add_account(
+        faucet_pubkey,
+        Account::new(faucet_lamports(), state::Faucet::LEN, &id()),
+    );
+    pc.add_account(VALID_MINT_PUBKEY, mint_account(&Pubkey::new_unique()));
+    pc.add_account(
+        vault_pubkey,
+        token_account(&VALID_MINT_PUBKEY, &Pubkey::new_unique(), 100),
+    );
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut init_faucet_tx = Transaction::new_with_payer(
+        &[instruction::init_vault_faucet(
+            &id(),
+            &VALID_MINT_PUBKEY,
+            &faucet_pubkey,
+            &vault_pubkey,
+            None,
+            state::FaucetConfig {
+                amount: 10,
+                ..state::FaucetConfig::default()
+            },
+        )],
+        Some(&payer.pubkey()),
+    );
+
+    init_faucet_tx.sign(&[&payer], recent_blockhash);
+
+    // WHEN THEN
+    let error = banks_client
+        .process_transaction(init_faucet_tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        TransactionError::InstructionError(0, InstructionError::Custom(0x1d)),
+        error
+    );
+}
+
+#[tokio::test]
+async fn test_happy_flow_mint_tokens_from_vault() {
+    // GIVEN
+    let token_account_address = VALID_TOKEN_ACCOUNT_PUBKEY;
+    let faucet_pubkey = Pubkey::new_unique();
+    let vault_pubkey = Pubkey::new_unique();
+    let mut pc = program_test();
+    pc.add_account_with_file_data(
+        token_account_address,
+        1000000000,
+        spl_token::id(),
+        "valid-token-account-data.bin",
+    );
+    pc.add_account(
+        faucet_pubkey,
+        faucet_account(state::Faucet {
+            vault: COption::Some(vault_pubkey),
+            ..initialized_faucet(None, VALID_MINT_PUBKEY, 10)
+        }),
+    );
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_pubkey);
+    pc.add_account(VALID_MINT_PUBKEY, mint_account(&Pubkey::new_unique()));
+    pc.add_account(vault_pubkey, token_account(&VALID_MINT_PUBKEY, &pda, 100));
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut mint_tokens_tx = Transaction::new_with_payer(
+        &[Instruction {
+            program_id: id(),
+            accounts: vec![
+                AccountMeta::new_readonly(pda, false),
+                AccountMeta::new(VALID_MINT_PUBKEY, false),
+                AccountMeta::new(token_account_address, false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+                AccountMeta::new(faucet_pubkey, false),
+                AccountMeta::new(vault_pubkey, false),
+            ],
+            data: vec![1, 5, 0, 0, 0, 0, 0, 0, 0],
+        }],
+        Some(&payer.pubkey()),
+    );
+
+    mint_tokens_tx.sign(&[&payer], recent_blockhash);
+
+    // WHEN
+    banks_client
+        .process_transaction(mint_tokens_tx)
+        .await
+        .unwrap();
+
+    // THEN
+    let vault_acc = banks_client
+        .get_account(vault_pubkey)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        95,
+        spl_token::state::Account::unpack_from_slice(&vault_acc.data)
+            .unwrap()
+            .amount
+    );
+}
+
+#[tokio::test]
+async fn test_mint_tokens_incorrect_vault() {
+    // GIVEN
+    let token_account_address = VALID_TOKEN_ACCOUNT_PUBKEY;
+    let faucet_pubkey = Pubkey::new_unique();
+    let vault_pubkey = Pubkey::new_unique();
+    let impostor_vault_pubkey = Pubkey::new_unique();
+    let mut pc = program_test();
+    pc.add_account_with_file_data(
+        token_account_address,
+        1000000000,
+        spl_token::id(),
+        "valid-token-account-data.bin",
+    );
+    pc.add_account(
+        faucet_pubkey,
+        faucet_account(state::Faucet {
+            vault: COption::Some(vault_pubkey),
+            ..initialized_faucet(None, VALID_MINT_PUBKEY, 10)
+        }),
+    );
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_pubkey);
+    pc.add_account(VALID_MINT_PUBKEY, mint_account(&Pubkey::new_unique()));
+    pc.add_account(
+        impostor_vault_pubkey,
+        token_account(&VALID_MINT_PUBKEY, &pda, 100),
+    );
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut mint_tokens_tx = Transaction::new_with_payer(
+        &[Instruction {
+            program_id: id(),
+            accounts: vec![
+                AccountMeta::new_readonly(pda, false),
+                AccountMeta::new(VALID_MINT_PUBKEY, false),
+                AccountMeta::new(token_account_address, false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+                AccountMeta::new(faucet_pubkey, false),
+                AccountMeta::new(impostor_vault_pubkey, false),
+            ],
+            data: vec![1, 5, 0, 0, 0, 0, 0, 0, 0],
+        }],
+        Some(&payer.pubkey()),
+    );
+
+    mint_tokens_tx.sign(&[&payer], recent_blockhash);
+
+    // WHEN THEN
+    let error = banks_client
+        .process_transaction(mint_tokens_tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        TransactionError::InstructionError(0, InstructionError::Custom(0x1d)),
+        error
+    );
+}
+
+#[tokio::test]
+async fn test_withdraw_impostor_admin() {
+    // GIVEN
+    let admin_keypair = Keypair::new();
+    let impostor_admin_keypair = Keypair::new();
+    let faucet_pubkey = Pubkey::new_unique();
+    let vault_pubkey = Pubkey::new_unique();
+    let dest_pubkey = Pubkey::new_unique();
+    let mut pc = program_test();
+    pc.add_account(
+        faucet_pubkey,
+        faucet_account(state::Faucet {
+            vault: COption::Some(vault_pubkey),
+            ..initialized_faucet(Some(admin_keypair.pubkey()), VALID_MINT_PUBKEY, 10)
+        }),
+    );
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_pubkey);
+    pc.add_account(VALID_MINT_PUBKEY, mint_account(&Pubkey::new_unique()));
+    pc.add_account(vault_pubkey, token_account(&VALID_MINT_PUBKEY, &pda, 100));
+    pc.add_account(
+        dest_pubkey,
+        token_account(&VALID_MINT_PUBKEY, &impostor_admin_keypair.pubkey(), 0),
+    );
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut withdraw_tx = Transaction::new_with_payer(
+        &[instruction::withdraw(
+            &id(),
+            &spl_token::id(),
+            &impostor_admin_keypair.pubkey(),
+            &faucet_pubkey,
+            &vault_pubkey,
+            &dest_pubkey,
+            &VALID_MINT_PUBKEY,
+            100,
+        )],
+        Some(&payer.pubkey()),
+    );
+
+    withdraw_tx.sign(&[&payer, &impostor_admin_keypair], recent_blockhash);
+
+    // WHEN THEN
+    let error = banks_client
+        .process_transaction(withdraw_tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        TransactionError::InstructionError(0, InstructionError::Custom(0x1e)),
+        error
+    );
+}
+
+#[tokio::test]
+async fn test_happy_flow_close_vault_faucet_sweeps_vault() {
+    // GIVEN
+    let admin_keypair = Keypair::new();
+    let faucet_pubkey = Pubkey::new_unique();
+    let vault_pubkey = Pubkey::new_unique();
+    let dest_pubkey = Pubkey::new_unique();
+    let mut pc = program_test();
+    pc.add_account(
+        faucet_pubkey,
+        faucet_account(state::Faucet {
+            vault: COption::Some(vault_pubkey),
+            ..initialized_faucet(Some(admin_keypair.pubkey()), VALID_MINT_PUBKEY, 10)
+        }),
+    );
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_pubkey);
+    pc.add_account(VALID_MINT_PUBKEY, mint_account(&Pubkey::new_unique()));
+    pc.add_account(vault_pubkey, token_account(&VALID_MINT_PUBKEY, &pda, 100));
+    pc.add_account(
+        dest_pubkey,
+        token_account(&VALID_MINT_PUBKEY, &admin_keypair.pubkey(), 0),
+    );
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut close_faucet_tx = Transaction::new_with_payer(
+        &[Instruction {
+            program_id: id(),
+            accounts: vec![
+                AccountMeta::new_readonly(admin_keypair.pubkey(), true),
+                AccountMeta::new(faucet_pubkey, false),
+                AccountMeta::new(payer.pubkey(), false),
+                AccountMeta::new(VALID_MINT_PUBKEY, false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+                AccountMeta::new_readonly(pda, false),
+                AccountMeta::new(vault_pubkey, false),
+                AccountMeta::new(dest_pubkey, false),
+                AccountMeta::new(find_sol_vault_address(&id(), &faucet_pubkey).0, false),
+                AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            ],
+            data: vec![2],
+        }],
+        Some(&payer.pubkey()),
+    );
+
+    close_faucet_tx.sign(&[&payer, &admin_keypair], recent_blockhash);
+
+    // WHEN
+    banks_client
+        .process_transaction(close_faucet_tx)
+        .await
+        .unwrap();
+
+    // THEN
+    let dest_acc = banks_client
+        .get_account(dest_pubkey)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        100,
+        spl_token::state::Account::unpack_from_slice(&dest_acc.data)
+            .unwrap()
+            .amount
+    );
+    assert_eq!(None, banks_client.get_account(vault_pubkey).await.unwrap());
+}
+
+#[tokio::test]
+async fn test_happy_flow_drip_lamports() {
+    // GIVEN
+    let faucet_pubkey = Pubkey::new_unique();
+    let recipient_pubkey = Pubkey::new_unique();
+    let mut pc = program_test();
+    pc.add_account(
+        faucet_pubkey,
+        faucet_account(state::Faucet {
+            lamport_amount: 5_000_000,
+            ..initialized_faucet(None, VALID_MINT_PUBKEY, 10)
+        }),
+    );
+    let (sol_vault, _nonce) = find_sol_vault_address(&id(), &faucet_pubkey);
+    pc.add_account(
+        sol_vault,
+        Account::new(1_000_000_000, 0, &solana_program::system_program::id()),
+    );
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut drip_lamports_tx = Transaction::new_with_payer(
+        &[instruction::drip_lamports(
+            &id(),
+            &faucet_pubkey,
+            &recipient_pubkey,
+            None,
+            None,
+            5_000_000,
+        )],
+        Some(&payer.pubkey()),
+    );
+
+    drip_lamports_tx.sign(&[&payer], recent_blockhash);
+
+    // WHEN
+    banks_client
+        .process_transaction(drip_lamports_tx)
+        .await
+        .unwrap();
+
+    // THEN
+    assert_eq!(
+        5_000_000,
+        banks_client.get_balance(recipient_pubkey).await.unwrap()
+    );
+}
+
+#[tokio::test]
+async fn test_drip_too_many_lamports() {
+    // GIVEN
+    let faucet_pubkey = Pubkey::new_unique();
+    let recipient_pubkey = Pubkey::new_unique();
+    let mut pc = program_test();
+    pc.add_account(
+        faucet_pubkey,
+        faucet_account(state::Faucet {
+            lamport_amount: 5_000_000,
+            ..initialized_faucet(None, VALID_MINT_PUBKEY, 10)
+        }),
+    );
+    let (sol_vault, _nonce) = find_sol_vault_address(&id(), &faucet_pubkey);
+    pc.add_account(
+        sol_vault,
+        Account::new(1_000_000_000, 0, &solana_program::system_program::id()),
+    );
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut drip_lamports_tx = Transaction::new_with_payer(
+        &[instruction::drip_lamports(
+            &id(),
+            &faucet_pubkey,
+            &recipient_pubkey,
+            None,
+            None,
+            5_000_001,
+        )],
+        Some(&payer.pubkey()),
+    );
+
+    drip_lamports_tx.sign(&[&payer], recent_blockhash);
+
+    // WHEN THEN
+    let error = banks_client
+        .process_transaction(drip_lamports_tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        TransactionError::InstructionError(0, InstructionError::Custom(0x20)),
+        error
+    );
+}
+
+#[tokio::test]
+async fn test_drip_lamports_incorrect_sol_vault() {
+    // GIVEN
+    let faucet_pubkey = Pubkey::new_unique();
+    let recipient_pubkey = Pubkey::new_unique();
+    let impostor_sol_vault = Pubkey::new_unique();
+    let mut pc = program_test();
+    pc.add_account(
+        faucet_pubkey,
+        faucet_account(state::Faucet {
+            lamport_amount: 5_000_000,
+            ..initialized_faucet(None, VALID_MINT_PUBKEY, 10)
+        }),
+    );
+    pc.add_account(
+        impostor_sol_vault,
+        Account::new(1_000_000_000, 0, &solana_program::system_program::id()),
+    );
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut drip_lamports_ix =
+        instruction::drip_lamports(&id(), &faucet_pubkey, &recipient_pubkey, None, None, 1);
+    drip_lamports_ix.accounts[0] = AccountMeta::new(impostor_sol_vault, false);
+    let mut drip_lamports_tx =
+        Transaction::new_with_payer(&[drip_lamports_ix], Some(&payer.pubkey()));
+
+    drip_lamports_tx.sign(&[&payer], recent_blockhash);
+
+    // WHEN THEN
+    let error = banks_client
+        .process_transaction(drip_lamports_tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        TransactionError::InstructionError(0, InstructionError::Custom(0x1f)),
+        error
+    );
+}
+
+#[tokio::test]
+async fn test_drip_lamports_cooldown_not_elapsed() {
+    // GIVEN
+    let faucet_pubkey = Pubkey::new_unique();
+    let recipient_pubkey = Pubkey::new_unique();
+    let mut pc = program_test();
+    pc.add_account(
+        faucet_pubkey,
+        faucet_account(state::Faucet {
+            lamport_amount: 5_000_000,
+            cooldown: Some(state::Cooldown::Slots(1_000_000)),
+            ..initialized_faucet(None, VALID_MINT_PUBKEY, 10)
+        }),
+    );
+    let (sol_vault, _nonce) = find_sol_vault_address(&id(), &faucet_pubkey);
+    pc.add_account(
+        sol_vault,
+        Account::new(1_000_000_000, 0, &solana_program::system_program::id()),
+    );
+    let (claim_record, _nonce) =
+        find_lamport_claim_record_address(&id(), &faucet_pubkey, &recipient_pubkey);
+    pc.add_account(
+        claim_record,
+        claim_record_account(state::ClaimRecord {
+            is_initialized: true,
+            last_claim_slot: 0,
+            last_claim_timestamp: 0,
+            total_claimed: 5_000_000,
+        }),
+    );
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut drip_lamports_tx = Transaction::new_with_payer(
+        &[instruction::drip_lamports(
+            &id(),
+            &faucet_pubkey,
+            &recipient_pubkey,
+            Some(&payer.pubkey()),
+            None,
+            1,
+        )],
+        Some(&payer.pubkey()),
+    );
+
+    drip_lamports_tx.sign(&[&payer], recent_blockhash);
+
+    // WHEN THEN
+    let error = banks_client
+        .process_transaction(drip_lamports_tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        TransactionError::InstructionError(0, InstructionError::Custom(0x12)),
+        error
+    );
+}
+
+#[tokio::test]
+async fn test_mint_tokens_lamports_per_claim_incorrect_recipient() {
+    // GIVEN
+    let token_account_address = VALID_TOKEN_ACCOUNT_PUBKEY;
+    let faucet_pubkey = Pubkey::new_unique();
+    let impostor_recipient_pubkey = Pubkey::new_unique();
+    let mut pc = program_test();
+    pc.add_account_with_file_data(
+        token_account_address,
+        1000000000,
+        spl_token::id(),
+        "valid-token-account-data.bin",
+    );
+    pc.add_account(
+        faucet_pubkey,
+        faucet_account(state::Faucet {
+            lamports_per_claim: Some(5_000_000),
+            ..initialized_faucet(None, VALID_MINT_PUBKEY, 10)
+        }),
+    );
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_pubkey);
+    pc.add_account(VALID_MINT_PUBKEY, mint_account(&pda));
+    let (sol_vault, _nonce) = find_sol_vault_address(&id(), &faucet_pubkey);
+    pc.add_account(
+        sol_vault,
+        Account::new(1_000_000_000, 0, &solana_program::system_program::id()),
+    );
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut mint_tokens_tx = Transaction::new_with_payer(
+        &[Instruction {
+            program_id: id(),
+            accounts: vec![
+                AccountMeta::new_readonly(pda, false),
+                AccountMeta::new(VALID_MINT_PUBKEY, false),
+                AccountMeta::new(token_account_address, false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+                AccountMeta::new(faucet_pubkey, false),
+                AccountMeta::new(sol_vault, false),
+                AccountMeta::new(impostor_recipient_pubkey, false),
+                AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            ],
+            data: vec![1, 5, 0, 0, 0, 0, 0, 0, 0],
+        }],
+        Some(&payer.pubkey()),
+    );
+
+    mint_tokens_tx.sign(&[&payer], recent_blockhash);
+
+    // WHEN THEN
+    let error = banks_client
+        .process_transaction(mint_tokens_tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        TransactionError::InstructionError(0, InstructionError::Custom(0x21)),
+        error
+    );
+}
+
+#[tokio::test]
+async fn test_happy_flow_mint_tokens_with_lamports_per_claim() {
+    // GIVEN
+    let token_account_address = VALID_TOKEN_ACCOUNT_PUBKEY;
+    let faucet_pubkey = Pubkey::new_unique();
+    let mut pc = program_test();
+    pc.add_account_with_file_data(
+        token_account_address,
+        1000000000,
+        spl_token::id(),
+        "valid-token-account-data.bin",
+    );
+    pc.add_account(
+        faucet_pubkey,
+        faucet_account(state::Faucet {
+            lamports_per_claim: Some(5_000_000),
+            ..initialized_faucet(None, VALID_MINT_PUBKEY, 10)
+        }),
+    );
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_pubkey);
+    pc.add_account(VALID_MINT_PUBKEY, mint_account(&pda));
+    let (sol_vault, _nonce) = find_sol_vault_address(&id(), &faucet_pubkey);
+    pc.add_account(
+        sol_vault,
+        Account::new(1_000_000_000, 0, &solana_program::system_program::id()),
+    );
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut mint_tokens_tx = Transaction::new_with_payer(
+        &[Instruction {
+            program_id: id(),
+            accounts: vec![
+                AccountMeta::new_readonly(pda, false),
+                AccountMeta::new(VALID_MINT_PUBKEY, false),
+                AccountMeta::new(token_account_address, false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+                AccountMeta::new(faucet_pubkey, false),
+                AccountMeta::new(sol_vault, false),
+                AccountMeta::new(TOKEN_ACCOUNT_OWNER_PUBKEY, false),
+                AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            ],
+            data: vec![1, 5, 0, 0, 0, 0, 0, 0, 0],
+        }],
+        Some(&payer.pubkey()),
+    );
+
+    mint_tokens_tx.sign(&[&payer], recent_blockhash);
+
+    // WHEN
+    banks_client
+        .process_transaction(mint_tokens_tx)
+        .await
+        .unwrap();
+
+    // THEN
+    assert_eq!(
+        5_000_000,
+        banks_client
+            .get_balance(TOKEN_ACCOUNT_OWNER_PUBKEY)
+            .await
+            .unwrap()
+    );
+}
+
+#[tokio::test]
+async fn test_happy_flow_mint_nothing_with_lamports_per_claim() {
+    // GIVEN
+    let token_account_address = VALID_TOKEN_ACCOUNT_PUBKEY;
+    let faucet_pubkey = Pubkey::new_unique();
+    let mut pc = program_test();
+    pc.add_account_with_file_data(
+        token_account_address,
+        1000000000,
+        spl_token::id(),
+        "valid-token-account-data.bin",
+    );
+    pc.add_account(
+        faucet_pubkey,
+        faucet_account(state::Faucet {
+            lamports_per_claim: Some(5_000_000),
+            ..initialized_faucet(None, VALID_MINT_PUBKEY, 10)
+        }),
+    );
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_pubkey);
+    pc.add_account(VALID_MINT_PUBKEY, mint_account(&pda));
+    let (sol_vault, _nonce) = find_sol_vault_address(&id(), &faucet_pubkey);
+    pc.add_account(
+        sol_vault,
+        Account::new(1_000_000_000, 0, &solana_program::system_program::id()),
+    );
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut mint_tokens_tx = Transaction::new_with_payer(
+        &[Instruction {
+            program_id: id(),
+            accounts: vec![
+                AccountMeta::new_readonly(pda, false),
+                AccountMeta::new(VALID_MINT_PUBKEY, false),
+                AccountMeta::new(token_account_address, false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+                AccountMeta::new(faucet_pubkey, false),
+                AccountMeta::new(sol_vault, false),
+                AccountMeta::new(TOKEN_ACCOUNT_OWNER_PUBKEY, false),
+                AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            ],
+            data: vec![1, 0, 0, 0, 0, 0, 0, 0, 0],
+        }],
+        Some(&payer.pubkey()),
+    );
+
+    mint_tokens_tx.sign(&[&payer], recent_blockhash);
+
+    // WHEN
+    banks_client
+        .process_transaction(mint_tokens_tx)
+        .await
+        .unwrap();
+
+    // THEN
+    assert_eq!(
+        0,
+        banks_client
+            .get_balance(TOKEN_ACCOUNT_OWNER_PUBKEY)
+            .await
+            .unwrap()
+    );
+}
+
+#[tokio::test]
+async fn test_happy_flow_fund_sol_vault() {
+    // GIVEN
+    let faucet_pubkey = Pubkey::new_unique();
+    let mut pc = program_test();
+    pc.add_account(
+        faucet_pubkey,
+        faucet_account(initialized_faucet(None, VALID_MINT_PUBKEY, 10)),
+    );
+    let (sol_vault, _nonce) = find_sol_vault_address(&id(), &faucet_pubkey);
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut fund_sol_vault_tx = Transaction::new_with_payer(
+        &[instruction::fund_sol_vault(
+            &id(),
+            &payer.pubkey(),
+            &faucet_pubkey,
+            1_000_000_000,
+        )],
+        Some(&payer.pubkey()),
+    );
+
+    fund_sol_vault_tx.sign(&[&payer], recent_blockhash);
+
+    // WHEN
+    banks_client
+        .process_transaction(fund_sol_vault_tx)
+        .await
+        .unwrap();
+
+    // THEN
+    assert_eq!(
+        1_000_000_000,
+        banks_client.get_balance(sol_vault).await.unwrap()
+    );
+}
+
+#[tokio::test]
+async fn test_withdraw_lamports_impostor_admin() {
+    // GIVEN
+    let admin_keypair = Keypair::new();
+    let impostor_admin_keypair = Keypair::new();
+    let faucet_pubkey = Pubkey::new_unique();
+    let mut pc = program_test();
+    pc.add_account(
+        faucet_pubkey,
+        faucet_account(initialized_faucet(
+            Some(admin_keypair.pubkey()),
+            VALID_MINT_PUBKEY,
+            10,
+        )),
+    );
+    let (sol_vault, _nonce) = find_sol_vault_address(&id(), &faucet_pubkey);
+    pc.add_account(
+        sol_vault,
+        Account::new(1_000_000_000, 0, &solana_program::system_program::id()),
+    );
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut withdraw_lamports_tx = Transaction::new_with_payer(
+        &[instruction::withdraw_lamports(
+            &id(),
+            &impostor_admin_keypair.pubkey(),
+            &faucet_pubkey,
+            &impostor_admin_keypair.pubkey(),
+            1_000_000_000,
+        )],
+        Some(&payer.pubkey()),
+    );
+
+    withdraw_lamports_tx.sign(&[&payer, &impostor_admin_keypair], recent_blockhash);
+
+    // WHEN THEN
+    let error = banks_client
+        .process_transaction(withdraw_lamports_tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        TransactionError::InstructionError(0, InstructionError::Custom(0x1e)),
+        error
+    );
+}
+
+#[tokio::test]
+async fn test_happy_flow_mint_to_owner_creates_associated_token_account() {
+    // GIVEN
+    let recipient_pubkey = Pubkey::new_unique();
+    let associated_token_account = spl_associated_token_account::get_associated_token_address(
+        &recipient_pubkey,
+        &VALID_MINT_PUBKEY,
+    );
+    let (mut banks_client, payer, recent_blockhash, faucet_pubkey) =
+        create_faucet(program_test(), None).await;
+
+    let mut mint_to_owner_tx = Transaction::new_with_payer(
+        &[instruction::mint_to_owner(
+            &id(),
+            &spl_token::id(),
+            &VALID_MINT_PUBKEY,
+            &recipient_pubkey,
+            &faucet_pubkey,
+            &payer.pubkey(),
+            5,
+        )],
+        Some(&payer.pubkey()),
+    );
+
+    mint_to_owner_tx.sign(&[&payer], recent_blockhash);
+
+    // WHEN
+    banks_client
+        .process_transaction(mint_to_owner_tx)
+        .await
+        .unwrap();
+
+    // THEN
+    let acc = banks_client
+        .get_account(associated_token_account)
+        .await
+        .unwrap()
+        .unwrap();
+    let token_account = spl_token::state::Account::unpack_from_slice(&acc.data).unwrap();
+    assert_eq!(recipient_pubkey, token_account.owner);
+    assert_eq!(5, token_account.amount);
+}
+
+#[tokio::test]
+async fn test_mint_to_owner_incorrect_associated_token_account() {
+    // GIVEN
+    let recipient_pubkey = Pubkey::new_unique();
+    let (mut banks_client, payer, recent_blockhash, faucet_pubkey) =
+        create_faucet(program_test(), None).await;
+
+    let mut mint_to_owner_ix = instruction::mint_to_owner(
+        &id(),
+        &spl_token::id(),
+        &VALID_MINT_PUBKEY,
+        &recipient_pubkey,
+        &faucet_pubkey,
+        &payer.pubkey(),
+        5,
+    );
+    mint_to_owner_ix.accounts[2] = AccountMeta::new(Pubkey::new_unique(), false);
+    let mut mint_to_owner_tx =
+        Transaction::new_with_payer(&[mint_to_owner_ix], Some(&payer.pubkey()));
+
+    mint_to_owner_tx.sign(&[&payer], recent_blockhash);
+
+    // WHEN THEN
+    let error = banks_client
+        .process_transaction(mint_to_owner_tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        TransactionError::InstructionError(0, InstructionError::Custom(0x22)),
+        error
+    );
+}
+
+#[tokio::test]
+async fn test_mint_to_owner_incorrect_associated_token_program() {
+    // GIVEN
+    let recipient_pubkey = Pubkey::new_unique();
+    let (mut banks_client, payer, recent_blockhash, faucet_pubkey) =
+        create_faucet(program_test(), None).await;
+    let mut mint_to_owner_ix = instruction::mint_to_owner(
+        &id(),
+        &spl_token::id(),
+        &VALID_MINT_PUBKEY,
+        &recipient_pubkey,
+        &faucet_pubkey,
+        &payer.pubkey(),
+        5,
+    );
+    mint_to_owner_ix.accounts[9] = AccountMeta::new_readonly(Pubkey::new_unique(), false);
+
+    let mut mint_to_owner_tx =
+        Transaction::new_with_payer(&[mint_to_owner_ix], Some(&payer.pubkey()));
+
+    mint_to_owner_tx.sign(&[&payer], recent_blockhash);
+
+    // WHEN THEN
+    let error = banks_client
+        .process_transaction(mint_to_owner_tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        TransactionError::InstructionError(0, InstructionError::Custom(0x23)),
+        error
+    );
+}
+
+#[tokio::test]
+async fn test_happy_flow_mint_to_owner_token_2022_existing_associated_token_account() {
+    // GIVEN
+    let recipient_pubkey = Pubkey::new_unique();
+    let faucet_pubkey = Pubkey::new_unique();
+    let associated_token_account = token::find_associated_token_address(
+        &recipient_pubkey,
+        &VALID_MINT_PUBKEY,
+        &token::spl_token_2022::id(),
+    );
+    let mut pc = token_2022_program_test();
+    pc.add_account(
+        faucet_pubkey,
+        faucet_account(initialized_faucet(None, VALID_MINT_PUBKEY, 10)),
+    );
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_pubkey);
+    pc.add_account(VALID_MINT_PUBKEY, token_2022_mint_account(&pda));
+    pc.add_account(
+        associated_token_account,
+        token_2022_token_account(&VALID_MINT_PUBKEY, &recipient_pubkey, 0),
+    );
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut mint_to_owner_tx = Transaction::new_with_payer(
+        &[instruction::mint_to_owner(
+            &id(),
+            &token::spl_token_2022::id(),
+            &VALID_MINT_PUBKEY,
+            &recipient_pubkey,
+            &faucet_pubkey,
+            &payer.pubkey(),
+            5,
+        )],
+        Some(&payer.pubkey()),
+    );
+
+    mint_to_owner_tx.sign(&[&payer], recent_blockhash);
+
+    // WHEN
+    banks_client
+        .process_transaction(mint_to_owner_tx)
+        .await
+        .unwrap();
+
+    // THEN
+    let acc = banks_client
+        .get_account(associated_token_account)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(5, token::unpack_account(&acc.data).unwrap().amount);
+}
+
+#[tokio::test]
+async fn test_mint_to_owner_token_2022_creates_associated_token_account() {
+    // GIVEN
+    let recipient_pubkey = Pubkey::new_unique();
+    let faucet_pubkey = Pubkey::new_unique();
+    let mut pc = token_2022_program_test();
+    pc.add_account(
+        faucet_pubkey,
+        faucet_account(initialized_faucet(None, VALID_MINT_PUBKEY, 10)),
+    );
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_pubkey);
+    pc.add_account(VALID_MINT_PUBKEY, token_2022_mint_account(&pda));
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut mint_to_owner_tx = Transaction::new_with_payer(
+        &[instruction::mint_to_owner(
+            &id(),
+            &token::spl_token_2022::id(),
+            &VALID_MINT_PUBKEY,
+            &recipient_pubkey,
+            &faucet_pubkey,
+            &payer.pubkey(),
+            5,
+        )],
+        Some(&payer.pubkey()),
+    );
+
+    mint_to_owner_tx.sign(&[&payer], recent_blockhash);
+
+    // WHEN THEN
+    let error = banks_client
+        .process_transaction(mint_to_owner_tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        TransactionError::InstructionError(0, InstructionError::Custom(0x2d)),
+        error
+    );
+}
+
+fn basket_account(faucets: Vec<Pubkey>) -> Account {
+    let mut account = Account::new(
+        Rent::default().minimum_balance(state::Basket::LEN),
+        state::Basket::LEN,
+        &id(),
+    );
+    state::Basket::pack(
+        state::Basket {
+            is_initialized: true,
+            creator: Pubkey::new_unique(),
+            faucets,
+        },
+        &mut account.data,
+    )
+    .unwrap();
+    account
+}
+
+/// Two faucets of different mints in one basket, each with a token account of the recipient,
+/// and the `MintTokens` accounts of both faucets
+fn basket_program_test() -> (ProgramTest, Pubkey, Vec<AccountMeta>) {
+    let mut pc = program_test();
+    let basket_pubkey = Pubkey::new_unique();
+    let mut faucets = vec![];
+    let mut accounts = vec![];
+    for (mint, amount) in &[(VALID_MINT_PUBKEY, 10), (SECOND_MINT_PUBKEY, 20)] {
+        let faucet_pubkey = Pubkey::new_unique();
+        let token_account_pubkey = Pubkey::new_unique();
+        let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_pubkey);
+        pc.add_account(
+            faucet_pubkey,
+            faucet_account(initialized_faucet(None, *mint, *amount)),
+        );
+        pc.add_account(*mint, mint_account(&pda));
+        pc.add_account(
+            token_account_pubkey,
+            token_account(mint, &TOKEN_ACCOUNT_OWNER_PUBKEY, 0),
+        );
+        faucets.push(faucet_pubkey);
+        accounts.extend(
+            instruction::mint_tokens(
+                &id(),
+                &spl_token::id(),
+                mint,
+                &token_account_pubkey,
+                &faucet_pubkey,
+                None,
+                *amount,
+            )
+            .accounts,
+        );
+    }
+    pc.add_account(basket_pubkey, basket_account(faucets));
+    (pc, basket_pubkey, accounts)
+}
+
+#[tokio::test]
+async fn test_happy_flow_init_basket() {
+    // GIVEN
+    let creator_keypair = Keypair::new();
+    let mut pc = program_test();
+    let basket_pubkey = Pubkey::new_unique();
+    let faucets = vec![Pubkey::new_unique(), Pubkey::new_unique()];
+    pc.add_account(
+        basket_pubkey,
+        Account::new(
+            Rent::default().minimum_balance(state::Basket::LEN),
+            state::Basket::LEN,
+            &id(),
+        ),
+    );
+    pc.add_account(
+        faucets[0],
+        faucet_account(initialized_faucet(None, VALID_MINT_PUBKEY, 10)),
+    );
+    pc.add_account(
+        faucets[1],
+        faucet_account(initialized_faucet(
+            Some(creator_keypair.pubkey()),
+            SECOND_MINT_PUBKEY,
+            20,
+        )),
+    );
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut init_basket_tx = Transaction::new_with_payer(
+        &[instruction::init_basket(
+            &id(),
+            &basket_pubkey,
+            &creator_keypair.pubkey(),
+            &faucets,
+        )],
+        Some(&payer.pubkey()),
+    );
+
+    init_basket_tx.sign(&[&payer, &creator_keypair], recent_blockhash);
+
+    // WHEN
+    banks_client
+        .process_transaction(init_basket_tx)
+        .await
+        .unwrap();
+
+    // THEN
+    let acc = banks_client
+        .get_account(basket_pubkey)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        state::Basket {
+            is_initialized: true,
+            creator: creator_keypair.pubkey(),
+            faucets,
+        },
+        state::Basket::unpack(&acc.data).unwrap()
+    );
+}
+
+#[tokio::test]
+async fn test_init_basket_non_admin_creator() {
+    // GIVEN
+    let creator_keypair = Keypair::new();
+    let mut pc = program_test();
+    let basket_pubkey = Pubkey::new_unique();
+    let faucet_pubkey = Pubkey::new_unique();
+    pc.add_account(
+        basket_pubkey,
+        Account::new(
+            Rent::default().minimum_balance(state::Basket::LEN),
+            state::Basket::LEN,
+            &id(),
+        ),
+    );
+    pc.add_account(
+        faucet_pubkey,
+        faucet_account(initialized_faucet(
+            Some(Pubkey::new_unique()),
+            VALID_MINT_PUBKEY,
+            10,
+        )),
+    );
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut init_basket_tx = Transaction::new_with_payer(
+        &[instruction::init_basket(
+            &id(),
+            &basket_pubkey,
+            &creator_keypair.pubkey(),
+            &[faucet_pubkey],
+        )],
+        Some(&payer.pubkey()),
+    );
+
+    init_basket_tx.sign(&[&payer, &creator_keypair], recent_blockhash);
+
+    // WHEN THEN
+    let error = banks_client
+        .process_transaction(init_basket_tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        TransactionError::InstructionError(0, InstructionError::Custom(0x2e)),
+        error
+    );
+}
+
+#[tokio::test]
+async fn test_init_basket_creator_didnt_sign() {
+    // GIVEN
+    let mut pc = program_test();
+    let basket_pubkey = Pubkey::new_unique();
+    let faucet_pubkey = Pubkey::new_unique();
+    pc.add_account(
+        basket_pubkey,
+        Account::new(
+            Rent::default().minimum_balance(state::Basket::LEN),
+            state::Basket::LEN,
+            &id(),
+        ),
+    );
+    pc.add_account(
+        faucet_pubkey,
+        faucet_account(initialized_faucet(None, VALID_MINT_PUBKEY, 10)),
+    );
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+    let mut init_basket_ix =
+        instruction::init_basket(&id(), &basket_pubkey, &payer.pubkey(), &[faucet_pubkey]);
+    init_basket_ix.accounts[1] = AccountMeta::new_readonly(Pubkey::new_unique(), false);
+
+    let mut init_basket_tx = Transaction::new_with_payer(&[init_basket_ix], Some(&payer.pubkey()));
+
+    init_basket_tx.sign(&[&payer], recent_blockhash);
+
+    // WHEN THEN
+    let error = banks_client
+        .process_transaction(init_basket_tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        TransactionError::InstructionError(0, InstructionError::Custom(0x11)),
+        error
+    );
+}
+
+#[tokio::test]
+async fn test_happy_flow_mint_basket() {
+    // GIVEN
+    let (pc, basket_pubkey, accounts) = basket_program_test();
+    let token_account_pubkeys = vec![accounts[2].pubkey, accounts[7].pubkey];
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut mint_basket_tx = Transaction::new_with_payer(
+        &[instruction::mint_basket(&id(), &basket_pubkey, accounts, None, vec![5, 20]).unwrap()],
+        Some(&payer.pubkey()),
+    );
+
+    mint_basket_tx.sign(&[&payer], recent_blockhash);
+
+    // WHEN
+    banks_client
+        .process_transaction(mint_basket_tx)
+        .await
+        .unwrap();
+
+    // THEN
+    for (token_account_pubkey, amount) in token_account_pubkeys.into_iter().zip(&[5, 20]) {
+        let acc = banks_client
+            .get_account(token_account_pubkey)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            *amount,
+            spl_token::state::Account::unpack_from_slice(&acc.data)
+                .unwrap()
+                .amount
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_happy_flow_mint_basket_records_slot() {
+    // GIVEN
+    let (pc, basket_pubkey, accounts) = basket_program_test();
+    let faucet_pubkeys = vec![accounts[4].pubkey, accounts[9].pubkey];
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut mint_basket_ix =
+        instruction::mint_basket(&id(), &basket_pubkey, accounts, None, vec![5, 20]).unwrap();
+    mint_basket_ix
+        .accounts
+        .push(AccountMeta::new_readonly(sysvar::clock::id(), false));
+    let mut mint_basket_tx = Transaction::new_with_payer(&[mint_basket_ix], Some(&payer.pubkey()));
+
+    mint_basket_tx.sign(&[&payer], recent_blockhash);
+
+    // WHEN
+    banks_client
+        .process_transaction(mint_basket_tx)
+        .await
+        .unwrap();
+
+    // THEN
+    for faucet_pubkey in faucet_pubkeys {
+        let acc = banks_client
+            .get_account(faucet_pubkey)
+            .await
+            .unwrap()
+            .unwrap();
+        let faucet = state::Faucet::unpack_versioned(&acc.data).unwrap();
+        assert_eq!(1, faucet.mint_count);
+        assert_ne!(0, faucet.last_mint_slot);
+    }
+}
+
+#[tokio::test]
+async fn test_mint_basket_too_many_trailing_accounts() {
+    // GIVEN
+    let (pc, basket_pubkey, accounts) = basket_program_test();
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut mint_basket_ix =
+        instruction::mint_basket(&id(), &basket_pubkey, accounts, None, vec![5, 20]).unwrap();
+    mint_basket_ix.accounts.extend(vec![
+        AccountMeta::new_readonly(Pubkey::new_unique(), false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(Pubkey::new_unique(), false),
+    ]);
+    let mut mint_basket_tx = Transaction::new_with_payer(&[mint_basket_ix], Some(&payer.pubkey()));
+
+    mint_basket_tx.sign(&[&payer], recent_blockhash);
+
+    // WHEN THEN
+    let error = banks_client
+        .process_transaction(mint_basket_tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        TransactionError::InstructionError(0, InstructionError::Custom(0x00)),
+        error
+    );
+}
+
+#[tokio::test]
+async fn test_mint_basket_requesting_too_many_tokens() {
+    // GIVEN
+    let (pc, basket_pubkey, accounts) = basket_program_test();
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut mint_basket_tx = Transaction::new_with_payer(
+        &[instruction::mint_basket(&id(), &basket_pubkey, accounts, None, vec![11, 20]).unwrap()],
+        Some(&payer.pubkey()),
+    );
+
+    mint_basket_tx.sign(&[&payer], recent_blockhash);
+
+    // WHEN THEN
+    let error = banks_client
+        .process_transaction(mint_basket_tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        TransactionError::InstructionError(0, InstructionError::Custom(0x04)),
+        error
+    );
+}
+
+#[tokio::test]
+async fn test_mint_basket_incorrect_faucet_order() {
+    // GIVEN
+    let (pc, basket_pubkey, mut accounts) = basket_program_test();
+    let second_faucet_accounts: Vec<_> = accounts.drain(5..).collect();
+    accounts.splice(0..0, second_faucet_accounts);
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut mint_basket_tx = Transaction::new_with_payer(
+        &[instruction::mint_basket(&id(), &basket_pubkey, accounts, None, vec![5, 5]).unwrap()],
+        Some(&payer.pubkey()),
+    );
+
+    mint_basket_tx.sign(&[&payer], recent_blockhash);
+
+    // WHEN THEN
+    let error = banks_client
+        .process_transaction(mint_basket_tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        TransactionError::InstructionError(0, InstructionError::Custom(0x24)),
+        error
+    );
+}
+
+/// A faucet of the admin and two destination accounts of its mint
+fn batch_mint_program_test(admin: &Pubkey) -> (ProgramTest, Pubkey, Vec<Pubkey>) {
+    let mut pc = program_test();
+    let faucet_pubkey = Pubkey::new_unique();
+    pc.add_account(
+        faucet_pubkey,
+        faucet_account(initialized_faucet(Some(*admin), VALID_MINT_PUBKEY, 10)),
+    );
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_pubkey);
+    pc.add_account(VALID_MINT_PUBKEY, mint_account(&pda));
+    let destinations = vec![Pubkey::new_unique(), Pubkey::new_unique()];
+    for destination in &destinations {
+        pc.add_account(
+            *destination,
+            token_account(&VALID_MINT_PUBKEY, &Pubkey::new_unique(), 0),
+        );
+    }
+    (pc, faucet_pubkey, destinations)
+}
+
+#[tokio::test]
+async fn test_happy_flow_batch_mint() {
+    // GIVEN
+    let admin = Keypair::new();
+    let (pc, faucet_pubkey, destinations) = batch_mint_program_test(&admin.pubkey());
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut batch_mint_tx = Transaction::new_with_payer(
+        &[instruction::batch_mint(
+            &id(),
+            &spl_token::id(),
+            &admin.pubkey(),
+            &faucet_pubkey,
+            &VALID_MINT_PUBKEY,
+            None,
+            vec![(destinations[0], 100), (destinations[1], 200)],
+        )
+        .unwrap()],
+        Some(&payer.pubkey()),
+    );
+
+    batch_mint_tx.sign(&[&payer, &admin], recent_blockhash);
+
+    // WHEN
+    banks_client
+        .process_transaction(batch_mint_tx)
+        .await
+        .unwrap();
+
+    // THEN
+    for (destination, amount) in destinations.into_iter().zip(&[100, 200]) {
+        let acc = banks_client
+            .get_account(destination)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            *amount,
+            spl_token::state::Account::unpack_from_slice(&acc.data)
+                .unwrap()
+                .amount
+        );
+    }
+    let acc = banks_client
+        .get_account(faucet_pubkey)
+        .await
+        .unwrap()
+        .unwrap();
+    let faucet = state::Faucet::unpack(&acc.data).unwrap();
+    assert_eq!(300, faucet.total_minted);
+    assert_eq!(2, faucet.mint_count);
+    assert_ne!(0, faucet.last_mint_slot);
+}
+
+#[tokio::test]
+async fn test_batch_mint_impostor_admin() {
+    // GIVEN
+    let impostor = Keypair::new();
+    let (pc, faucet_pubkey, destinations) = batch_mint_program_test(&Pubkey::new_unique());
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut batch_mint_tx = Transaction::new_with_payer(
+        &[instruction::batch_mint(
+            &id(),
+            &spl_token::id(),
+            &impostor.pubkey(),
+            &faucet_pubkey,
+            &VALID_MINT_PUBKEY,
+            None,
+            vec![(destinations[0], 100)],
+        )
+        .unwrap()],
+        Some(&payer.pubkey()),
+    );
+
+    batch_mint_tx.sign(&[&payer, &impostor], recent_blockhash);
+
+    // WHEN THEN
+    let error = banks_client
+        .process_transaction(batch_mint_tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        TransactionError::InstructionError(0, InstructionError::Custom(0x26)),
+        error
+    );
+}
+
+#[tokio::test]
+async fn test_batch_mint_destination_of_other_mint() {
+    // GIVEN
+    let admin = Keypair::new();
+    let (mut pc, faucet_pubkey, destinations) = batch_mint_program_test(&admin.pubkey());
+    let other_mint_destination = Pubkey::new_unique();
+    pc.add_account(
+        other_mint_destination,
+        token_account(&SECOND_MINT_PUBKEY, &Pubkey::new_unique(), 0),
+    );
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut batch_mint_tx = Transaction::new_with_payer(
+        &[instruction::batch_mint(
+            &id(),
+            &spl_token::id(),
+            &admin.pubkey(),
+            &faucet_pubkey,
+            &VALID_MINT_PUBKEY,
+            None,
+            vec![(destinations[0], 100), (other_mint_destination, 100)],
+        )
+        .unwrap()],
+        Some(&payer.pubkey()),
+    );
+
+    batch_mint_tx.sign(&[&payer, &admin], recent_blockhash);
+
+    // WHEN THEN
+    let error = banks_client
+        .process_transaction(batch_mint_tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        TransactionError::InstructionError(0, InstructionError::Custom(0x08)),
+        error
+    );
+    let acc = banks_client
+        .get_account(destinations[0])
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        0,
+        spl_token::state::Account::unpack_from_slice(&acc.data)
+            .unwrap()
+            .amount
+    );
+}
+
+#[tokio::test]
+async fn test_batch_mint_incorrect_destination_account() {
+    // GIVEN
+    let admin = Keypair::new();
+    let (pc, faucet_pubkey, destinations) = batch_mint_program_test(&admin.pubkey());
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut batch_mint_ix = instruction::batch_mint(
+        &id(),
+        &spl_token::id(),
+        &admin.pubkey(),
+        &faucet_pubkey,
+        &VALID_MINT_PUBKEY,
+        None,
+        vec![(destinations[0], 100)],
+    )
+    .unwrap();
+    batch_mint_ix.accounts[5] = AccountMeta::new(destinations[1], false);
+    let mut batch_mint_tx = Transaction::new_with_payer(&[batch_mint_ix], Some(&payer.pubkey()));
+
+    batch_mint_tx.sign(&[&payer, &admin], recent_blockhash);
+
+    // WHEN THEN
+    let error = banks_client
+        .process_transaction(batch_mint_tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        TransactionError::InstructionError(0, InstructionError::Custom(0x27)),
+        error
+    );
+}
+
+fn legacy_faucet_account(faucet: state::FaucetV1) -> Account {
+    let mut account = Account::new(
+        Rent::default().minimum_balance(state::FaucetV1::LEN),
+        state::FaucetV1::LEN,
+        &id(),
+    );
+    state::FaucetV1::pack(faucet, &mut account.data).unwrap();
+    account
+}
+
+#[tokio::test]
+async fn test_happy_flow_mint_tokens_records_stats() {
+    // GIVEN
+    let faucet_pubkey = Pubkey::new_unique();
+    let mut pc = program_test();
+    pc.add_account_with_file_data(
+        VALID_TOKEN_ACCOUNT_PUBKEY,
+        1000000000,
+        spl_token::id(),
+        "valid-token-account-data.bin",
+    );
+    pc.add_account(
+        faucet_pubkey,
+        faucet_account(state::Faucet {
+            recipient_limit: Some(100),
+            ..initialized_faucet(None, VALID_MINT_PUBKEY, 10)
+        }),
+    );
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_pubkey);
+    pc.add_account(VALID_MINT_PUBKEY, mint_account(&pda));
+    let (claim_record, _nonce) =
+        find_claim_record_address(&id(), &faucet_pubkey, &TOKEN_ACCOUNT_OWNER_PUBKEY);
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut mint_tokens_tx = Transaction::new_with_payer(
+        &[Instruction {
+            program_id: id(),
+            accounts: vec![
+                AccountMeta::new_readonly(pda, false),
+                AccountMeta::new(VALID_MINT_PUBKEY, false),
+                AccountMeta::new(VALID_TOKEN_ACCOUNT_PUBKEY, false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+                AccountMeta::new(faucet_pubkey, false),
+                AccountMeta::new(claim_record, false),
+                AccountMeta::new(payer.pubkey(), true),
+                AccountMeta::new_readonly(solana_program::system_program::id(), false),
+                AccountMeta::new_readonly(sysvar::clock::id(), false),
+                AccountMeta::new_readonly(sysvar::rent::id(), false),
+            ],
+            data: vec![1, 5, 0, 0, 0, 0, 0, 0, 0],
+        }],
+        Some(&payer.pubkey()),
+    );
+
+    mint_tokens_tx.sign(&[&payer], recent_blockhash);
+
+    // WHEN
+    banks_client
+        .process_transaction(mint_tokens_tx)
+        .await
+        .unwrap();
+
+    // THEN
+    let acc = banks_client
+        .get_account(faucet_pubkey)
+        .await
+        .unwrap()
+        .unwrap();
+    let faucet = state::Faucet::unpack(&acc.data).unwrap();
+    assert_eq!(5, faucet.total_minted);
+    assert_eq!(1, faucet.mint_count);
+    assert_eq!(1, faucet.unique_recipients);
+    assert_ne!(0, faucet.last_mint_slot);
+}
+
+#[tokio::test]
+async fn test_mint_tokens_legacy_faucet_requesting_too_many_tokens() {
+    // GIVEN
+    let faucet_pubkey = Pubkey::new_unique();
+    let mut pc = program_test();
+    pc.add_account_with_file_data(
+        VALID_TOKEN_ACCOUNT_PUBKEY,
+        1000000000,
+        spl_token::id(),
+        "valid-token-account-data.bin",
+    );
+    pc.add_account(
+        faucet_pubkey,
+        legacy_faucet_account(state::FaucetV1 {
+            is_initialized: true,
+            admin: COption::None,
+            mint: VALID_MINT_PUBKEY,
+            amount: 10,
+        }),
+    );
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_pubkey);
+    pc.add_account(VALID_MINT_PUBKEY, mint_account(&pda));
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut mint_tokens_tx = Transaction::new_with_payer(
+        &[Instruction {
+            program_id: id(),
+            accounts: vec![
+                AccountMeta::new_readonly(pda, false),
+                AccountMeta::new(VALID_MINT_PUBKEY, false),
+                AccountMeta::new(VALID_TOKEN_ACCOUNT_PUBKEY, false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+                AccountMeta::new(faucet_pubkey, false),
+            ],
+            data: vec![1, 11, 0, 0, 0, 0, 0, 0, 0],
+        }],
+        Some(&payer.pubkey()),
+    );
+
+    mint_tokens_tx.sign(&[&payer], recent_blockhash);
+
+    // WHEN THEN
+    let error = banks_client
+        .process_transaction(mint_tokens_tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        TransactionError::InstructionError(0, InstructionError::Custom(0x04)),
+        error
+    );
+}
+
+#[tokio::test]
+async fn test_mint_tokens_legacy_faucet_not_migrated() {
+    // GIVEN
+    let faucet_pubkey = Pubkey::new_unique();
+    let mut pc = program_test();
+    pc.add_account_with_file_data(
+        VALID_TOKEN_ACCOUNT_PUBKEY,
+        1000000000,
+        spl_token::id(),
+        "valid-token-account-data.bin",
+    );
+    pc.add_account(
+        faucet_pubkey,
+        legacy_faucet_account(state::FaucetV1 {
+            is_initialized: true,
+            admin: COption::None,
+            mint: VALID_MINT_PUBKEY,
+            amount: 10,
+        }),
+    );
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_pubkey);
+    pc.add_account(VALID_MINT_PUBKEY, mint_account(&pda));
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    // the first layout can't store the stats of the mint
+    let mut mint_tokens_tx = Transaction::new_with_payer(
+        &[instruction::mint_tokens(
+            &id(),
+            &spl_token::id(),
+            &VALID_MINT_PUBKEY,
+            &VALID_TOKEN_ACCOUNT_PUBKEY,
+            &faucet_pubkey,
+            None,
+            5,
+        )],
+        Some(&payer.pubkey()),
+    );
+
+    mint_tokens_tx.sign(&[&payer], recent_blockhash);
+
+    // WHEN THEN
+    let error = banks_client
+        .process_transaction(mint_tokens_tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        TransactionError::InstructionError(0, InstructionError::Custom(0x28)),
+        error
+    );
+}
+
+#[tokio::test]
+async fn test_update_config_legacy_faucet_not_migrated() {
+    // GIVEN
+    let admin_keypair = Keypair::new();
+    let faucet_pubkey = Pubkey::new_unique();
+    let mut pc = program_test();
+    pc.add_account(
+        faucet_pubkey,
+        legacy_faucet_account(state::FaucetV1 {
+            is_initialized: true,
+            admin: COption::Some(admin_keypair.pubkey()),
+            mint: VALID_MINT_PUBKEY,
+            amount: 10,
+        }),
+    );
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut update_config_tx = Transaction::new_with_payer(
+        &[Instruction {
+            program_id: id(),
+            accounts: vec![
+                AccountMeta::new_readonly(admin_keypair.pubkey(), true),
+                AccountMeta::new(faucet_pubkey, false),
+            ],
+            data: instruction::FaucetInstruction::UpdateConfig {
+                config: state::FaucetConfig {
+                    amount: 20,
+                    recipient_limit: Some(100),
+                    ..state::FaucetConfig::default()
+                },
+            }
+            .pack(),
+        }],
+        Some(&payer.pubkey()),
+    );
+
+    update_config_tx.sign(&[&payer, &admin_keypair], recent_blockhash);
+
+    // WHEN THEN
+    let error = banks_client
+        .process_transaction(update_config_tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        TransactionError::InstructionError(0, InstructionError::Custom(0x28)),
+        error
+    );
+}
+
+#[tokio::test]
+async fn test_happy_flow_update_config_legacy_faucet_amount() {
+    // GIVEN
+    let admin_keypair = Keypair::new();
+    let faucet_pubkey = Pubkey::new_unique();
+    let mut pc = program_test();
+    pc.add_account(
+        faucet_pubkey,
+        legacy_faucet_account(state::FaucetV1 {
+            is_initialized: true,
+            admin: COption::Some(admin_keypair.pubkey()),
+            mint: VALID_MINT_PUBKEY,
+            amount: 10,
+        }),
+    );
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut update_config_tx = Transaction::new_with_payer(
+        &[Instruction {
+            program_id: id(),
+            accounts: vec![
+                AccountMeta::new_readonly(admin_keypair.pubkey(), true),
+                AccountMeta::new(faucet_pubkey, false),
+            ],
+            data: instruction::FaucetInstruction::UpdateConfig {
+                config: state::FaucetConfig {
+                    amount: 20,
+                    ..state::FaucetConfig::default()
+                },
+            }
+            .pack(),
+        }],
+        Some(&payer.pubkey()),
+    );
+
+    update_config_tx.sign(&[&payer, &admin_keypair], recent_blockhash);
+
+    // WHEN
+    banks_client
+        .process_transaction(update_config_tx)
+        .await
+        .unwrap();
+
+    // THEN
+    let acc = banks_client
+        .get_account(faucet_pubkey)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(state::FaucetV1::LEN, acc.data.len());
+    assert_eq!(20, state::FaucetV1::unpack(&acc.data).unwrap().amount);
+}
+
+/// A faucet of the first layout of the admin, in an account of its length like the faucets
+/// created before the layout changed, and the mint held by the faucet's PDA
+fn migrate_faucet_program_test(admin: &Pubkey) -> (ProgramTest, Pubkey) {
+    let mut pc = program_test();
+    let faucet_pubkey = Pubkey::new_unique();
+    let mut account = Account::new(
+        Rent::default().minimum_balance(state::FaucetV1::LEN),
+        state::FaucetV1::LEN,
+        &id(),
+    );
+    state::FaucetV1::pack(
+        state::FaucetV1 {
+            is_initialized: true,
+            admin: COption::Some(*admin),
+            mint: VALID_MINT_PUBKEY,
+            amount: 10,
+        },
+        &mut account.data,
+    )
+    .unwrap();
+    pc.add_account(faucet_pubkey, account);
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_pubkey);
+    pc.add_account(VALID_MINT_PUBKEY, mint_account(&pda));
+    (pc, faucet_pubkey)
+}
+
+/// An uninitialized account of the current layout to migrate a faucet into
+fn new_faucet_account() -> Account {
+    Account::new(faucet_lamports(), state::Faucet::LEN, &id())
+}
+
+#[tokio::test]
+async fn test_happy_flow_migrate_faucet() {
+    // GIVEN
+    let admin_keypair = Keypair::new();
+    let new_faucet_keypair = Keypair::new();
+    let (pc, faucet_pubkey) = migrate_faucet_program_test(&admin_keypair.pubkey());
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    // the new account is created in the same transaction, nobody can take it in between
+    let mut migrate_faucet_tx = Transaction::new_with_payer(
+        &[
+            solana_sdk::system_instruction::create_account(
+                &payer.pubkey(),
+                &new_faucet_keypair.pubkey(),
+                faucet_lamports(),
+                state::Faucet::LEN as u64,
+                &id(),
+            ),
+            instruction::migrate_faucet(
+                &id(),
+                &spl_token::id(),
+                &faucet_pubkey,
+                &new_faucet_keypair.pubkey(),
+                &VALID_MINT_PUBKEY,
+                Some(&admin_keypair.pubkey()),
+            ),
+        ],
+        Some(&payer.pubkey()),
+    );
+
+    migrate_faucet_tx.sign(
+        &[&payer, &new_faucet_keypair, &admin_keypair],
+        recent_blockhash,
+    );
+
+    // WHEN
+    banks_client
+        .process_transaction(migrate_faucet_tx)
+        .await
+        .unwrap();
+
+    // THEN
+    assert_eq!(None, banks_client.get_account(faucet_pubkey).await.unwrap());
+    let acc = banks_client
+        .get_account(new_faucet_keypair.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        faucet_lamports() + Rent::default().minimum_balance(state::FaucetV1::LEN),
+        acc.lamports
+    );
+    assert_eq!(
+        initialized_faucet(Some(admin_keypair.pubkey()), VALID_MINT_PUBKEY, 10),
+        state::Faucet::unpack(&acc.data).unwrap()
+    );
+    let acc = banks_client
+        .get_account(VALID_MINT_PUBKEY)
+        .await
+        .unwrap()
+        .unwrap();
+    let (new_pda, _nonce) = find_mint_authority_address(&id(), &new_faucet_keypair.pubkey());
+    assert_eq!(
+        COption::Some(new_pda),
+        spl_token::state::Mint::unpack(&acc.data)
+            .unwrap()
+            .mint_authority
+    );
+}
+
+#[tokio::test]
+async fn test_migrate_faucet_new_account_of_first_layout_length() {
+    // GIVEN
+    let admin_keypair = Keypair::new();
+    let new_faucet_pubkey = Pubkey::new_unique();
+    let (mut pc, faucet_pubkey) = migrate_faucet_program_test(&admin_keypair.pubkey());
+    pc.add_account(
+        new_faucet_pubkey,
+        Account::new(faucet_lamports(), state::FaucetV1::LEN, &id()),
+    );
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut migrate_faucet_tx = Transaction::new_with_payer(
+        &[instruction::migrate_faucet(
+            &id(),
+            &spl_token::id(),
+            &faucet_pubkey,
+            &new_faucet_pubkey,
+            &VALID_MINT_PUBKEY,
+            Some(&admin_keypair.pubkey()),
+        )],
+        Some(&payer.pubkey()),
+    );
+
+    migrate_faucet_tx.sign(&[&payer, &admin_keypair], recent_blockhash);
+
+    // WHEN THEN
+    let error = banks_client
+        .process_transaction(migrate_faucet_tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        TransactionError::InstructionError(0, InstructionError::Custom(0x2c)),
+        error
+    );
+}
+
+#[tokio::test]
+async fn test_migrate_faucet_new_account_in_use() {
+    // GIVEN
+    let admin_keypair = Keypair::new();
+    let new_faucet_pubkey = Pubkey::new_unique();
+    let (mut pc, faucet_pubkey) = migrate_faucet_program_test(&admin_keypair.pubkey());
+    pc.add_account(
+        new_faucet_pubkey,
+        faucet_account(initialized_faucet(None, VALID_MINT_PUBKEY, 10)),
+    );
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut migrate_faucet_tx = Transaction::new_with_payer(
+        &[instruction::migrate_faucet(
+            &id(),
+            &spl_token::id(),
+            &faucet_pubkey,
+            &new_faucet_pubkey,
+            &VALID_MINT_PUBKEY,
+            Some(&admin_keypair.pubkey()),
+        )],
+        Some(&payer.pubkey()),
+    );
+
+    migrate_faucet_tx.sign(&[&payer, &admin_keypair], recent_blockhash);
+
+    // WHEN THEN
+    let error = banks_client
+        .process_transaction(migrate_faucet_tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        TransactionError::InstructionError(0, InstructionError::Custom(0x03)),
+        error
+    );
+}
+
+#[tokio::test]
+async fn test_migrate_faucet_impostor_admin() {
+    // GIVEN
+    let impostor = Keypair::new();
+    let new_faucet_pubkey = Pubkey::new_unique();
+    let (mut pc, faucet_pubkey) = migrate_faucet_program_test(&Pubkey::new_unique());
+    pc.add_account(new_faucet_pubkey, new_faucet_account());
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut migrate_faucet_tx = Transaction::new_with_payer(
+        &[instruction::migrate_faucet(
+            &id(),
+            &spl_token::id(),
+            &faucet_pubkey,
+            &new_faucet_pubkey,
+            &VALID_MINT_PUBKEY,
+            Some(&impostor.pubkey()),
+        )],
+        Some(&payer.pubkey()),
+    );
+
+    migrate_faucet_tx.sign(&[&payer, &impostor], recent_blockhash);
+
+    // WHEN THEN
+    let error = banks_client
+        .process_transaction(migrate_faucet_tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        TransactionError::InstructionError(0, InstructionError::Custom(0x0a)),
+        error
+    );
+}
+
+#[tokio::test]
+async fn test_migrate_faucet_already_migrated() {
+    // GIVEN
+    let admin_keypair = Keypair::new();
+    let new_faucet_pubkey = Pubkey::new_unique();
+    let (mut banks_client, payer, recent_blockhash, faucet_pubkey) =
+        create_faucet(program_test(), Some(admin_keypair.pubkey())).await;
+
+    let mut migrate_faucet_tx = Transaction::new_with_payer(
+        &[instruction::migrate_faucet(
+            &id(),
+            &spl_token::id(),
+            &faucet_pubkey,
+            &new_faucet_pubkey,
+            &VALID_MINT_PUBKEY,
+            Some(&admin_keypair.pubkey()),
+        )],
+        Some(&payer.pubkey()),
+    );
+
+    migrate_faucet_tx.sign(&[&payer, &admin_keypair], recent_blockhash);
+
+    // WHEN THEN
+    let error = banks_client
+        .process_transaction(migrate_faucet_tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        TransactionError::InstructionError(0, InstructionError::Custom(0x29)),
+        error
+    );
+}
+
+fn create_faucet_instruction(
+    payer: &Pubkey,
+    faucet: &Pubkey,
+    faucet_is_signer: bool,
+    label: Option<&[u8]>,
+) -> Instruction {
+    let mut instruction = instruction::create_faucet(
+        &id(),
+        payer,
+        &VALID_MINT_PUBKEY,
+        faucet,
+        None,
+        label,
+        state::FaucetConfig {
+            amount: 10,
+            ..state::FaucetConfig::default()
+        },
+    );
+    instruction.accounts[1].is_signer = faucet_is_signer;
+    instruction
+}
+
+#[tokio::test]
+async fn test_happy_flow_create_faucet() {
+    // GIVEN
+    let faucet_keypair = Keypair::new();
+    let mut pc = program_test();
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_keypair.pubkey());
+    pc.add_account(VALID_MINT_PUBKEY, mint_account(&pda));
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut create_faucet_tx = Transaction::new_with_payer(
+        &[create_faucet_instruction(
+            &payer.pubkey(),
+            &faucet_keypair.pubkey(),
+            true,
+            None,
+        )],
+        Some(&payer.pubkey()),
+    );
+
+    create_faucet_tx.sign(&[&payer, &faucet_keypair], recent_blockhash);
+
+    // WHEN
+    banks_client
+        .process_transaction(create_faucet_tx)
+        .await
+        .unwrap();
+
+    // THEN
+    let acc = banks_client
+        .get_account(faucet_keypair.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(id(), acc.owner);
+    assert_eq!(faucet_lamports(), acc.lamports);
+    assert_eq!(
+        initialized_faucet(None, VALID_MINT_PUBKEY, 10),
+        state::Faucet::unpack(&acc.data).unwrap()
+    );
+}
+
+#[tokio::test]
+async fn test_create_faucet_faucet_not_signer() {
+    // GIVEN
+    let faucet_pubkey = Pubkey::new_unique();
+    let mut pc = program_test();
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_pubkey);
+    pc.add_account(VALID_MINT_PUBKEY, mint_account(&pda));
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut create_faucet_tx = Transaction::new_with_payer(
+        &[create_faucet_instruction(
+            &payer.pubkey(),
+            &faucet_pubkey,
+            false,
+            None,
+        )],
+        Some(&payer.pubkey()),
+    );
+
+    create_faucet_tx.sign(&[&payer], recent_blockhash);
+
+    // WHEN THEN
+    let error = banks_client
+        .process_transaction(create_faucet_tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        TransactionError::InstructionError(0, InstructionError::Custom(0x11)),
+        error
+    );
+}
+
+#[tokio::test]
+async fn test_happy_flow_create_faucet_with_label() {
+    // GIVEN
+    let (faucet_pubkey, _nonce) = find_faucet_address(&id(), &VALID_MINT_PUBKEY, b"devnet");
+    let mut pc = program_test();
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_pubkey);
+    pc.add_account(VALID_MINT_PUBKEY, mint_account(&pda));
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut create_faucet_tx = Transaction::new_with_payer(
+        &[create_faucet_instruction(
+            &payer.pubkey(),
+            &faucet_pubkey,
+            false,
+            Some(b"devnet"),
+        )],
+        Some(&payer.pubkey()),
+    );
+
+    create_faucet_tx.sign(&[&payer], recent_blockhash);
+
+    // WHEN
+    banks_client
+        .process_transaction(create_faucet_tx)
+        .await
+        .unwrap();
+
+    // THEN
+    let acc = banks_client
+        .get_account(faucet_pubkey)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        initialized_faucet(None, VALID_MINT_PUBKEY, 10),
+        state::Faucet::unpack(&acc.data).unwrap()
+    );
+}
+
+#[tokio::test]
+async fn test_create_faucet_incorrect_faucet_address() {
+    // GIVEN
+    let (faucet_pubkey, _nonce) = find_faucet_address(&id(), &VALID_MINT_PUBKEY, b"devnet");
+    let mut pc = program_test();
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_pubkey);
+    pc.add_account(VALID_MINT_PUBKEY, mint_account(&pda));
+    let (mut banks_client, payer, recent_blockhash) = pc.start().await;
+
+    let mut create_faucet_tx = Transaction::new_with_payer(
+        &[create_faucet_instruction(
+            &payer.pubkey(),
+            &faucet_pubkey,
+            false,
+            Some(b"testnet"),
+        )],
+        Some(&payer.pubkey()),
+    );
+
+    create_faucet_tx.sign(&[&payer], recent_blockhash);
+
+    // WHEN THEN
+    let error = banks_client
+        .process_transaction(create_faucet_tx)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        TransactionError::InstructionError(0, InstructionError::Custom(0x2a)),
+        error
+    );
+}
+
+/// A faucet that mints at most 10 tokens at once, and the associated token account of `recipient`
+fn client_program_test(recipient: &Pubkey) -> (ProgramTest, Pubkey) {
+    let mut pc = program_test();
+    let faucet_pubkey = Pubkey::new_unique();
+    pc.add_account(
+        faucet_pubkey,
+        faucet_account(initialized_faucet(None, VALID_MINT_PUBKEY, 10)),
+    );
+    let (pda, _nonce) = find_mint_authority_address(&id(), &faucet_pubkey);
+    pc.add_account(VALID_MINT_PUBKEY, mint_account(&pda));
+    pc.add_account(
+        token::find_associated_token_address(recipient, &VALID_MINT_PUBKEY, &spl_token::id()),
+        token_account(&VALID_MINT_PUBKEY, recipient, 0),
+    );
+    (pc, faucet_pubkey)
+}
+
+#[tokio::test]
+async fn test_client_get_faucet() {
+    // GIVEN
+    let recipient = Pubkey::new_unique();
+    let (pc, faucet_pubkey) = client_program_test(&recipient);
+    let (banks_client, _payer, _recent_blockhash) = pc.start().await;
+    let mut client = client::FaucetClient::new(banks_client);
+
+    // WHEN
+    let faucet = client.get_faucet(&faucet_pubkey).await.unwrap();
+
+    // THEN
+    assert_eq!(faucet, initialized_faucet(None, VALID_MINT_PUBKEY, 10));
+    assert_eq!(
+        client.mint_authority(&faucet_pubkey),
+        find_mint_authority_address(&id(), &faucet_pubkey).0
+    );
+    assert!(matches!(
+        client.get_faucet(&VALID_MINT_PUBKEY).await,
+        Err(client::ClientError::NotAFaucet(address)) if address == VALID_MINT_PUBKEY
+    ));
+}
+
+#[tokio::test]
+async fn test_client_mint_tokens_instructions_create_destination() {
+    // GIVEN
+    let recipient = Pubkey::new_unique();
+    let (pc, faucet_pubkey) = client_program_test(&Pubkey::new_unique());
+    let (banks_client, payer, _recent_blockhash) = pc.start().await;
+    let mut client = client::FaucetClient::new(banks_client);
+
+    // WHEN
+    let instructions = client
+        .mint_tokens_instructions(&faucet_pubkey, &recipient, &payer.pubkey(), None, 5)
+        .await
+        .unwrap();
+
+    // THEN
+    assert_eq!(
+        instructions,
+        vec![
+            token::create_associated_token_account(
+                &payer.pubkey(),
+                &recipient,
+                &VALID_MINT_PUBKEY,
+                &spl_token::id()
+            ),
+            instruction::mint_tokens(
+                &id(),
+                &spl_token::id(),
+                &VALID_MINT_PUBKEY,
+                &token::find_associated_token_address(
+                    &recipient,
+                    &VALID_MINT_PUBKEY,
+                    &spl_token::id()
+                ),
+                &faucet_pubkey,
+                None,
+                5
+            ),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_client_happy_flow_mint_tokens() {
+    // GIVEN
+    let recipient = Pubkey::new_unique();
+    let (pc, faucet_pubkey) = client_program_test(&recipient);
+    let (banks_client, payer, _recent_blockhash) = pc.start().await;
+    let mut client = client::FaucetClient::new(banks_client);
+
+    // WHEN
+    client
+        .mint_tokens(&payer, &faucet_pubkey, &recipient, None, 5)
+        .await
+        .unwrap();
+
+    // THEN
+    let acc = client
+        .transport()
+        .get_account(token::find_associated_token_address(
+            &recipient,
+            &VALID_MINT_PUBKEY,
+            &spl_token::id(),
+        ))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        5,
+        spl_token::state::Account::unpack_from_slice(&acc.data)
+            .unwrap()
+            .amount
+    );
+}
+
+#[tokio::test]
+async fn test_client_mint_too_many_tokens() {
+    // GIVEN
+    let recipient = Pubkey::new_unique();
+    let (pc, faucet_pubkey) = client_program_test(&recipient);
+    let (banks_client, payer, _recent_blockhash) = pc.start().await;
+    let mut client = client::FaucetClient::new(banks_client);
+
+    // WHEN
+    let result = client
+        .mint_tokens(&payer, &faucet_pubkey, &recipient, None, 11)
+        .await;
+
+    // THEN
+    assert!(matches!(
+        result,
+        Err(client::ClientError::Faucet(
+            error::FaucetError::RequestingTooManyTokens
+        ))
+    ));
+}
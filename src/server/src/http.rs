@@ -0,0 +1,143 @@
+//! Just enough HTTP/1.1 for the claim endpoint: one request per connection, bodies with a `Content-Length`
+
+use thiserror::Error;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+/// Requests with longer heads or bodies are rejected
+const MAX_HEAD_LEN: usize = 8 * 1024;
+const MAX_BODY_LEN: usize = 4 * 1024;
+const MAX_HEADERS: usize = 32;
+
+#[derive(Error, Debug)]
+pub enum HttpError {
+    /// Malformed request
+    #[error("Malformed request: {0}")]
+    Malformed(String),
+    /// Request too large
+    #[error("Request too large")]
+    TooLarge,
+    /// Io error
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub body: Vec<u8>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+impl Response {
+    pub fn json(status: u16, body: &serde_json::Value) -> Self {
+        Response {
+            status,
+            body: body.to_string(),
+        }
+    }
+
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            204 => "No Content",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            408 => "Request Timeout",
+            413 => "Payload Too Large",
+            429 => "Too Many Requests",
+            500 => "Internal Server Error",
+            502 => "Bad Gateway",
+            _ => "",
+        }
+    }
+}
+
+/// Reads a request off the stream, `None` if the peer closed the connection before sending one
+pub async fn read_request<S: AsyncRead + Unpin>(
+    stream: &mut S,
+) -> Result<Option<Request>, HttpError> {
+    let mut buf = Vec::with_capacity(1024);
+    let mut chunk = [0; 1024];
+    loop {
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            if buf.is_empty() {
+                return Ok(None);
+            }
+            return Err(HttpError::Malformed(
+                "unexpected end of request".to_string(),
+            ));
+        }
+        buf.extend_from_slice(&chunk[..read]);
+
+        let mut headers = [httparse::EMPTY_HEADER; MAX_HEADERS];
+        let mut request = httparse::Request::new(&mut headers);
+        let head_len = match request
+            .parse(&buf)
+            .map_err(|err| HttpError::Malformed(err.to_string()))?
+        {
+            httparse::Status::Complete(head_len) => head_len,
+            httparse::Status::Partial if buf.len() > MAX_HEAD_LEN => {
+                return Err(HttpError::TooLarge)
+            }
+            httparse::Status::Partial => continue,
+        };
+
+        let content_length = match request
+            .headers
+            .iter()
+            .find(|header| header.name.eq_ignore_ascii_case("content-length"))
+        {
+            Some(header) => std::str::from_utf8(header.value)
+                .ok()
+                .and_then(|value| value.trim().parse::<usize>().ok())
+                .ok_or_else(|| HttpError::Malformed("invalid Content-Length".to_string()))?,
+            None => 0,
+        };
+        if content_length > MAX_BODY_LEN {
+            return Err(HttpError::TooLarge);
+        }
+        let method = request.method.unwrap_or_default().to_string();
+        let path = request.path.unwrap_or_default().to_string();
+
+        let mut body = buf.split_off(head_len);
+        if body.len() < content_length {
+            let mut rest = vec![0; content_length - body.len()];
+            stream.read_exact(&mut rest).await?;
+            body.extend_from_slice(&rest);
+        }
+        body.truncate(content_length);
+        return Ok(Some(Request { method, path, body }));
+    }
+}
+
+/// Writes the response and closes the connection.
+/// Any origin may call the server, the faucet is meant to be used from browsers.
+pub async fn write_response<S: AsyncWrite + Unpin>(
+    stream: &mut S,
+    response: &Response,
+) -> Result<(), HttpError> {
+    let head = format!(
+        "HTTP/1.1 {} {}\r\n\
+         Content-Type: application/json\r\n\
+         Content-Length: {}\r\n\
+         Access-Control-Allow-Origin: *\r\n\
+         Access-Control-Allow-Methods: POST, OPTIONS\r\n\
+         Access-Control-Allow-Headers: Content-Type\r\n\
+         Connection: close\r\n\r\n",
+        response.status,
+        response.reason(),
+        response.body.len()
+    );
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(response.body.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
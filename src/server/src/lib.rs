@@ -0,0 +1,215 @@
+//! HTTP server that mints from faucets on behalf of wallets, paying the fees itself.
+//!
+//! `POST /claim {"mint": ..., "wallet": ...}` mints the faucet's amount to the associated token account of the wallet,
+//! creating the account first if needed, and responds with `{"signature": ...}`.
+//! Errors are responded to with `{"error": ...}`.
+
+pub mod http;
+pub mod rate_limit;
+
+use crate::{
+    http::{read_request, write_response, HttpError, Request, Response},
+    rate_limit::{RateLimitError, RateLimiter},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signature},
+};
+use spl_token_faucet::client::{ClientError, FaucetClient, FaucetTransport};
+use std::{
+    collections::HashMap,
+    io,
+    net::IpAddr,
+    str::FromStr,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use thiserror::Error;
+use tokio::{net::TcpListener, sync::Semaphore, time::timeout};
+
+/// Connections that don't send a whole request in time are dropped
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Error, Debug)]
+pub enum ServerError {
+    /// Invalid request
+    #[error("Invalid request: {0}")]
+    InvalidRequest(String),
+    /// The server has no faucet for the mint
+    #[error("No faucet for mint {0}")]
+    UnknownMint(Pubkey),
+    /// Rate limited
+    #[error(transparent)]
+    RateLimit(#[from] RateLimitError),
+    /// The claim failed
+    #[error(transparent)]
+    Client(#[from] ClientError),
+}
+
+impl ServerError {
+    /// The HTTP status of the response to a request failing with this error
+    pub fn status(&self) -> u16 {
+        match self {
+            ServerError::InvalidRequest(_) => 400,
+            ServerError::UnknownMint(_) => 404,
+            ServerError::RateLimit(RateLimitError::Store(_)) => 500,
+            ServerError::RateLimit(_) => 429,
+            // the faucet itself refused, e.g. because it's paused or the wallet's cooldown hasn't elapsed
+            ServerError::Client(ClientError::Faucet(_)) => 400,
+            ServerError::Client(_) => 502,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct ClaimRequest {
+    pub mint: String,
+    pub wallet: String,
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct ClaimResponse {
+    pub signature: String,
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs())
+}
+
+fn parse_pubkey(name: &str, value: &str) -> Result<Pubkey, ServerError> {
+    Pubkey::from_str(value)
+        .map_err(|_| ServerError::InvalidRequest(format!("invalid {} {}", name, value)))
+}
+
+/// Mints from a fixed set of faucets, one per mint
+pub struct Server<T> {
+    // cloned for every claim, so claims don't wait on each other's round trips
+    client: FaucetClient<T>,
+    fee_payer: Keypair,
+    faucets: HashMap<Pubkey, Pubkey>,
+    rate_limiter: RateLimiter,
+}
+
+impl<T: FaucetTransport + Clone + Send + Sync + 'static> Server<T> {
+    /// Fetches the faucets to learn their mints, fails if any of them isn't a faucet
+    pub async fn new(
+        mut client: FaucetClient<T>,
+        fee_payer: Keypair,
+        faucets: &[Pubkey],
+        rate_limiter: RateLimiter,
+    ) -> Result<Self, ClientError> {
+        let mut faucets_by_mint = HashMap::new();
+        for faucet in faucets {
+            let mint = client.get_faucet(faucet).await?.mint;
+            faucets_by_mint.insert(mint, *faucet);
+        }
+        Ok(Server {
+            client,
+            fee_payer,
+            faucets: faucets_by_mint,
+            rate_limiter,
+        })
+    }
+
+    /// The faucet the server mints `mint` from
+    pub fn faucet(&self, mint: &Pubkey) -> Option<&Pubkey> {
+        self.faucets.get(mint)
+    }
+
+    /// Mints the faucet's amount of `mint` to `wallet`, if neither the IP nor the wallet are over their limits
+    pub async fn claim(
+        &self,
+        ip: IpAddr,
+        request: &ClaimRequest,
+    ) -> Result<Signature, ServerError> {
+        let mint = parse_pubkey("mint", &request.mint)?;
+        let wallet = parse_pubkey("wallet", &request.wallet)?;
+        let faucet_address = *self.faucet(&mint).ok_or(ServerError::UnknownMint(mint))?;
+        let wallet_key = wallet.to_string();
+
+        // the claim counts from the start, so concurrent claims can't all pass the limits
+        let reserved_at = now();
+        self.rate_limiter
+            .reserve(&ip, &wallet_key, reserved_at)
+            .await?;
+        let mut client = self.client.clone();
+        let result = async {
+            let amount = client.get_faucet(&faucet_address).await?.amount;
+            client
+                .mint_tokens(&self.fee_payer, &faucet_address, &wallet, None, amount)
+                .await
+        }
+        .await;
+        // a transport error may hide a mint that went through, only claims that certainly failed are taken back
+        if let Err(err) = &result {
+            if !matches!(err, ClientError::Transport(_)) {
+                self.rate_limiter
+                    .release(&ip, &wallet_key, reserved_at, now())
+                    .await?;
+            }
+        }
+        Ok(result?)
+    }
+
+    /// Routes a request from `ip`
+    pub async fn handle(&self, ip: IpAddr, request: &Request) -> Response {
+        if request.method == "OPTIONS" {
+            return Response {
+                status: 204,
+                body: String::new(),
+            };
+        }
+        if request.path != "/claim" {
+            return Response::json(404, &json!({ "error": "Not found" }));
+        }
+        if request.method != "POST" {
+            return Response::json(405, &json!({ "error": "Method not allowed" }));
+        }
+        let result = match serde_json::from_slice::<ClaimRequest>(&request.body) {
+            Ok(claim) => self.claim(ip, &claim).await,
+            Err(err) => Err(ServerError::InvalidRequest(err.to_string())),
+        };
+        match result {
+            Ok(signature) => Response::json(
+                200,
+                &json!(ClaimResponse {
+                    signature: signature.to_string()
+                }),
+            ),
+            Err(err) => Response::json(err.status(), &json!({ "error": err.to_string() })),
+        }
+    }
+
+    /// Serves requests until accepting connections fails, at most `max_connections` at a time
+    pub async fn run(
+        self: Arc<Self>,
+        listener: TcpListener,
+        max_connections: usize,
+    ) -> io::Result<()> {
+        let connections = Arc::new(Semaphore::new(max_connections));
+        loop {
+            // further connections wait in the listen backlog until one is done
+            let permit = connections.clone().acquire_owned().await;
+            let (mut stream, peer) = listener.accept().await?;
+            let server = self.clone();
+            tokio::spawn(async move {
+                let _permit = permit;
+                let response = match timeout(REQUEST_TIMEOUT, read_request(&mut stream)).await {
+                    Err(_) => Response::json(408, &json!({ "error": "Request timeout" })),
+                    Ok(Ok(Some(request))) => server.handle(peer.ip(), &request).await,
+                    Ok(Ok(None)) | Ok(Err(HttpError::Io(_))) => return,
+                    Ok(Err(err @ HttpError::TooLarge)) => {
+                        Response::json(413, &json!({ "error": err.to_string() }))
+                    }
+                    Ok(Err(err)) => Response::json(400, &json!({ "error": err.to_string() })),
+                };
+                // the peer may be gone already, there's no one left to tell
+                let _ = write_response(&mut stream, &response).await;
+            });
+        }
+    }
+}
@@ -0,0 +1,216 @@
+use clap::{crate_description, crate_name, crate_version, App, Arg, ArgMatches};
+use solana_clap_utils::{
+    input_parsers::{pubkey_of, pubkeys_of, value_of},
+    input_validators::{is_keypair, is_parsable, is_url, is_valid_pubkey},
+};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::signature::{read_keypair_file, Signer};
+use spl_token_faucet::client::{cluster::ClusterProfile, FaucetClient, RpcTransport};
+use spl_token_faucet_server::{
+    rate_limit::{RateLimit, RateLimiter},
+    Server,
+};
+use std::{process::exit, sync::Arc};
+use tokio::net::TcpListener;
+
+/// The rate limit given by the `limit` and `window` arguments, if any
+fn rate_limit_of(matches: &ArgMatches<'_>, limit: &str, window: &str) -> Option<RateLimit> {
+    value_of(matches, limit).map(|max_claims| RateLimit {
+        max_claims,
+        window_seconds: value_of(matches, window).unwrap(),
+    })
+}
+
+#[tokio::main]
+async fn main() {
+    let matches = App::new(crate_name!())
+        .about(crate_description!())
+        .version(crate_version!())
+        .arg({
+            let arg = Arg::with_name("config_file")
+                .short("C")
+                .long("config")
+                .value_name("PATH")
+                .takes_value(true)
+                .help("Configuration file to use");
+            if let Some(ref config_file) = *solana_cli_config::CONFIG_FILE {
+                arg.default_value(&config_file)
+            } else {
+                arg
+            }
+        })
+        .arg(
+            Arg::with_name("json_rpc_url")
+                .short("u")
+                .long("url")
+                .value_name("URL")
+                .takes_value(true)
+                .validator(is_url)
+                .help("JSON RPC URL for the cluster [default: value from the cluster profile or configuration file]"),
+        )
+        .arg(
+            Arg::with_name("cluster")
+                .long("cluster")
+                .value_name("CLUSTER")
+                .takes_value(true)
+                .help("Cluster profile to use, devnet, testnet, mainnet-beta or the path of a cluster env file"),
+        )
+        .arg(
+            Arg::with_name("keypair")
+                .long("keypair")
+                .value_name("KEYPAIR")
+                .takes_value(true)
+                .validator(is_keypair)
+                .help("Fee payer of the claims [default: value from configuration file]"),
+        )
+        .arg(
+            Arg::with_name("program_id")
+                .long("program-id")
+                .value_name("PROGRAM_ID")
+                .takes_value(true)
+                .validator(is_valid_pubkey)
+                .help("The faucet program [default: value from the cluster profile or the program id the crate was built with]"),
+        )
+        .arg(
+            Arg::with_name("faucet")
+                .long("faucet")
+                .value_name("FAUCET_ADDRESS")
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1)
+                .required(true)
+                .validator(is_valid_pubkey)
+                .help("Faucet to mint from, at most one per mint"),
+        )
+        .arg(
+            Arg::with_name("bind")
+                .long("bind")
+                .value_name("HOST:PORT")
+                .takes_value(true)
+                .default_value("127.0.0.1:8080")
+                .help("Address to listen on"),
+        )
+        .arg(
+            Arg::with_name("max_connections")
+                .long("max-connections")
+                .value_name("CONNECTIONS")
+                .takes_value(true)
+                .validator(is_parsable::<usize>)
+                .default_value("256")
+                .help("Connections served at a time, further ones wait until one is done"),
+        )
+        .arg(
+            Arg::with_name("db")
+                .long("db")
+                .value_name("PATH")
+                .takes_value(true)
+                .default_value("faucet-server-db")
+                .help("Directory of the store the rate limits are kept in"),
+        )
+        .arg(
+            Arg::with_name("ip_limit")
+                .long("ip-limit")
+                .value_name("CLAIMS")
+                .takes_value(true)
+                .validator(is_parsable::<u32>)
+                .help("Claims allowed per IP and window [default: unlimited]"),
+        )
+        .arg(
+            Arg::with_name("ip_window")
+                .long("ip-window")
+                .value_name("SECONDS")
+                .takes_value(true)
+                .validator(is_parsable::<u64>)
+                .default_value("86400")
+                .help("Length of the window claims per IP are counted in"),
+        )
+        .arg(
+            Arg::with_name("wallet_limit")
+                .long("wallet-limit")
+                .value_name("CLAIMS")
+                .takes_value(true)
+                .validator(is_parsable::<u32>)
+                .help("Claims allowed per wallet and window [default: unlimited]"),
+        )
+        .arg(
+            Arg::with_name("wallet_window")
+                .long("wallet-window")
+                .value_name("SECONDS")
+                .takes_value(true)
+                .validator(is_parsable::<u64>)
+                .default_value("86400")
+                .help("Length of the window claims per wallet are counted in"),
+        )
+        .get_matches();
+
+    let cli_config = if let Some(config_file) = matches.value_of("config_file") {
+        solana_cli_config::Config::load(config_file).unwrap_or_default()
+    } else {
+        solana_cli_config::Config::default()
+    };
+    let keypair_path = matches
+        .value_of("keypair")
+        .unwrap_or(&cli_config.keypair_path);
+    let fee_payer = read_keypair_file(keypair_path).unwrap_or_else(|err| {
+        eprintln!("error: failed to read keypair {}: {}", keypair_path, err);
+        exit(1);
+    });
+    let client = if let Some(name) = matches.value_of("cluster") {
+        let mut cluster = ClusterProfile::from_name(name).unwrap_or_else(|err| {
+            eprintln!("error: {}", err);
+            exit(1);
+        });
+        if let Some(json_rpc_url) = matches.value_of("json_rpc_url") {
+            cluster.json_rpc_url = json_rpc_url.to_string();
+        }
+        if let Some(program_id) = pubkey_of(&matches, "program_id") {
+            cluster.program_id = program_id;
+        }
+        FaucetClient::from_cluster(&cluster)
+    } else {
+        let json_rpc_url = matches
+            .value_of("json_rpc_url")
+            .map(str::to_string)
+            .unwrap_or(cli_config.json_rpc_url);
+        FaucetClient::with_program_id(
+            RpcTransport::new(RpcClient::new(json_rpc_url)),
+            pubkey_of(&matches, "program_id").unwrap_or_else(spl_token_faucet::id),
+        )
+    };
+
+    let rate_limiter = RateLimiter::open(
+        matches.value_of("db").unwrap(),
+        rate_limit_of(&matches, "ip_limit", "ip_window"),
+        rate_limit_of(&matches, "wallet_limit", "wallet_window"),
+    )
+    .unwrap_or_else(|err| {
+        eprintln!("error: failed to open the store: {}", err);
+        exit(1);
+    });
+    println!("Fee payer: {}", fee_payer.pubkey());
+    let server = Server::new(
+        client,
+        fee_payer,
+        &pubkeys_of(&matches, "faucet").unwrap(),
+        rate_limiter,
+    )
+    .await
+    .unwrap_or_else(|err| {
+        eprintln!("error: {}", err);
+        exit(1);
+    });
+
+    let bind = matches.value_of("bind").unwrap();
+    let listener = TcpListener::bind(bind).await.unwrap_or_else(|err| {
+        eprintln!("error: failed to listen on {}: {}", bind, err);
+        exit(1);
+    });
+    println!("Listening on {}", bind);
+    if let Err(err) = Arc::new(server)
+        .run(listener, value_of(&matches, "max_connections").unwrap())
+        .await
+    {
+        eprintln!("error: {}", err);
+        exit(1);
+    }
+}
@@ -0,0 +1,315 @@
+//! Per-IP and per-wallet rate limits, persisted in a sled database so they survive restarts
+
+use std::{net::IpAddr, path::Path, sync::Mutex};
+use thiserror::Error;
+
+/// The sled tree claims are counted in
+const CLAIMS_TREE: &str = "claims";
+
+#[derive(Error, Debug)]
+pub enum RateLimitError {
+    /// Too many claims from the same IP
+    #[error("Too many claims from {0}, try again in {1} seconds")]
+    IpLimited(IpAddr, u64),
+    /// Too many claims to the same wallet
+    #[error("Too many claims to {0}, try again in {1} seconds")]
+    WalletLimited(String, u64),
+    /// Store error
+    #[error(transparent)]
+    Store(#[from] sled::Error),
+}
+
+/// At most `max_claims` claims per `window_seconds`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RateLimit {
+    pub max_claims: u32,
+    pub window_seconds: u64,
+}
+
+/// The claims of one IP or wallet in the current window
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct Window {
+    start: u64,
+    claims: u32,
+}
+
+impl Window {
+    const LEN: usize = 12;
+
+    fn unpack(data: &[u8]) -> Self {
+        if data.len() != Self::LEN {
+            return Window::default();
+        }
+        let mut start = [0; 8];
+        start.copy_from_slice(&data[..8]);
+        let mut claims = [0; 4];
+        claims.copy_from_slice(&data[8..]);
+        Window {
+            start: u64::from_le_bytes(start),
+            claims: u32::from_le_bytes(claims),
+        }
+    }
+
+    fn pack(&self) -> [u8; Self::LEN] {
+        let mut data = [0; Self::LEN];
+        data[..8].copy_from_slice(&self.start.to_le_bytes());
+        data[8..].copy_from_slice(&self.claims.to_le_bytes());
+        data
+    }
+
+    /// The window `now` falls into, claims of an elapsed window don't count anymore
+    fn current(self, limit: &RateLimit, now: u64) -> Self {
+        if now >= self.start.saturating_add(limit.window_seconds) {
+            Window {
+                start: now,
+                claims: 0,
+            }
+        } else {
+            self
+        }
+    }
+
+    /// How long until another claim is allowed, if the limit is reached
+    fn retry_after(&self, limit: &RateLimit, now: u64) -> Option<u64> {
+        if self.claims < limit.max_claims {
+            None
+        } else {
+            Some(self.start.saturating_add(limit.window_seconds) - now)
+        }
+    }
+}
+
+/// Counts claims by IP and by wallet in fixed windows, either limit may be turned off
+pub struct RateLimiter {
+    claims: sled::Tree,
+    // held from checking the windows until the claim is counted, so concurrent reservations can't race
+    lock: Mutex<()>,
+    ip_limit: Option<RateLimit>,
+    wallet_limit: Option<RateLimit>,
+}
+
+impl RateLimiter {
+    /// Opens the store at `path`, creating it if it doesn't exist yet
+    pub fn open<P: AsRef<Path>>(
+        path: P,
+        ip_limit: Option<RateLimit>,
+        wallet_limit: Option<RateLimit>,
+    ) -> Result<Self, RateLimitError> {
+        Self::new(&sled::open(path)?, ip_limit, wallet_limit)
+    }
+
+    pub fn new(
+        db: &sled::Db,
+        ip_limit: Option<RateLimit>,
+        wallet_limit: Option<RateLimit>,
+    ) -> Result<Self, RateLimitError> {
+        Ok(RateLimiter {
+            claims: db.open_tree(CLAIMS_TREE)?,
+            lock: Mutex::new(()),
+            ip_limit,
+            wallet_limit,
+        })
+    }
+
+    fn window(&self, key: &[u8], limit: &RateLimit, now: u64) -> Result<Window, RateLimitError> {
+        Ok(self
+            .claims
+            .get(key)?
+            .map_or_else(Window::default, |data| Window::unpack(&data))
+            .current(limit, now))
+    }
+
+    fn ip_key(ip: &IpAddr) -> Vec<u8> {
+        format!("ip:{}", ip).into_bytes()
+    }
+
+    fn wallet_key(wallet: &str) -> Vec<u8> {
+        format!("wallet:{}", wallet).into_bytes()
+    }
+
+    /// Fails if either the IP or the wallet has used up its claims of the current window
+    pub fn check(&self, ip: &IpAddr, wallet: &str, now: u64) -> Result<(), RateLimitError> {
+        if let Some(limit) = &self.ip_limit {
+            let window = self.window(&Self::ip_key(ip), limit, now)?;
+            if let Some(seconds) = window.retry_after(limit, now) {
+                return Err(RateLimitError::IpLimited(*ip, seconds));
+            }
+        }
+        if let Some(limit) = &self.wallet_limit {
+            let window = self.window(&Self::wallet_key(wallet), limit, now)?;
+            if let Some(seconds) = window.retry_after(limit, now) {
+                return Err(RateLimitError::WalletLimited(wallet.to_string(), seconds));
+            }
+        }
+        Ok(())
+    }
+
+    /// Counts a claim against the IP and the wallet, and flushes it to disk
+    pub async fn record(&self, ip: &IpAddr, wallet: &str, now: u64) -> Result<(), RateLimitError> {
+        self.update(ip, wallet, now, Self::add_claim)?;
+        self.flush().await
+    }
+
+    /// Checks the limits and counts the claim in one go, so that concurrent claims
+    /// can't all pass the check before any of them is counted
+    pub async fn reserve(&self, ip: &IpAddr, wallet: &str, now: u64) -> Result<(), RateLimitError> {
+        {
+            let _lock = self.lock.lock().unwrap_or_else(|err| err.into_inner());
+            self.check(ip, wallet, now)?;
+            self.update(ip, wallet, now, Self::add_claim)?;
+        }
+        self.flush().await
+    }
+
+    /// Takes back a claim reserved at `reserved_at` that didn't go through,
+    /// unless its window has elapsed in the meantime
+    pub async fn release(
+        &self,
+        ip: &IpAddr,
+        wallet: &str,
+        reserved_at: u64,
+        now: u64,
+    ) -> Result<(), RateLimitError> {
+        {
+            let _lock = self.lock.lock().unwrap_or_else(|err| err.into_inner());
+            self.update(ip, wallet, now, |window| {
+                // a window started after the reservation doesn't hold its claim
+                if window.start <= reserved_at {
+                    Window {
+                        claims: window.claims.saturating_sub(1),
+                        ..window
+                    }
+                } else {
+                    window
+                }
+            })?;
+        }
+        self.flush().await
+    }
+
+    fn add_claim(window: Window) -> Window {
+        Window {
+            claims: window.claims.saturating_add(1),
+            ..window
+        }
+    }
+
+    /// Writes the claims to disk without blocking the runtime
+    async fn flush(&self) -> Result<(), RateLimitError> {
+        self.claims.flush_async().await?;
+        Ok(())
+    }
+
+    fn update<F: Fn(Window) -> Window>(
+        &self,
+        ip: &IpAddr,
+        wallet: &str,
+        now: u64,
+        f: F,
+    ) -> Result<(), RateLimitError> {
+        for (key, limit) in &[
+            (Self::ip_key(ip), self.ip_limit),
+            (Self::wallet_key(wallet), self.wallet_limit),
+        ] {
+            if let Some(limit) = limit {
+                let window = f(self.window(key, limit, now)?);
+                self.claims.insert(key, &window.pack()[..])?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv4Addr;
+
+    const IP: IpAddr = IpAddr::V4(Ipv4Addr::LOCALHOST);
+    const WALLET: &str = "4bXpkKSV8swHSnwqtzuboGPaPDeEgAn4Vt8GfarV5rZt";
+
+    fn rate_limiter(ip_limit: Option<RateLimit>, wallet_limit: Option<RateLimit>) -> RateLimiter {
+        let db = sled::Config::new().temporary(true).open().unwrap();
+        RateLimiter::new(&db, ip_limit, wallet_limit).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_ip_limit() {
+        let limiter = rate_limiter(
+            Some(RateLimit {
+                max_claims: 2,
+                window_seconds: 60,
+            }),
+            None,
+        );
+        limiter.record(&IP, WALLET, 100).await.unwrap();
+        limiter.check(&IP, "another wallet", 110).unwrap();
+        limiter.record(&IP, "another wallet", 110).await.unwrap();
+        assert!(matches!(
+            limiter.check(&IP, "a third wallet", 130),
+            Err(RateLimitError::IpLimited(ip, 30)) if ip == IP
+        ));
+        limiter.check(&IP, "a third wallet", 160).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_wallet_limit() {
+        let limiter = rate_limiter(
+            None,
+            Some(RateLimit {
+                max_claims: 1,
+                window_seconds: 60,
+            }),
+        );
+        limiter.record(&IP, WALLET, 100).await.unwrap();
+        assert!(matches!(
+            limiter.check(&"10.0.0.1".parse().unwrap(), WALLET, 100),
+            Err(RateLimitError::WalletLimited(_, 60))
+        ));
+        limiter.check(&IP, "another wallet", 100).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_reserve_and_release() {
+        let limiter = rate_limiter(
+            None,
+            Some(RateLimit {
+                max_claims: 1,
+                window_seconds: 60,
+            }),
+        );
+        limiter.reserve(&IP, WALLET, 100).await.unwrap();
+        assert!(matches!(
+            limiter.reserve(&IP, WALLET, 110).await,
+            Err(RateLimitError::WalletLimited(_, 50))
+        ));
+        limiter.release(&IP, WALLET, 100, 115).await.unwrap();
+        limiter.reserve(&IP, WALLET, 120).await.unwrap();
+
+        // releasing a claim of an elapsed window leaves the claims of the next window alone
+        limiter.reserve(&IP, WALLET, 170).await.unwrap();
+        limiter.release(&IP, WALLET, 120, 175).await.unwrap();
+        assert!(matches!(
+            limiter.reserve(&IP, WALLET, 180).await,
+            Err(RateLimitError::WalletLimited(_, 50))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_no_limits() {
+        let limiter = rate_limiter(None, None);
+        for now in 0..10 {
+            limiter.record(&IP, WALLET, now).await.unwrap();
+        }
+        limiter.check(&IP, WALLET, 10).unwrap();
+    }
+
+    #[test]
+    fn test_window_pack() {
+        let window = Window {
+            start: 1_600_000_000,
+            claims: 3,
+        };
+        assert_eq!(Window::unpack(&window.pack()), window);
+    }
+}
@@ -0,0 +1,329 @@
+// Mark this test as BPF-only due to current `ProgramTest` limitations when CPIing into the system program.
+// `BPF_OUT_DIR` has to point at the build of the program, see `npm run test:server`
+#![cfg(feature = "test-bpf")]
+
+use serde_json::{json, Value};
+use solana_program_test::*;
+use solana_sdk::{
+    account::Account, program_option::COption, program_pack::Pack, pubkey::Pubkey, rent::Rent,
+};
+use spl_associated_token_account::get_associated_token_address;
+use spl_token_faucet::{
+    client::FaucetClient, find_mint_authority_address, id, processor, state::Faucet,
+};
+use spl_token_faucet_server::{
+    rate_limit::{RateLimit, RateLimiter},
+    Server,
+};
+use std::{
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+};
+
+const PER_DAY: u64 = 86400;
+
+fn program_test() -> ProgramTest {
+    let mut pc = ProgramTest::new(
+        "spl_token_faucet",
+        id(),
+        processor!(processor::Processor::process),
+    );
+
+    // Add SPL Token program
+    pc.add_program(
+        "spl_token",
+        spl_token::id(),
+        processor!(spl_token::processor::Processor::process),
+    );
+
+    // Add Associated Token Account program
+    pc.add_program(
+        "spl_associated_token_account",
+        spl_associated_token_account::id(),
+        processor!(spl_associated_token_account::processor::process_instruction),
+    );
+
+    pc
+}
+
+fn mint_account(mint_authority: &Pubkey) -> Account {
+    let mut account = Account::new(
+        Rent::default().minimum_balance(spl_token::state::Mint::LEN),
+        spl_token::state::Mint::LEN,
+        &spl_token::id(),
+    );
+    spl_token::state::Mint {
+        mint_authority: COption::Some(*mint_authority),
+        is_initialized: true,
+        ..spl_token::state::Mint::default()
+    }
+    .pack_into_slice(&mut account.data);
+    account
+}
+
+fn token_account(mint: &Pubkey, owner: &Pubkey) -> Account {
+    let mut account = Account::new(
+        Rent::default().minimum_balance(spl_token::state::Account::LEN),
+        spl_token::state::Account::LEN,
+        &spl_token::id(),
+    );
+    spl_token::state::Account {
+        mint: *mint,
+        owner: *owner,
+        state: spl_token::state::AccountState::Initialized,
+        ..spl_token::state::Account::default()
+    }
+    .pack_into_slice(&mut account.data);
+    account
+}
+
+/// A faucet that mints 100 tokens per claim to a new mint
+fn add_faucet(pc: &mut ProgramTest, paused: bool) -> (Pubkey, Pubkey) {
+    let faucet = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    let mut account = Account::new(
+        Rent::default().minimum_balance(Faucet::LEN),
+        Faucet::LEN,
+        &id(),
+    );
+    Faucet::pack(
+        Faucet {
+            is_initialized: true,
+            mint,
+            amount: 100,
+            paused,
+            ..Faucet::default()
+        },
+        &mut account.data,
+    )
+    .unwrap();
+    pc.add_account(faucet, account);
+    let (mint_authority, _nonce) = find_mint_authority_address(&id(), &faucet);
+    pc.add_account(mint, mint_account(&mint_authority));
+    (faucet, mint)
+}
+
+/// Starts a server in front of the bank, `record` is called with a rate limiter on the server's store first
+async fn start_server(
+    pc: ProgramTest,
+    faucet: &Pubkey,
+    ip_limit: Option<RateLimit>,
+    wallet_limit: Option<RateLimit>,
+    claims: &[(IpAddr, String, u64)],
+) -> (SocketAddr, BanksClient) {
+    let (banks_client, payer, _recent_blockhash) = pc.start().await;
+    let db = sled::Config::new().temporary(true).open().unwrap();
+    let limiter = RateLimiter::new(&db, ip_limit, wallet_limit).unwrap();
+    for (ip, wallet, now) in claims {
+        limiter.record(ip, wallet, *now).await.unwrap();
+    }
+    let server = Server::new(
+        FaucetClient::new(banks_client.clone()),
+        payer,
+        &[*faucet],
+        RateLimiter::new(&db, ip_limit, wallet_limit).unwrap(),
+    )
+    .await
+    .unwrap();
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    tokio::spawn(Arc::new(server).run(listener, 16));
+    (address, banks_client)
+}
+
+async fn post(address: SocketAddr, path: &str, body: &str) -> (u16, Value) {
+    let mut stream = TcpStream::connect(address).await.unwrap();
+    let request = format!(
+        "POST {} HTTP/1.1\r\nHost: localhost\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+        path,
+        body.len(),
+        body
+    );
+    stream.write_all(request.as_bytes()).await.unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+    let status = response[9..12].parse().unwrap();
+    let body = &response[response.find("\r\n\r\n").unwrap() + 4..];
+    (status, serde_json::from_str(body).unwrap_or(Value::Null))
+}
+
+async fn claim(address: SocketAddr, mint: &Pubkey, wallet: &Pubkey) -> (u16, Value) {
+    let body = json!({ "mint": mint.to_string(), "wallet": wallet.to_string() });
+    post(address, "/claim", &body.to_string()).await
+}
+
+async fn balance(banks_client: &mut BanksClient, mint: &Pubkey, wallet: &Pubkey) -> u64 {
+    let account = banks_client
+        .get_account(get_associated_token_address(wallet, mint))
+        .await
+        .unwrap()
+        .unwrap();
+    spl_token::state::Account::unpack(&account.data)
+        .unwrap()
+        .amount
+}
+
+#[tokio::test]
+async fn test_happy_flow_claim() {
+    // GIVEN
+    let mut pc = program_test();
+    let (faucet, mint) = add_faucet(&mut pc, false);
+    let (address, mut banks_client) = start_server(pc, &faucet, None, None, &[]).await;
+    let wallet = Pubkey::new_unique();
+
+    // WHEN
+    let (status, body) = claim(address, &mint, &wallet).await;
+
+    // THEN
+    assert_eq!(status, 200);
+    assert!(body["signature"].is_string());
+    assert_eq!(balance(&mut banks_client, &mint, &wallet).await, 100);
+}
+
+#[tokio::test]
+async fn test_claim_ip_limit() {
+    // GIVEN
+    let mut pc = program_test();
+    let (faucet, mint) = add_faucet(&mut pc, false);
+    let ip_limit = RateLimit {
+        max_claims: 1,
+        window_seconds: PER_DAY,
+    };
+    let (address, mut banks_client) = start_server(pc, &faucet, Some(ip_limit), None, &[]).await;
+    let wallet = Pubkey::new_unique();
+    let (status, _body) = claim(address, &mint, &wallet).await;
+    assert_eq!(status, 200);
+
+    // WHEN
+    let (status, _body) = claim(address, &mint, &Pubkey::new_unique()).await;
+
+    // THEN
+    assert_eq!(status, 429);
+    assert_eq!(balance(&mut banks_client, &mint, &wallet).await, 100);
+}
+
+#[tokio::test]
+async fn test_claim_wallet_limit() {
+    // GIVEN
+    let mut pc = program_test();
+    let (faucet, mint) = add_faucet(&mut pc, false);
+    let wallet = Pubkey::new_unique();
+    let wallet_limit = RateLimit {
+        max_claims: 1,
+        window_seconds: PER_DAY,
+    };
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let claims = [("10.0.0.1".parse().unwrap(), wallet.to_string(), now)];
+    let (address, _banks_client) =
+        start_server(pc, &faucet, None, Some(wallet_limit), &claims).await;
+
+    // WHEN
+    let (status, body) = claim(address, &mint, &wallet).await;
+
+    // THEN
+    assert_eq!(status, 429);
+    assert!(body["error"]
+        .as_str()
+        .unwrap()
+        .starts_with(&format!("Too many claims to {}", wallet)));
+}
+
+#[tokio::test]
+async fn test_claim_paused_faucet() {
+    // GIVEN
+    let mut pc = program_test();
+    let (faucet, mint) = add_faucet(&mut pc, true);
+    let wallet = Pubkey::new_unique();
+    pc.add_account(
+        get_associated_token_address(&wallet, &mint),
+        token_account(&mint, &wallet),
+    );
+    let (address, _banks_client) = start_server(pc, &faucet, None, None, &[]).await;
+
+    // WHEN
+    let (status, body) = claim(address, &mint, &wallet).await;
+
+    // THEN
+    assert_eq!(status, 400);
+    assert_eq!(body, json!({ "error": "Faucet error: Faucet Paused" }));
+}
+
+#[tokio::test]
+async fn test_failed_claim_not_counted() {
+    // GIVEN
+    let mut pc = program_test();
+    let (faucet, mint) = add_faucet(&mut pc, true);
+    let wallet = Pubkey::new_unique();
+    pc.add_account(
+        get_associated_token_address(&wallet, &mint),
+        token_account(&mint, &wallet),
+    );
+    let wallet_limit = RateLimit {
+        max_claims: 1,
+        window_seconds: PER_DAY,
+    };
+    let (address, _banks_client) = start_server(pc, &faucet, None, Some(wallet_limit), &[]).await;
+    let (status, _body) = claim(address, &mint, &wallet).await;
+    assert_eq!(status, 400);
+
+    // WHEN
+    let (status, body) = claim(address, &mint, &wallet).await;
+
+    // THEN
+    assert_eq!(status, 400);
+    assert_eq!(body, json!({ "error": "Faucet error: Faucet Paused" }));
+}
+
+#[tokio::test]
+async fn test_claim_unknown_mint() {
+    // GIVEN
+    let mut pc = program_test();
+    let (faucet, _mint) = add_faucet(&mut pc, false);
+    let (address, _banks_client) = start_server(pc, &faucet, None, None, &[]).await;
+    let mint = Pubkey::new_unique();
+
+    // WHEN
+    let (status, body) = claim(address, &mint, &Pubkey::new_unique()).await;
+
+    // THEN
+    assert_eq!(status, 404);
+    assert_eq!(
+        body,
+        json!({ "error": format!("No faucet for mint {}", mint) })
+    );
+}
+
+#[tokio::test]
+async fn test_claim_invalid_request() {
+    // GIVEN
+    let mut pc = program_test();
+    let (faucet, mint) = add_faucet(&mut pc, false);
+    let (address, _banks_client) = start_server(pc, &faucet, None, None, &[]).await;
+
+    // WHEN
+    let (invalid_wallet, _body) = post(
+        address,
+        "/claim",
+        &json!({ "mint": mint.to_string(), "wallet": "not a wallet" }).to_string(),
+    )
+    .await;
+    let (missing_wallet, _body) = post(
+        address,
+        "/claim",
+        &json!({ "mint": mint.to_string() }).to_string(),
+    )
+    .await;
+    let (unknown_path, _body) = post(address, "/mint", "{}").await;
+
+    // THEN
+    assert_eq!(invalid_wallet, 400);
+    assert_eq!(missing_wallet, 400);
+    assert_eq!(unknown_path, 404);
+}